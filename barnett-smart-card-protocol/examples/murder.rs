@@ -4,15 +4,16 @@ use ark_serialize::CanonicalSerialize;
 use barnett_smart_card_protocol::discrete_log_cards;
 use barnett_smart_card_protocol::BarnettSmartProtocol;
 
-use anyhow;
 use ark_ff::UniformRand;
 use ark_std::cmp;
 use ark_std::collections::HashMap;
 use ark_std::iter::Iterator;
-use ark_std::{rand::Rng, One};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::One;
 use proof_essentials::homomorphic_encryption::el_gamal::ElGamal;
 use proof_essentials::utils::permutation::Permutation;
 use proof_essentials::utils::rand::sample_vector;
+use proof_essentials::utils::secret::Secret;
 use proof_essentials::vector_commitment::pedersen::PedersenCommitment;
 use proof_essentials::zkp::arguments::shuffle::proof::Proof;
 use proof_essentials::zkp::proofs::{chaum_pedersen_dl_equality, schnorr_identification};
@@ -27,6 +28,7 @@ type Scalar = ark_bn254::Fr;
 // Instantiate concrete type for our card protocol
 type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
 type CardParameters = discrete_log_cards::Parameters<Curve>;
+type SessionContext = discrete_log_cards::SessionContext<Curve>;
 type PublicKey = discrete_log_cards::PublicKey<Curve>;
 type SecretKey = discrete_log_cards::PlayerSecretKey<Curve>;
 type AggregatePublicKey = ark_bn254::G1Affine;
@@ -88,9 +90,14 @@ pub struct Player {
 }
 
 impl Player {
-    pub fn new<R: Rng>(rng: &mut R, pp: &CardParameters, name: &Vec<u8>) -> anyhow::Result<Self> {
+    pub fn new<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &CardParameters,
+        ctx: &SessionContext,
+        name: &Vec<u8>,
+    ) -> anyhow::Result<Self> {
         let (pk, sk) = CardProtocol::player_keygen(rng, pp)?;
-        let proof_key = CardProtocol::prove_key_ownership(rng, pp, &pk, &sk, name)?;
+        let proof_key = CardProtocol::prove_key_ownership(rng, pp, ctx, &pk, &sk, name)?;
         Ok(Self {
             name: name.clone(),
             sk,
@@ -113,6 +120,7 @@ impl Player {
     pub fn peek_at_card(
         &mut self,
         parameters: &CardParameters,
+        ctx: &SessionContext,
         mut reveal_tokens: Vec<(RevealToken, RevealProof, PublicKey)>,
         card_mappings: &HashMap<Card, MurderCard>,
         card: &MaskedCard,
@@ -123,10 +131,10 @@ impl Player {
 
         //TODO add function to create that without the proof
         let rng = &mut thread_rng();
-        let own_reveal_token = self.compute_reveal_token(rng, parameters, card)?;
+        let own_reveal_token = self.compute_reveal_token(rng, parameters, ctx, card)?;
         reveal_tokens.push(own_reveal_token);
 
-        let unmasked_card = CardProtocol::unmask(&parameters, &reveal_tokens, card)?;
+        let unmasked_card = CardProtocol::unmask(parameters, ctx, &reveal_tokens, card)?;
         let opened_card = card_mappings.get(&unmasked_card);
         let opened_card = opened_card.ok_or(GameErrors::InvalidCard)?;
 
@@ -137,53 +145,57 @@ impl Player {
     pub fn peek_my_cards(
         &mut self,
         parameters: &CardParameters,
+        ctx: &SessionContext,
         card_mappings: &HashMap<Card, MurderCard>,
         collection: &HashMap<MaskedCard, Vec<(RevealToken, RevealProof, PublicKey)>>,
     ) -> Result<(), anyhow::Error> {
         for card in self.cards.clone() {
             let reveal_tokens = collection.get(&card).ok_or(GameErrors::CardNotFound)?;
-            self.peek_at_card(parameters, reveal_tokens.clone(), card_mappings, &card)?;
+            self.peek_at_card(parameters, ctx, reveal_tokens.clone(), card_mappings, &card)?;
         }
         Ok(())
     }
 
-    pub fn compute_reveal_token<R: Rng>(
+    pub fn compute_reveal_token<R: CryptoRng + RngCore>(
         &self,
         rng: &mut R,
         pp: &CardParameters,
+        ctx: &SessionContext,
         card: &MaskedCard,
     ) -> anyhow::Result<(RevealToken, RevealProof, PublicKey)> {
         let (reveal_token, reveal_proof) =
-            CardProtocol::compute_reveal_token(rng, &pp, &self.sk, &self.pk, card)?;
+            CardProtocol::compute_reveal_token(rng, pp, ctx, &self.sk, &self.pk, card)?;
 
         Ok((reveal_token, reveal_proof, self.pk))
     }
 
-    pub fn compute_my_reveal_tokens<R: Rng>(
+    pub fn compute_my_reveal_tokens<R: CryptoRng + RngCore>(
         &self,
         rng: &mut R,
         pp: &CardParameters,
+        ctx: &SessionContext,
     ) -> anyhow::Result<HashMap<MaskedCard, (RevealToken, RevealProof, PublicKey)>> {
         let mut reveal_tokens = HashMap::new();
         for card in self.cards.clone() {
             let (reveal_token, reveal_proof) =
-                CardProtocol::compute_reveal_token(rng, &pp, &self.sk, &self.pk, &card)?;
+                CardProtocol::compute_reveal_token(rng, pp, ctx, &self.sk, &self.pk, &card)?;
             reveal_tokens.insert(card, (reveal_token, reveal_proof, self.pk));
         }
         Ok(reveal_tokens)
     }
 
-    pub fn compute_others_reveal_tokens<R: Rng>(
+    pub fn compute_others_reveal_tokens<R: CryptoRng + RngCore>(
         &self,
         rng: &mut R,
         pp: &CardParameters,
+        ctx: &SessionContext,
         deck: &Vec<MaskedCard>,
     ) -> anyhow::Result<HashMap<MaskedCard, (RevealToken, RevealProof, PublicKey)>> {
         let mut reveal_tokens = HashMap::new();
         for card in deck {
             if !self.cards.contains(card) {
                 let (reveal_token, reveal_proof) =
-                    CardProtocol::compute_reveal_token(rng, &pp, &self.sk, &self.pk, card)?;
+                    CardProtocol::compute_reveal_token(rng, pp, ctx, &self.sk, &self.pk, card)?;
                 reveal_tokens.insert(*card, (reveal_token, reveal_proof, self.pk));
             }
         }
@@ -198,7 +210,7 @@ pub fn collect_reveal_tokens(
 ) {
     for (card, token) in reveal_tokens {
         if deck.contains(&card) {
-            let tokens = collection.entry(card).or_insert(Vec::new());
+            let tokens = collection.entry(card).or_default();
             if !tokens.contains(&token) {
                 tokens.push(token);
             }
@@ -209,6 +221,7 @@ pub fn collect_reveal_tokens(
 // Every player will have to calculate this function for cards that are in play
 pub fn open_cards(
     parameters: &CardParameters,
+    ctx: &SessionContext,
     cards: Vec<MaskedCard>,
     card_mappings: &HashMap<Card, MurderCard>,
     collection: &HashMap<MaskedCard, Vec<(RevealToken, RevealProof, PublicKey)>>,
@@ -217,10 +230,10 @@ pub fn open_cards(
 
     for card in cards {
         let reveal_tokens = collection.get(&card).ok_or(GameErrors::CardNotFound)?;
-        let unmasked_card = CardProtocol::unmask(&parameters, reveal_tokens, &card)?;
+        let unmasked_card = CardProtocol::unmask(parameters, ctx, reveal_tokens, &card)?;
         let opened_card = card_mappings.get(&unmasked_card);
         let opened_card = opened_card.ok_or(GameErrors::InvalidCard)?;
-        opened_cards.insert(card, opened_card.clone());
+        opened_cards.insert(card, *opened_card);
     }
     Ok(opened_cards)
 }
@@ -232,13 +245,13 @@ pub fn print_cards(
 ) {
     print!("{} ", name);
     for masked in cards {
-        let opened_card = mappings.get(&masked).unwrap();
+        let opened_card = mappings.get(masked).unwrap();
         print!(" {:?}", opened_card);
     }
-    println!("");
+    println!();
 }
 
-fn encode_cards<R: Rng>(
+fn encode_cards<R: CryptoRng + RngCore>(
     rng: &mut R,
     num_of_cards: usize,
 ) -> (Vec<Card>, HashMap<Card, MurderCard>) {
@@ -248,9 +261,9 @@ fn encode_cards<R: Rng>(
         .collect::<Vec<_>>();
 
     // 4 guests
-    for i in 0..40 {
+    for &plaintext in plaintexts.iter().take(40) {
         let greet_card = MurderCard::new(Suite::Greet);
-        map.insert(plaintexts[i], greet_card);
+        map.insert(plaintext, greet_card);
     }
 
     // 2 killers
@@ -270,7 +283,11 @@ fn encode_cards<R: Rng>(
     (plaintexts, map)
 }
 
-fn chunk_permutation<R: Rng>(rng: &mut R, size: usize, chunk_size: usize) -> Vec<usize> {
+fn chunk_permutation<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    size: usize,
+    chunk_size: usize,
+) -> Vec<usize> {
     // permutation of cards
     let mut chunks = Vec::new();
     for i in (0..size).step_by(chunk_size) {
@@ -281,31 +298,30 @@ fn chunk_permutation<R: Rng>(rng: &mut R, size: usize, chunk_size: usize) -> Vec
 
     // permutation of chunks
     let permutation_chunk = Permutation::new(rng, size / chunk_size);
-    let permuted_chunks: Vec<Vec<usize>> = permutation_chunk
-        .mapping
-        .iter()
-        .map(|&pi| chunks[pi].clone())
-        .collect();
-    permuted_chunks.into_iter().flatten().collect()
+    let chunks = chunks.iter().collect::<Vec<_>>();
+    let permuted_chunks = permutation_chunk.permute_array(&chunks);
+    permuted_chunks.into_iter().flatten().copied().collect()
 }
 
 // Shuffle cards by chunks (4 guest chunks and 2 killer chunks)
-pub fn shuffle_chunks<R: Rng>(
+pub fn shuffle_chunks<R: CryptoRng + RngCore>(
     rng: &mut R,
     pp: &CardParameters,
+    ctx: &SessionContext,
     shared_key: &AggregatePublicKey,
     deck: &Vec<MaskedCard>,
     chunk_size: usize,
 ) -> anyhow::Result<(Vec<MaskedCard>, ShuffleProof)> {
     let permutation_vec = chunk_permutation(rng, deck.len(), chunk_size);
     let permutation = Permutation::from(&permutation_vec);
-    let masking_factors: Vec<Scalar> = sample_vector(rng, deck.len());
+    let masking_factors = Secret::new(sample_vector(rng, deck.len()));
 
     let (shuffled_deck, shuffle_proof) = CardProtocol::shuffle_and_remask(
         rng,
-        &pp,
-        &shared_key,
-        &deck,
+        pp,
+        ctx,
+        shared_key,
+        deck,
         &masking_factors,
         &permutation,
     )?;
@@ -313,10 +329,10 @@ pub fn shuffle_chunks<R: Rng>(
     Ok((shuffled_deck, shuffle_proof))
 }
 
-pub fn draw_cards(player: &mut Player, deck: &Vec<MaskedCard>, amount: usize, offset: usize) {
+pub fn draw_cards(player: &mut Player, deck: &[MaskedCard], amount: usize, offset: usize) {
     let end: usize = cmp::min(deck.len(), offset + amount);
-    for i in offset..end {
-        player.receive_card(deck[i]);
+    for &card in &deck[offset..end] {
+        player.receive_card(card);
     }
 }
 
@@ -330,19 +346,20 @@ fn main() -> anyhow::Result<()> {
     let rng = &mut thread_rng();
 
     let parameters = CardProtocol::setup(rng, m, n)?;
+    let registration_ctx = SessionContext::new(b"murder", 0, &parameters);
     let (encoded_cards, card_mappings) = encode_cards(rng, num_of_cards);
 
     // Each player creates a game key offline to `joinGame()` and smart contract run `verify_key_ownship()` on chain.
     // Note: If a game key is securely stored, it can be reused; however, if the key is leaked, it must be replaced.
-    let mut andrija = Player::new(rng, &parameters, &b"Andrija".to_vec())?;
-    let mut kobi = Player::new(rng, &parameters, &b"Kobi".to_vec())?;
-    let mut nico = Player::new(rng, &parameters, &b"Nico".to_vec())?;
-    let mut tom = Player::new(rng, &parameters, &b"Tom".to_vec())?;
-    let mut jay = Player::new(rng, &parameters, &b"Jay".to_vec())?;
-    let mut bob = Player::new(rng, &parameters, &b"Bob".to_vec())?;
+    let mut andrija = Player::new(rng, &parameters, &registration_ctx, &b"Andrija".to_vec())?;
+    let mut kobi = Player::new(rng, &parameters, &registration_ctx, &b"Kobi".to_vec())?;
+    let mut nico = Player::new(rng, &parameters, &registration_ctx, &b"Nico".to_vec())?;
+    let mut tom = Player::new(rng, &parameters, &registration_ctx, &b"Tom".to_vec())?;
+    let mut jay = Player::new(rng, &parameters, &registration_ctx, &b"Jay".to_vec())?;
+    let mut bob = Player::new(rng, &parameters, &registration_ctx, &b"Bob".to_vec())?;
 
     // Smart contract computes aggregation key on chain once all players have joined the game
-    let players = vec![
+    let players = [
         andrija.clone(),
         kobi.clone(),
         nico.clone(),
@@ -354,13 +371,17 @@ fn main() -> anyhow::Result<()> {
         .iter()
         .map(|p| (p.pk, p.proof_key, p.name.clone()))
         .collect::<Vec<_>>();
-    let shared_key = CardProtocol::compute_aggregate_key(&parameters, &key_proof_info)?;
+    let roster = CardProtocol::register_players(&parameters, &registration_ctx, &key_proof_info)?;
+    let shared_key = *roster.aggregate_key();
+
+    // All proofs for the hand are bound to the table, the hand number and the player roster
+    let ctx = registration_ctx.with_roster(&roster.public_keys());
 
     // Smart contract creates initial deck on chain.
     // Also, each player should run this computation and verify offline so that all players agree on the initial deck.
     let mut deck_and_proofs: Vec<(MaskedCard, RemaskingProof)> = encoded_cards
         .iter()
-        .map(|card| CardProtocol::mask(rng, &parameters, &shared_key, &card, &Scalar::one()))
+        .map(|card| CardProtocol::mask(rng, &parameters, &ctx, &shared_key, card, &Scalar::one()))
         .collect::<Result<Vec<_>, _>>()?;
 
     deck_and_proofs = deck_and_proofs
@@ -370,7 +391,7 @@ fn main() -> anyhow::Result<()> {
             let mut data = Vec::with_capacity(proof.compressed_size());
             proof.serialize_compressed(&mut data).unwrap();
             proof = CanonicalDeserialize::deserialize_compressed(data.as_slice()).unwrap();
-            (vdeck_and_proofs.0, proof.clone())
+            (vdeck_and_proofs.0, proof)
         })
         .collect();
 
@@ -383,11 +404,12 @@ fn main() -> anyhow::Result<()> {
     // 1.a Andrija shuffles first.
     //     Andrija shuffles deck offline and then calls `shuffleCards()` to put shuffled deck and proofs on chain.
     let (a_shuffled_deck, a_shuffle_proofs) =
-        shuffle_chunks(rng, &parameters, &shared_key, &deck, chunk_size)?;
+        shuffle_chunks(rng, &parameters, &ctx, &shared_key, &deck, chunk_size)?;
 
     // 1.b Smart contract checks the shuffle proofs!
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &shared_key,
         &deck,
         &a_shuffled_deck,
@@ -396,12 +418,19 @@ fn main() -> anyhow::Result<()> {
 
     //2.a Kobi shuffles second
     //    Kobi shuffles deck offline and then calls `shuffleCards()` to put shuffled deck and proof on chain.
-    let (k_shuffled_deck, k_shuffle_proofs) =
-        shuffle_chunks(rng, &parameters, &shared_key, &a_shuffled_deck, chunk_size)?;
+    let (k_shuffled_deck, k_shuffle_proofs) = shuffle_chunks(
+        rng,
+        &parameters,
+        &ctx,
+        &shared_key,
+        &a_shuffled_deck,
+        chunk_size,
+    )?;
 
     //2.b Smart contract checks the shuffle proofs!
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &shared_key,
         &a_shuffled_deck,
         &k_shuffled_deck,
@@ -410,12 +439,19 @@ fn main() -> anyhow::Result<()> {
 
     //3.a Nico shuffles third
     //    Nico shuffles deck offline and then calls `shuffleCards()` to put shuffled deck and proof on chain.
-    let (n_shuffled_deck, n_shuffle_proofs) =
-        shuffle_chunks(rng, &parameters, &shared_key, &k_shuffled_deck, chunk_size)?;
+    let (n_shuffled_deck, n_shuffle_proofs) = shuffle_chunks(
+        rng,
+        &parameters,
+        &ctx,
+        &shared_key,
+        &k_shuffled_deck,
+        chunk_size,
+    )?;
 
     //3.b Smart contract checks the shuffle proofs!
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &shared_key,
         &k_shuffled_deck,
         &n_shuffled_deck,
@@ -424,12 +460,19 @@ fn main() -> anyhow::Result<()> {
 
     //4.a Tom shuffles fourth
     //    Tom shuffles deck offline and then calls `shuffleCards()` to put shuffled deck and proof on chain.
-    let (t_shuffled_deck, t_shuffle_proofs) =
-        shuffle_chunks(rng, &parameters, &shared_key, &n_shuffled_deck, chunk_size)?;
+    let (t_shuffled_deck, t_shuffle_proofs) = shuffle_chunks(
+        rng,
+        &parameters,
+        &ctx,
+        &shared_key,
+        &n_shuffled_deck,
+        chunk_size,
+    )?;
 
     //4.b Smart contract checks the shuffle proofs!
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &shared_key,
         &n_shuffled_deck,
         &t_shuffled_deck,
@@ -438,12 +481,19 @@ fn main() -> anyhow::Result<()> {
 
     //5.a Jay shuffles fifth
     //    Jay shuffles deck offline and then calls `shuffleCards()` to put shuffled deck and proof on chain.
-    let (j_shuffled_deck, j_shuffle_proofs) =
-        shuffle_chunks(rng, &parameters, &shared_key, &t_shuffled_deck, chunk_size)?;
+    let (j_shuffled_deck, j_shuffle_proofs) = shuffle_chunks(
+        rng,
+        &parameters,
+        &ctx,
+        &shared_key,
+        &t_shuffled_deck,
+        chunk_size,
+    )?;
 
     //5.b Smart contract checks the shuffle proofs!
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &shared_key,
         &t_shuffled_deck,
         &j_shuffled_deck,
@@ -452,12 +502,19 @@ fn main() -> anyhow::Result<()> {
 
     //6.a Bob shuffles last
     //    Bob shuffles deck offline and then calls `shuffleCards()` to put shuffled deck and proof on chain.
-    let (b_shuffled_deck, b_shuffle_proofs) =
-        shuffle_chunks(rng, &parameters, &shared_key, &j_shuffled_deck, chunk_size)?;
+    let (b_shuffled_deck, b_shuffle_proofs) = shuffle_chunks(
+        rng,
+        &parameters,
+        &ctx,
+        &shared_key,
+        &j_shuffled_deck,
+        chunk_size,
+    )?;
 
     //5.b Smart contract checks the shuffle proofs!
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &shared_key,
         &j_shuffled_deck,
         &b_shuffled_deck,
@@ -470,7 +527,7 @@ fn main() -> anyhow::Result<()> {
     // DRAW LOTS --------------
     // Each player `drawLots()` to get a chunk of cards in a row.
     draw_cards(&mut andrija, &deck, chunk_size, 0);
-    draw_cards(&mut kobi, &deck, chunk_size, chunk_size * 1);
+    draw_cards(&mut kobi, &deck, chunk_size, chunk_size);
     draw_cards(&mut nico, &deck, chunk_size, chunk_size * 2);
     draw_cards(&mut tom, &deck, chunk_size, chunk_size * 3);
     draw_cards(&mut jay, &deck, chunk_size, chunk_size * 4);
@@ -478,22 +535,22 @@ fn main() -> anyhow::Result<()> {
 
     // Each player computes reveal tokens offline
     let rts_andrija = andrija
-        .compute_others_reveal_tokens(rng, &parameters, &deck)
+        .compute_others_reveal_tokens(rng, &parameters, &ctx, &deck)
         .unwrap();
     let rts_kobi = kobi
-        .compute_others_reveal_tokens(rng, &parameters, &deck)
+        .compute_others_reveal_tokens(rng, &parameters, &ctx, &deck)
         .unwrap();
     let rts_nico = nico
-        .compute_others_reveal_tokens(rng, &parameters, &deck)
+        .compute_others_reveal_tokens(rng, &parameters, &ctx, &deck)
         .unwrap();
     let rts_tom = tom
-        .compute_others_reveal_tokens(rng, &parameters, &deck)
+        .compute_others_reveal_tokens(rng, &parameters, &ctx, &deck)
         .unwrap();
     let rts_jay = jay
-        .compute_others_reveal_tokens(rng, &parameters, &deck)
+        .compute_others_reveal_tokens(rng, &parameters, &ctx, &deck)
         .unwrap();
     let rts_bob = bob
-        .compute_others_reveal_tokens(rng, &parameters, &deck)
+        .compute_others_reveal_tokens(rng, &parameters, &ctx, &deck)
         .unwrap();
 
     // Each player sends 50 reveal tokens (of other players' cards) to coordinator.
@@ -508,12 +565,12 @@ fn main() -> anyhow::Result<()> {
     collect_reveal_tokens(&deck, rts_bob, &mut collected_tokens);
 
     //At this moment players privately open their cards and they only know their own cards.
-    andrija.peek_my_cards(&parameters, &card_mappings, &collected_tokens)?;
-    kobi.peek_my_cards(&parameters, &card_mappings, &collected_tokens)?;
-    nico.peek_my_cards(&parameters, &card_mappings, &collected_tokens)?;
-    tom.peek_my_cards(&parameters, &card_mappings, &collected_tokens)?;
-    jay.peek_my_cards(&parameters, &card_mappings, &collected_tokens)?;
-    bob.peek_my_cards(&parameters, &card_mappings, &collected_tokens)?;
+    andrija.peek_my_cards(&parameters, &ctx, &card_mappings, &collected_tokens)?;
+    kobi.peek_my_cards(&parameters, &ctx, &card_mappings, &collected_tokens)?;
+    nico.peek_my_cards(&parameters, &ctx, &card_mappings, &collected_tokens)?;
+    tom.peek_my_cards(&parameters, &ctx, &card_mappings, &collected_tokens)?;
+    jay.peek_my_cards(&parameters, &ctx, &card_mappings, &collected_tokens)?;
+    bob.peek_my_cards(&parameters, &ctx, &card_mappings, &collected_tokens)?;
 
     /* Here we can add custom logic of a game:
         1. play card
@@ -524,12 +581,24 @@ fn main() -> anyhow::Result<()> {
     //At this moment players reveal their cards to each other and everything becomes public.
 
     //1. everyone computes reveal tokens for their own cards.
-    let rts_andrija = andrija.compute_my_reveal_tokens(rng, &parameters).unwrap();
-    let rts_kobi = kobi.compute_my_reveal_tokens(rng, &parameters).unwrap();
-    let rts_nico = nico.compute_my_reveal_tokens(rng, &parameters).unwrap();
-    let rts_tom = tom.compute_my_reveal_tokens(rng, &parameters).unwrap();
-    let rts_jay = jay.compute_my_reveal_tokens(rng, &parameters).unwrap();
-    let rts_bob = bob.compute_my_reveal_tokens(rng, &parameters).unwrap();
+    let rts_andrija = andrija
+        .compute_my_reveal_tokens(rng, &parameters, &ctx)
+        .unwrap();
+    let rts_kobi = kobi
+        .compute_my_reveal_tokens(rng, &parameters, &ctx)
+        .unwrap();
+    let rts_nico = nico
+        .compute_my_reveal_tokens(rng, &parameters, &ctx)
+        .unwrap();
+    let rts_tom = tom
+        .compute_my_reveal_tokens(rng, &parameters, &ctx)
+        .unwrap();
+    let rts_jay = jay
+        .compute_my_reveal_tokens(rng, &parameters, &ctx)
+        .unwrap();
+    let rts_bob = bob
+        .compute_my_reveal_tokens(rng, &parameters, &ctx)
+        .unwrap();
 
     //2. Each player sends 10 reveal tokens (of their own cards) to coordinator.
     collect_reveal_tokens(&deck, rts_andrija, &mut collected_tokens);
@@ -542,15 +611,46 @@ fn main() -> anyhow::Result<()> {
     //3. Coordinator opens cards in play on chain.
     let andrija_cards = open_cards(
         &parameters,
+        &ctx,
         andrija.cards(),
         &card_mappings,
         &collected_tokens,
     )?;
-    let kobi_cards = open_cards(&parameters, kobi.cards(), &card_mappings, &collected_tokens)?;
-    let nico_cards = open_cards(&parameters, nico.cards(), &card_mappings, &collected_tokens)?;
-    let tom_cards = open_cards(&parameters, tom.cards(), &card_mappings, &collected_tokens)?;
-    let jay_cards = open_cards(&parameters, jay.cards(), &card_mappings, &collected_tokens)?;
-    let bob_cards = open_cards(&parameters, bob.cards(), &card_mappings, &collected_tokens)?;
+    let kobi_cards = open_cards(
+        &parameters,
+        &ctx,
+        kobi.cards(),
+        &card_mappings,
+        &collected_tokens,
+    )?;
+    let nico_cards = open_cards(
+        &parameters,
+        &ctx,
+        nico.cards(),
+        &card_mappings,
+        &collected_tokens,
+    )?;
+    let tom_cards = open_cards(
+        &parameters,
+        &ctx,
+        tom.cards(),
+        &card_mappings,
+        &collected_tokens,
+    )?;
+    let jay_cards = open_cards(
+        &parameters,
+        &ctx,
+        jay.cards(),
+        &card_mappings,
+        &collected_tokens,
+    )?;
+    let bob_cards = open_cards(
+        &parameters,
+        &ctx,
+        bob.cards(),
+        &card_mappings,
+        &collected_tokens,
+    )?;
 
     print_cards(String::from("Andrija: "), &andrija.cards(), &andrija_cards);
     print_cards(String::from("Kobi:    "), &kobi.cards(), &kobi_cards);
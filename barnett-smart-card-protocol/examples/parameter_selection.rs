@@ -2,7 +2,8 @@
 //! As per the Bayer-Groth paper, for a deck of $N = m \times n$ cards:
 //! - the prover performs m*N exponentiations
 //! - the proof is approximately 6m*|G|+4n*|Z| where |G| is the size of a EC point and |Z| is the size of a scalar
-//! (note that this is because we are not using the FFT-like improvement suggested in the paper)
+//!   (note that this is because the shuffle uses the default `DiagonalStrategy::Direct` of the multi-exponentiation
+//!   argument, `DiagonalStrategy::Interpolation` implements the improvement suggested in the paper)
 //!
 //! Analysis: increasing m will always increase the prover time. Assuming |G| ≈≈ 2*|Z|, proof size is approx 12m+4n and will
 //! be minimised when m ≈≈ n/3.
@@ -18,7 +19,8 @@ use barnett_smart_card_protocol::discrete_log_cards::MaskedCard;
 use barnett_smart_card_protocol::{discrete_log_cards, BarnettSmartProtocol};
 use proof_essentials::utils::permutation::Permutation;
 use proof_essentials::utils::rand::sample_vector;
-use rand::{thread_rng, Rng};
+use proof_essentials::utils::secret::Secret;
+use rand::{thread_rng, CryptoRng, RngCore};
 
 // Choose elliptic curve setting
 type Curve = ark_bn254::G1Projective;
@@ -26,6 +28,7 @@ type Scalar = ark_bn254::Fr;
 
 // Instantiate concrete type for our card protocol
 type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
+type SessionContext = discrete_log_cards::SessionContext<Curve>;
 
 const NUMBER_OF_CARDS: usize = 60;
 
@@ -34,7 +37,7 @@ fn main() -> anyhow::Result<()> {
 
     let deck: Vec<MaskedCard<Curve>> = sample_vector(&mut rng, NUMBER_OF_CARDS);
     let shared_key = Curve::rand(&mut rng);
-    let blinding_factors: Vec<Scalar> = sample_vector(&mut rng, NUMBER_OF_CARDS);
+    let blinding_factors = Secret::new(sample_vector(&mut rng, NUMBER_OF_CARDS));
     let permutation = Permutation::new(&mut rng, NUMBER_OF_CARDS);
 
     let m_values: Vec<usize> = vec![2, 3, 4, 5, 6, 10, 12, 15, 20, 30];
@@ -55,12 +58,12 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn benchmark_parameters<R: Rng>(
+fn benchmark_parameters<R: CryptoRng + RngCore>(
     deck: &Vec<MaskedCard<Curve>>,
     m: usize,
     n: usize,
     shared_key: &Curve,
-    masking_factors: &Vec<Scalar>,
+    masking_factors: &Secret<Vec<Scalar>>,
     permutation: &Permutation,
     rng: &mut R,
 ) -> anyhow::Result<()> {
@@ -75,11 +78,13 @@ fn benchmark_parameters<R: Rng>(
     );
 
     let parameters = CardProtocol::setup(rng, m, n)?;
+    let ctx = SessionContext::new(b"parameter_selection", 0, &parameters);
 
     let prover_start_time = Instant::now();
     let (_shuffled_deck, proof) = CardProtocol::shuffle_and_remask(
         rng,
         &parameters,
+        &ctx,
         &shared_key.into_affine(),
         deck,
         masking_factors,
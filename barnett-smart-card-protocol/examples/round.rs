@@ -1,15 +1,17 @@
 use ark_serialize::CanonicalDeserialize;
 use ark_serialize::CanonicalSerialize;
 use barnett_smart_card_protocol::discrete_log_cards;
+use barnett_smart_card_protocol::error::{Blame, Operation};
 use barnett_smart_card_protocol::BarnettSmartProtocol;
 
-use anyhow;
 use ark_ff::UniformRand;
 use ark_std::collections::HashMap;
 use ark_std::iter::Iterator;
-use ark_std::{rand::Rng, One};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::One;
 use proof_essentials::utils::permutation::Permutation;
 use proof_essentials::utils::rand::sample_vector;
+use proof_essentials::utils::secret::Secret;
 use proof_essentials::zkp::proofs::{chaum_pedersen_dl_equality, schnorr_identification};
 use rand::thread_rng;
 use thiserror::Error;
@@ -21,6 +23,7 @@ type Scalar = ark_bn254::Fr;
 // Instantiate concrete type for our card protocol
 type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
 type CardParameters = discrete_log_cards::Parameters<Curve>;
+type SessionContext = discrete_log_cards::SessionContext<Curve>;
 type PublicKey = discrete_log_cards::PublicKey<Curve>;
 type SecretKey = discrete_log_cards::PlayerSecretKey<Curve>;
 
@@ -140,9 +143,14 @@ struct Player {
 }
 
 impl Player {
-    pub fn new<R: Rng>(rng: &mut R, pp: &CardParameters, name: &Vec<u8>) -> anyhow::Result<Self> {
+    pub fn new<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &CardParameters,
+        ctx: &SessionContext,
+        name: &Vec<u8>,
+    ) -> anyhow::Result<Self> {
         let (pk, sk) = CardProtocol::player_keygen(rng, pp)?;
-        let proof_key = CardProtocol::prove_key_ownership(rng, pp, &pk, &sk, name)?;
+        let proof_key = CardProtocol::prove_key_ownership(rng, pp, ctx, &pk, &sk, name)?;
         Ok(Self {
             name: name.clone(),
             sk,
@@ -161,6 +169,7 @@ impl Player {
     pub fn peek_at_card(
         &mut self,
         parameters: &CardParameters,
+        ctx: &SessionContext,
         reveal_tokens: &mut Vec<(RevealToken, RevealProof, PublicKey)>,
         card_mappings: &HashMap<Card, ClassicPlayingCard>,
         card: &MaskedCard,
@@ -171,10 +180,10 @@ impl Player {
 
         //TODO add function to create that without the proof
         let rng = &mut thread_rng();
-        let own_reveal_token = self.compute_reveal_token(rng, parameters, card)?;
+        let own_reveal_token = self.compute_reveal_token(rng, parameters, ctx, card)?;
         reveal_tokens.push(own_reveal_token);
 
-        let unmasked_card = CardProtocol::unmask(&parameters, reveal_tokens, card)?;
+        let unmasked_card = CardProtocol::unmask(parameters, ctx, reveal_tokens, card)?;
         let opened_card = card_mappings.get(&unmasked_card);
         let opened_card = opened_card.ok_or(GameErrors::InvalidCard)?;
 
@@ -182,14 +191,15 @@ impl Player {
         Ok(())
     }
 
-    pub fn compute_reveal_token<R: Rng>(
+    pub fn compute_reveal_token<R: CryptoRng + RngCore>(
         &self,
         rng: &mut R,
         pp: &CardParameters,
+        ctx: &SessionContext,
         card: &MaskedCard,
     ) -> anyhow::Result<(RevealToken, RevealProof, PublicKey)> {
         let (reveal_token, reveal_proof) =
-            CardProtocol::compute_reveal_token(rng, &pp, &self.sk, &self.pk, card)?;
+            CardProtocol::compute_reveal_token(rng, pp, ctx, &self.sk, &self.pk, card)?;
 
         Ok((reveal_token, reveal_proof, self.pk))
     }
@@ -198,18 +208,22 @@ impl Player {
 //Every player will have to calculate this function for cards that are in play
 pub fn open_card(
     parameters: &CardParameters,
+    ctx: &SessionContext,
     reveal_tokens: &Vec<(RevealToken, RevealProof, PublicKey)>,
     card_mappings: &HashMap<Card, ClassicPlayingCard>,
     card: &MaskedCard,
 ) -> Result<ClassicPlayingCard, anyhow::Error> {
-    let unmasked_card = CardProtocol::unmask(&parameters, reveal_tokens, card)?;
+    let unmasked_card = CardProtocol::unmask(parameters, ctx, reveal_tokens, card)?;
     let opened_card = card_mappings.get(&unmasked_card);
     let opened_card = opened_card.ok_or(GameErrors::InvalidCard)?;
 
     Ok(*opened_card)
 }
 
-fn encode_cards<R: Rng>(rng: &mut R, num_of_cards: usize) -> HashMap<Card, ClassicPlayingCard> {
+fn encode_cards<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    num_of_cards: usize,
+) -> HashMap<Card, ClassicPlayingCard> {
     let mut map: HashMap<Card, ClassicPlayingCard> = HashMap::new();
     let plaintexts = (0..num_of_cards)
         .map(|_| Card::rand(rng))
@@ -233,15 +247,18 @@ fn main() -> anyhow::Result<()> {
     let num_of_cards = m * n;
     let rng = &mut thread_rng();
 
-    let parameters = CardProtocol::setup(rng, m, n)?;
+    // Derive the parameters from a public string so that every player can check them
+    let parameters = CardParameters::from_seed(b"round", m, n);
+    parameters.verify_parameters(b"round")?;
+    let registration_ctx = SessionContext::new(b"round", 0, &parameters);
     let card_mapping = encode_cards(rng, num_of_cards);
 
-    let mut andrija = Player::new(rng, &parameters, &b"Andrija".to_vec())?;
-    let mut kobi = Player::new(rng, &parameters, &b"Kobi".to_vec())?;
-    let mut nico = Player::new(rng, &parameters, &b"Nico".to_vec())?;
-    let mut tom = Player::new(rng, &parameters, &b"Tom".to_vec())?;
+    let mut andrija = Player::new(rng, &parameters, &registration_ctx, &b"Andrija".to_vec())?;
+    let mut kobi = Player::new(rng, &parameters, &registration_ctx, &b"Kobi".to_vec())?;
+    let mut nico = Player::new(rng, &parameters, &registration_ctx, &b"Nico".to_vec())?;
+    let mut tom = Player::new(rng, &parameters, &registration_ctx, &b"Tom".to_vec())?;
 
-    let players = vec![andrija.clone(), kobi.clone(), nico.clone(), tom.clone()];
+    let players = [andrija.clone(), kobi.clone(), nico.clone(), tom.clone()];
 
     let key_proof_info = players
         .iter()
@@ -249,12 +266,16 @@ fn main() -> anyhow::Result<()> {
         .collect::<Vec<_>>();
 
     // Each player should run this computation. Alternatively, it can be ran by a smart contract
-    let joint_pk = CardProtocol::compute_aggregate_key(&parameters, &key_proof_info)?;
+    let roster = CardProtocol::register_players(&parameters, &registration_ctx, &key_proof_info)?;
+    let joint_pk = *roster.aggregate_key();
+
+    // All proofs for the hand are bound to the table, the hand number and the player roster
+    let ctx = registration_ctx.with_roster(&roster.public_keys());
 
     // Each player should run this computation and verify that all players agree on the initial deck
     let mut deck_and_proofs: Vec<(MaskedCard, RemaskingProof)> = card_mapping
         .keys()
-        .map(|card| CardProtocol::mask(rng, &parameters, &joint_pk, &card, &Scalar::one()))
+        .map(|card| CardProtocol::mask(rng, &parameters, &ctx, &joint_pk, card, &Scalar::one()))
         .collect::<Result<Vec<_>, _>>()?;
 
     deck_and_proofs = deck_and_proofs
@@ -264,7 +285,7 @@ fn main() -> anyhow::Result<()> {
             let mut data = Vec::with_capacity(proof.compressed_size());
             proof.serialize_compressed(&mut data).unwrap();
             proof = CanonicalDeserialize::deserialize_compressed(data.as_slice()).unwrap();
-            (deck_and_proofs.0, proof.clone())
+            (deck_and_proofs.0, proof)
         })
         .collect();
 
@@ -276,11 +297,12 @@ fn main() -> anyhow::Result<()> {
     // SHUFFLE TIME --------------
     // 1.a Andrija shuffles first
     let permutation = Permutation::new(rng, m * n);
-    let masking_factors: Vec<Scalar> = sample_vector(rng, m * n);
+    let masking_factors = Secret::new(sample_vector(rng, m * n));
 
     let (a_shuffled_deck, a_shuffle_proof) = CardProtocol::shuffle_and_remask(
         rng,
         &parameters,
+        &ctx,
         &joint_pk,
         &deck,
         &masking_factors,
@@ -290,19 +312,22 @@ fn main() -> anyhow::Result<()> {
     // 1.b everyone checks!
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &joint_pk,
         &deck,
         &a_shuffled_deck,
         &a_shuffle_proof,
-    )?;
+    )
+    .blame(0, &andrija.pk, Operation::Shuffle)?;
 
     //2.a Kobi shuffles second
     let permutation = Permutation::new(rng, m * n);
-    let masking_factors: Vec<Scalar> = sample_vector(rng, m * n);
+    let masking_factors = Secret::new(sample_vector(rng, m * n));
 
     let (k_shuffled_deck, k_shuffle_proof) = CardProtocol::shuffle_and_remask(
         rng,
         &parameters,
+        &ctx,
         &joint_pk,
         &a_shuffled_deck,
         &masking_factors,
@@ -312,19 +337,22 @@ fn main() -> anyhow::Result<()> {
     //2.b Everyone checks
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &joint_pk,
         &a_shuffled_deck,
         &k_shuffled_deck,
         &k_shuffle_proof,
-    )?;
+    )
+    .blame(1, &kobi.pk, Operation::Shuffle)?;
 
     //3.a Nico shuffles third
     let permutation = Permutation::new(rng, m * n);
-    let masking_factors: Vec<Scalar> = sample_vector(rng, m * n);
+    let masking_factors = Secret::new(sample_vector(rng, m * n));
 
     let (n_shuffled_deck, n_shuffle_proof) = CardProtocol::shuffle_and_remask(
         rng,
         &parameters,
+        &ctx,
         &joint_pk,
         &k_shuffled_deck,
         &masking_factors,
@@ -334,19 +362,22 @@ fn main() -> anyhow::Result<()> {
     //3.b Everyone checks
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &joint_pk,
         &k_shuffled_deck,
         &n_shuffled_deck,
         &n_shuffle_proof,
-    )?;
+    )
+    .blame(2, &nico.pk, Operation::Shuffle)?;
 
     //4.a Tom shuffles last
     let permutation = Permutation::new(rng, m * n);
-    let masking_factors: Vec<Scalar> = sample_vector(rng, m * n);
+    let masking_factors = Secret::new(sample_vector(rng, m * n));
 
     let (final_shuffled_deck, final_shuffle_proof) = CardProtocol::shuffle_and_remask(
         rng,
         &parameters,
+        &ctx,
         &joint_pk,
         &n_shuffled_deck,
         &masking_factors,
@@ -356,11 +387,13 @@ fn main() -> anyhow::Result<()> {
     //4.b Everyone checks before accepting last deck for game
     CardProtocol::verify_shuffle(
         &parameters,
+        &ctx,
         &joint_pk,
         &n_shuffled_deck,
         &final_shuffled_deck,
         &final_shuffle_proof,
-    )?;
+    )
+    .blame(3, &tom.pk, Operation::Shuffle)?;
 
     // CARDS ARE SHUFFLED. ROUND OF THE GAME CAN BEGIN
     let deck = final_shuffled_deck;
@@ -370,21 +403,21 @@ fn main() -> anyhow::Result<()> {
     nico.receive_card(deck[2]);
     tom.receive_card(deck[3]);
 
-    let andrija_rt_1 = andrija.compute_reveal_token(rng, &parameters, &deck[1])?;
-    let andrija_rt_2 = andrija.compute_reveal_token(rng, &parameters, &deck[2])?;
-    let andrija_rt_3 = andrija.compute_reveal_token(rng, &parameters, &deck[3])?;
+    let andrija_rt_1 = andrija.compute_reveal_token(rng, &parameters, &ctx, &deck[1])?;
+    let andrija_rt_2 = andrija.compute_reveal_token(rng, &parameters, &ctx, &deck[2])?;
+    let andrija_rt_3 = andrija.compute_reveal_token(rng, &parameters, &ctx, &deck[3])?;
 
-    let kobi_rt_0 = kobi.compute_reveal_token(rng, &parameters, &deck[0])?;
-    let kobi_rt_2 = kobi.compute_reveal_token(rng, &parameters, &deck[2])?;
-    let kobi_rt_3 = kobi.compute_reveal_token(rng, &parameters, &deck[3])?;
+    let kobi_rt_0 = kobi.compute_reveal_token(rng, &parameters, &ctx, &deck[0])?;
+    let kobi_rt_2 = kobi.compute_reveal_token(rng, &parameters, &ctx, &deck[2])?;
+    let kobi_rt_3 = kobi.compute_reveal_token(rng, &parameters, &ctx, &deck[3])?;
 
-    let nico_rt_0 = nico.compute_reveal_token(rng, &parameters, &deck[0])?;
-    let nico_rt_1 = nico.compute_reveal_token(rng, &parameters, &deck[1])?;
-    let nico_rt_3 = nico.compute_reveal_token(rng, &parameters, &deck[3])?;
+    let nico_rt_0 = nico.compute_reveal_token(rng, &parameters, &ctx, &deck[0])?;
+    let nico_rt_1 = nico.compute_reveal_token(rng, &parameters, &ctx, &deck[1])?;
+    let nico_rt_3 = nico.compute_reveal_token(rng, &parameters, &ctx, &deck[3])?;
 
-    let tom_rt_0 = tom.compute_reveal_token(rng, &parameters, &deck[0])?;
-    let tom_rt_1 = tom.compute_reveal_token(rng, &parameters, &deck[1])?;
-    let tom_rt_2 = tom.compute_reveal_token(rng, &parameters, &deck[2])?;
+    let tom_rt_0 = tom.compute_reveal_token(rng, &parameters, &ctx, &deck[0])?;
+    let tom_rt_1 = tom.compute_reveal_token(rng, &parameters, &ctx, &deck[1])?;
+    let tom_rt_2 = tom.compute_reveal_token(rng, &parameters, &ctx, &deck[2])?;
 
     let mut rts_andrija = vec![kobi_rt_0, nico_rt_0, tom_rt_0];
     let mut rts_kobi = vec![andrija_rt_1, nico_rt_1, tom_rt_1];
@@ -392,10 +425,10 @@ fn main() -> anyhow::Result<()> {
     let mut rts_tom = vec![andrija_rt_3, kobi_rt_3, nico_rt_3];
 
     //At this moment players privately open their cards and only they know that values
-    andrija.peek_at_card(&parameters, &mut rts_andrija, &card_mapping, &deck[0])?;
-    kobi.peek_at_card(&parameters, &mut rts_kobi, &card_mapping, &deck[1])?;
-    nico.peek_at_card(&parameters, &mut rts_nico, &card_mapping, &deck[2])?;
-    tom.peek_at_card(&parameters, &mut rts_tom, &card_mapping, &deck[3])?;
+    andrija.peek_at_card(&parameters, &ctx, &mut rts_andrija, &card_mapping, &deck[0])?;
+    kobi.peek_at_card(&parameters, &ctx, &mut rts_kobi, &card_mapping, &deck[1])?;
+    nico.peek_at_card(&parameters, &ctx, &mut rts_nico, &card_mapping, &deck[2])?;
+    tom.peek_at_card(&parameters, &ctx, &mut rts_tom, &card_mapping, &deck[3])?;
 
     /* Here we can add custom logic of a game:
         1. swap card
@@ -406,28 +439,28 @@ fn main() -> anyhow::Result<()> {
     //At this moment players reveal their cards to each other and everything becomes public
 
     //1.a everyone reveals the secret for their card
-    let andrija_rt_0 = andrija.compute_reveal_token(rng, &parameters, &deck[0])?;
-    let kobi_rt_1 = kobi.compute_reveal_token(rng, &parameters, &deck[1])?;
-    let nico_rt_2 = nico.compute_reveal_token(rng, &parameters, &deck[2])?;
-    let tom_rt_3 = tom.compute_reveal_token(rng, &parameters, &deck[3])?;
+    let andrija_rt_0 = andrija.compute_reveal_token(rng, &parameters, &ctx, &deck[0])?;
+    let kobi_rt_1 = kobi.compute_reveal_token(rng, &parameters, &ctx, &deck[1])?;
+    let nico_rt_2 = nico.compute_reveal_token(rng, &parameters, &ctx, &deck[2])?;
+    let tom_rt_3 = tom.compute_reveal_token(rng, &parameters, &ctx, &deck[3])?;
 
     //2. tokens for all other cards are exchanged
     //TODO add struct for this so that we can just clone
-    let andrija_rt_1 = andrija.compute_reveal_token(rng, &parameters, &deck[1])?;
-    let andrija_rt_2 = andrija.compute_reveal_token(rng, &parameters, &deck[2])?;
-    let andrija_rt_3 = andrija.compute_reveal_token(rng, &parameters, &deck[3])?;
+    let andrija_rt_1 = andrija.compute_reveal_token(rng, &parameters, &ctx, &deck[1])?;
+    let andrija_rt_2 = andrija.compute_reveal_token(rng, &parameters, &ctx, &deck[2])?;
+    let andrija_rt_3 = andrija.compute_reveal_token(rng, &parameters, &ctx, &deck[3])?;
 
-    let kobi_rt_0 = kobi.compute_reveal_token(rng, &parameters, &deck[0])?;
-    let kobi_rt_2 = kobi.compute_reveal_token(rng, &parameters, &deck[2])?;
-    let kobi_rt_3 = kobi.compute_reveal_token(rng, &parameters, &deck[3])?;
+    let kobi_rt_0 = kobi.compute_reveal_token(rng, &parameters, &ctx, &deck[0])?;
+    let kobi_rt_2 = kobi.compute_reveal_token(rng, &parameters, &ctx, &deck[2])?;
+    let kobi_rt_3 = kobi.compute_reveal_token(rng, &parameters, &ctx, &deck[3])?;
 
-    let nico_rt_0 = nico.compute_reveal_token(rng, &parameters, &deck[0])?;
-    let nico_rt_1 = nico.compute_reveal_token(rng, &parameters, &deck[1])?;
-    let nico_rt_3 = nico.compute_reveal_token(rng, &parameters, &deck[3])?;
+    let nico_rt_0 = nico.compute_reveal_token(rng, &parameters, &ctx, &deck[0])?;
+    let nico_rt_1 = nico.compute_reveal_token(rng, &parameters, &ctx, &deck[1])?;
+    let nico_rt_3 = nico.compute_reveal_token(rng, &parameters, &ctx, &deck[3])?;
 
-    let tom_rt_0 = tom.compute_reveal_token(rng, &parameters, &deck[0])?;
-    let tom_rt_1 = tom.compute_reveal_token(rng, &parameters, &deck[1])?;
-    let tom_rt_2 = tom.compute_reveal_token(rng, &parameters, &deck[2])?;
+    let tom_rt_0 = tom.compute_reveal_token(rng, &parameters, &ctx, &deck[0])?;
+    let tom_rt_1 = tom.compute_reveal_token(rng, &parameters, &ctx, &deck[1])?;
+    let tom_rt_2 = tom.compute_reveal_token(rng, &parameters, &ctx, &deck[2])?;
 
     let rt_0 = vec![andrija_rt_0, kobi_rt_0, nico_rt_0, tom_rt_0];
     let rt_1 = vec![andrija_rt_1, kobi_rt_1, nico_rt_1, tom_rt_1];
@@ -435,10 +468,10 @@ fn main() -> anyhow::Result<()> {
     let rt_3 = vec![andrija_rt_3, kobi_rt_3, nico_rt_3, tom_rt_3];
 
     //Everyone computes for each card (except for their own card):
-    let andrija_card = open_card(&parameters, &rt_0, &card_mapping, &deck[0])?;
-    let kobi_card = open_card(&parameters, &rt_1, &card_mapping, &deck[1])?;
-    let nico_card = open_card(&parameters, &rt_2, &card_mapping, &deck[2])?;
-    let tom_card = open_card(&parameters, &rt_3, &card_mapping, &deck[3])?;
+    let andrija_card = open_card(&parameters, &ctx, &rt_0, &card_mapping, &deck[0])?;
+    let kobi_card = open_card(&parameters, &ctx, &rt_1, &card_mapping, &deck[1])?;
+    let nico_card = open_card(&parameters, &ctx, &rt_2, &card_mapping, &deck[2])?;
+    let tom_card = open_card(&parameters, &ctx, &rt_3, &card_mapping, &deck[3])?;
 
     println!("Andrija: {:?}", andrija_card);
     println!("Kobi: {:?}", kobi_card);
@@ -0,0 +1,281 @@
+use super::Parameters;
+use crate::error::CardProtocolError;
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::UniformRand;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::Zero;
+use blake2::Blake2s256;
+use proof_essentials::homomorphic_encryption::el_gamal;
+use proof_essentials::utils::constant_time::mul_secret;
+use proof_essentials::utils::rand::FiatShamirRng;
+use proof_essentials::vector_commitment::pedersen;
+use proof_essentials::zkp::{proofs::schnorr_identification, ArgumentOfKnowledge};
+use zeroize::Zeroize;
+
+const SETUP_RNG_SEED: &[u8] = b"Setup Contribution";
+
+/// One step of the setup ceremony: every base of the previous parameters is multiplied by a fresh
+/// secret scalar, together with a proof of knowledge of that scalar.
+#[derive(Clone, CanonicalDeserialize, CanonicalSerialize)]
+pub struct Contribution<C: CurveGroup> {
+    parameters: Parameters<C>,
+    proofs: Vec<schnorr_identification::proof::Proof<C>>,
+}
+
+impl<C: CurveGroup> Contribution<C> {
+    /// Parameters resulting from this contribution
+    pub fn parameters(&self) -> &Parameters<C> {
+        &self.parameters
+    }
+}
+
+/// Transcript of a multi-party setup ceremony. The resulting parameters are safe to use as long
+/// as one of the contributors was honest and discarded its secret scalars.
+#[derive(Clone, CanonicalDeserialize, CanonicalSerialize)]
+pub struct SetupTranscript<C: CurveGroup> {
+    initial: Parameters<C>,
+    contributions: Vec<Contribution<C>>,
+}
+
+impl<C: CurveGroup> SetupTranscript<C> {
+    /// Start a ceremony from some initial parameters, for example [`Parameters::from_seed`]
+    pub fn new(initial: Parameters<C>) -> Self {
+        Self {
+            initial,
+            contributions: Vec::new(),
+        }
+    }
+
+    /// Parameters the ceremony started from
+    pub fn initial_parameters(&self) -> &Parameters<C> {
+        &self.initial
+    }
+
+    pub fn contributions(&self) -> &[Contribution<C>] {
+        &self.contributions
+    }
+
+    /// Parameters resulting from the latest contribution
+    pub fn parameters(&self) -> &Parameters<C> {
+        self.contributions
+            .last()
+            .map_or(&self.initial, |contribution| &contribution.parameters)
+    }
+
+    /// Re-randomize the current parameters and append the contribution to the transcript
+    pub fn contribute<R: CryptoRng + RngCore>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<(), CardProtocolError> {
+        let previous = self.parameters();
+        let bases = previous.bases();
+
+        let mut new_bases = Vec::with_capacity(bases.len());
+        let mut proofs = Vec::with_capacity(bases.len());
+        for (i, base) in bases.iter().enumerate() {
+            let mut secret = C::ScalarField::rand(rng);
+            while secret.is_zero() {
+                secret = C::ScalarField::rand(rng);
+            }
+            let new_base = mul_secret::<C>(base, &secret).into_affine();
+
+            let mut fs_rng = contribution_fs_rng(previous, i);
+            let proof = schnorr_identification::SchnorrIdentification::prove(
+                rng,
+                base,
+                &new_base,
+                &secret,
+                &mut fs_rng,
+            )?;
+            secret.zeroize();
+
+            new_bases.push(new_base);
+            proofs.push(proof);
+        }
+
+        let parameters = previous.with_bases(&new_bases);
+        self.contributions.push(Contribution { parameters, proofs });
+
+        Ok(())
+    }
+
+    /// Check every contribution of the transcript against the parameters it builds on.
+    /// Returns the final parameters if the whole transcript is valid.
+    pub fn verify(&self) -> Result<&Parameters<C>, CardProtocolError> {
+        let mut previous = &self.initial;
+        for (position, contribution) in self.contributions.iter().enumerate() {
+            if !contribution.is_valid(previous) {
+                return Err(CardProtocolError::InvalidContribution(position));
+            }
+            previous = &contribution.parameters;
+        }
+
+        Ok(previous)
+    }
+}
+
+impl<C: CurveGroup> Contribution<C> {
+    fn is_valid(&self, previous: &Parameters<C>) -> bool {
+        if self.parameters.m != previous.m || self.parameters.n != previous.n {
+            return false;
+        }
+
+        let bases = previous.bases();
+        let new_bases = self.parameters.bases();
+        if new_bases.len() != bases.len() || self.proofs.len() != bases.len() {
+            return false;
+        }
+
+        bases
+            .iter()
+            .zip(new_bases.iter())
+            .zip(self.proofs.iter())
+            .enumerate()
+            .all(|(i, ((base, new_base), proof))| {
+                let mut fs_rng = contribution_fs_rng(previous, i);
+                !new_base.is_zero()
+                    && schnorr_identification::SchnorrIdentification::verify(
+                        base,
+                        new_base,
+                        proof,
+                        &mut fs_rng,
+                    )
+                    .is_ok()
+            })
+    }
+}
+
+impl<C: CurveGroup> Parameters<C> {
+    /// All the group elements of the parameters, in a fixed order
+    fn bases(&self) -> Vec<C::Affine> {
+        let g = self.commit_parameters.g();
+
+        let mut bases = Vec::with_capacity(g.len() + 3);
+        bases.push(self.enc_parameters.generator);
+        bases.extend_from_slice(g);
+        bases.push(*self.commit_parameters.h());
+        bases.push(self.generator.0);
+        bases
+    }
+
+    /// Inverse of [`Parameters::bases`]
+    fn with_bases(&self, bases: &[C::Affine]) -> Self {
+        let len = self.commit_parameters.g().len();
+
+        Self::new(
+            self.m,
+            self.n,
+            el_gamal::Parameters {
+                generator: bases[0],
+            },
+            pedersen::CommitKey::new(bases[1..=len].to_vec(), bases[len + 1]),
+            el_gamal::Plaintext(bases[len + 2]),
+        )
+    }
+}
+
+fn contribution_fs_rng<C: CurveGroup>(
+    previous: &Parameters<C>,
+    index: usize,
+) -> FiatShamirRng<Blake2s256> {
+    let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&SETUP_RNG_SEED);
+    fs_rng.absorb(&previous.digest());
+    fs_rng.absorb(&(index as u64));
+    fs_rng
+}
+
+#[cfg(test)]
+mod test {
+    use crate::discrete_log_cards;
+    use crate::error::CardProtocolError;
+    use crate::BarnettSmartProtocol;
+
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use proof_essentials::utils::permutation::Permutation;
+    use proof_essentials::utils::rand::sample_vector;
+    use proof_essentials::utils::secret::Secret;
+    use rand::thread_rng;
+
+    type Curve = ark_bn254::G1Projective;
+    type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
+    type CardParameters = discrete_log_cards::Parameters<Curve>;
+    type SessionContext = discrete_log_cards::SessionContext<Curve>;
+    type SetupTranscript = discrete_log_cards::SetupTranscript<Curve>;
+    type MaskedCard = discrete_log_cards::MaskedCard<Curve>;
+
+    #[test]
+    fn test_ceremony() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let initial = CardParameters::from_seed(b"test table", m, n);
+        let mut transcript = SetupTranscript::new(initial);
+        for _ in 0..3 {
+            transcript.contribute(rng).unwrap();
+        }
+
+        let mut data = Vec::with_capacity(transcript.compressed_size());
+        transcript.serialize_compressed(&mut data).unwrap();
+        let received = SetupTranscript::deserialize_compressed(data.as_slice()).unwrap();
+
+        let parameters = received.verify().unwrap();
+        assert_eq!(parameters.digest(), transcript.parameters().digest());
+        assert_eq!(
+            received
+                .initial_parameters()
+                .verify_parameters(b"test table"),
+            Ok(())
+        );
+
+        let (pk, _) = CardProtocol::player_keygen(rng, parameters).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, parameters);
+        let deck: Vec<MaskedCard> = sample_vector(rng, m * n);
+        let permutation = Permutation::new(rng, m * n);
+        let masking_factors = Secret::new(sample_vector(rng, m * n));
+        let (shuffled_deck, proof) = CardProtocol::shuffle_and_remask(
+            rng,
+            parameters,
+            &ctx,
+            &pk,
+            &deck,
+            &masking_factors,
+            &permutation,
+        )
+        .unwrap();
+        assert_eq!(
+            CardProtocol::verify_shuffle(parameters, &ctx, &pk, &deck, &shuffled_deck, &proof),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_invalid_contribution() {
+        let rng = &mut thread_rng();
+        let m = 2;
+        let n = 4;
+
+        let mut transcript = SetupTranscript::new(CardParameters::from_seed(b"test table", m, n));
+        transcript.contribute(rng).unwrap();
+        transcript.contribute(rng).unwrap();
+
+        // Replace the second contribution with one built on different parameters
+        let mut other = SetupTranscript::new(CardProtocol::setup(rng, m, n).unwrap());
+        other.contribute(rng).unwrap();
+        transcript.contributions[1] = other.contributions[0].clone();
+        assert_eq!(
+            transcript.verify().err(),
+            Some(CardProtocolError::InvalidContribution(1))
+        );
+
+        // Parameters swapped without proofs of knowledge
+        transcript.contributions.truncate(1);
+        transcript.contributions[0].parameters = CardProtocol::setup(rng, m, n).unwrap();
+        assert_eq!(
+            transcript.verify().err(),
+            Some(CardProtocolError::InvalidContribution(0))
+        );
+    }
+}
@@ -0,0 +1,451 @@
+use super::{
+    DLCards, MaskedCard, Parameters, PlayerSecretKey, ProofEncoding, PublicKey, RevealToken,
+    SessionContext,
+};
+use crate::error::{Blame, CardProtocolError, Operation};
+use crate::{BarnettSmartProtocol, Reveal};
+
+use ark_ec::CurveGroup;
+use ark_ff::{Field, One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use proof_essentials::utils::constant_time::mul_secret;
+use proof_essentials::utils::secret::Secret;
+use proof_essentials::zkp::proofs::schnorr_identification;
+use proof_essentials::zkp::ArgumentOfKnowledge;
+use zeroize::Zeroize;
+
+const DKG_RNG_SEED: &[u8] = b"Distributed Key Generation";
+
+/// Public part of a dealing in the Feldman VSS based distributed key generation: commitments to the
+/// coefficients of the dealer's secret polynomial and a proof of knowledge of its constant term.
+#[derive(Clone, Debug, PartialEq, CanonicalDeserialize, CanonicalSerialize)]
+pub struct DealerCommitment<C: CurveGroup> {
+    dealer: usize,
+    coefficients: Vec<C::Affine>,
+    proof: schnorr_identification::proof::Proof<C>,
+}
+
+impl<C: CurveGroup> DealerCommitment<C> {
+    pub fn dealer(&self) -> usize {
+        self.dealer
+    }
+
+    /// Commitment to the evaluation of the dealer's polynomial at the point of `player`
+    fn evaluate(&self, player: usize) -> C {
+        let x = evaluation_point::<C>(player);
+        let powers =
+            ark_std::iter::successors(Some(C::ScalarField::one()), |power| Some(*power * x))
+                .take(self.coefficients.len())
+                .collect::<Vec<_>>();
+
+        C::msm_unchecked(&self.coefficients, &powers)
+    }
+}
+
+/// Public outcome of the distributed key generation: the aggregate key, which can be used as the
+/// shared key of the protocol, and the verification key of every player's share of the secret.
+#[derive(Clone, Debug, PartialEq, CanonicalDeserialize, CanonicalSerialize)]
+pub struct ThresholdKey<C: CurveGroup> {
+    threshold: usize,
+    aggregate_key: PublicKey<C>,
+    verification_keys: Vec<PublicKey<C>>,
+}
+
+impl<C: CurveGroup> ThresholdKey<C> {
+    /// Number of reveal tokens needed to unmask a card
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn aggregate_key(&self) -> &PublicKey<C> {
+        &self.aggregate_key
+    }
+
+    /// Public key matching the secret share of `player`. Reveal tokens of that player are proven
+    /// against this key.
+    pub fn verification_key(&self, player: usize) -> Option<&PublicKey<C>> {
+        self.verification_keys.get(player)
+    }
+
+    pub fn verification_keys(&self) -> &[PublicKey<C>] {
+        &self.verification_keys
+    }
+}
+
+/// Outcome of a dealing: the public commitment and the secret shares, one per player
+pub type Dealing<C> = (
+    DealerCommitment<C>,
+    Secret<Vec<<C as ark_ec::Group>::ScalarField>>,
+);
+
+impl<'a, C: CurveGroup, E: ProofEncoding<C>> DLCards<'a, C, E> {
+    /// Sample a random polynomial of degree `threshold - 1` and deal shares of it to `num_of_players`
+    /// players. The commitment is broadcast while share `j` must be sent privately to player `j`.
+    pub fn deal<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Parameters<C>,
+        ctx: &SessionContext<C>,
+        dealer: usize,
+        threshold: usize,
+        num_of_players: usize,
+    ) -> Result<Dealing<C>, CardProtocolError> {
+        if threshold == 0 || threshold > num_of_players {
+            return Err(CardProtocolError::InvalidThreshold(
+                threshold,
+                num_of_players,
+            ));
+        }
+
+        let mut polynomial = (0..threshold)
+            .map(|_| C::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+
+        let generator = pp.enc_parameters.generator;
+        let coefficients = C::normalize_batch(
+            &polynomial
+                .iter()
+                .map(|coefficient| mul_secret::<C>(&generator, coefficient))
+                .collect::<Vec<_>>(),
+        );
+
+        let mut fs_rng = ctx.fiat_shamir_rng(DKG_RNG_SEED);
+        fs_rng.absorb(&(dealer as u64));
+        let proof = schnorr_identification::SchnorrIdentification::prove(
+            rng,
+            &generator,
+            &coefficients[0],
+            &polynomial[0],
+            &mut fs_rng,
+        )?;
+
+        let shares = Secret::new(
+            (0..num_of_players)
+                .map(|player| evaluate_polynomial(&polynomial, evaluation_point::<C>(player)))
+                .collect(),
+        );
+        polynomial.zeroize();
+
+        Ok((
+            DealerCommitment {
+                dealer,
+                coefficients,
+                proof,
+            },
+            shares,
+        ))
+    }
+
+    /// Check the commitments of all the dealers and compute the public threshold key. Dealers are
+    /// expected to be listed in order, one per player.
+    pub fn combine_commitments(
+        pp: &Parameters<C>,
+        ctx: &SessionContext<C>,
+        threshold: usize,
+        commitments: &[DealerCommitment<C>],
+    ) -> Result<ThresholdKey<C>, CardProtocolError> {
+        let num_of_players = commitments.len();
+        if threshold == 0 || threshold > num_of_players {
+            return Err(CardProtocolError::InvalidThreshold(
+                threshold,
+                num_of_players,
+            ));
+        }
+
+        for (dealer, commitment) in commitments.iter().enumerate() {
+            if commitment.dealer != dealer || commitment.coefficients.len() != threshold {
+                return Err(CardProtocolError::InvalidDealing(dealer));
+            }
+
+            let mut fs_rng = ctx.fiat_shamir_rng(DKG_RNG_SEED);
+            fs_rng.absorb(&(dealer as u64));
+            schnorr_identification::SchnorrIdentification::verify(
+                &pp.enc_parameters.generator,
+                &commitment.coefficients[0],
+                &commitment.proof,
+                &mut fs_rng,
+            )
+            .map_err(|_| CardProtocolError::InvalidDealing(dealer))?;
+        }
+
+        let aggregate_key = commitments
+            .iter()
+            .map(|commitment| commitment.coefficients[0])
+            .sum::<C>();
+
+        let verification_keys = C::normalize_batch(
+            &(0..num_of_players)
+                .map(|player| {
+                    commitments
+                        .iter()
+                        .map(|commitment| commitment.evaluate(player))
+                        .sum::<C>()
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        Ok(ThresholdKey {
+            threshold,
+            aggregate_key: aggregate_key.into_affine(),
+            verification_keys,
+        })
+    }
+
+    /// Verify the shares received by `player` against the dealers' commitments and combine them
+    /// into the player's secret share. `shares[i]` is the share sent by dealer `i`.
+    pub fn combine_shares(
+        pp: &Parameters<C>,
+        commitments: &[DealerCommitment<C>],
+        player: usize,
+        shares: &[C::ScalarField],
+    ) -> Result<PlayerSecretKey<C>, CardProtocolError> {
+        if shares.len() != commitments.len() {
+            return Err(CardProtocolError::MismatchedShares(
+                shares.len(),
+                commitments.len(),
+            ));
+        }
+
+        for (dealer, (commitment, share)) in commitments.iter().zip(shares.iter()).enumerate() {
+            if mul_secret::<C>(&pp.enc_parameters.generator, share) != commitment.evaluate(player) {
+                return Err(CardProtocolError::InvalidShare(dealer));
+            }
+        }
+
+        Ok(Secret::new(shares.iter().sum()))
+    }
+}
+
+/// Unmask a card from the reveal tokens of any `threshold` players of a [`ThresholdKey`], see
+/// [`BarnettSmartProtocol::unmask_threshold`]. The tokens are combined by Lagrange interpolation at
+/// zero.
+pub(super) fn unmask_threshold<C: CurveGroup, E: ProofEncoding<C>>(
+    pp: &Parameters<C>,
+    ctx: &SessionContext<C>,
+    key: &ThresholdKey<C>,
+    decryption_key: &[(RevealToken<C>, E::DLEqualityProof, PublicKey<C>)],
+    masked_card: &MaskedCard<C>,
+) -> Result<super::Card<C>, CardProtocolError> {
+    let mut players = Vec::with_capacity(decryption_key.len());
+    for (position, (token, proof, pk)) in decryption_key.iter().enumerate() {
+        let player = key
+            .verification_keys
+            .iter()
+            .position(|verification_key| verification_key == pk)
+            .ok_or(CardProtocolError::UnregisteredRevealToken(position))?;
+
+        if players.contains(&player) {
+            return Err(CardProtocolError::DuplicateRevealToken(player));
+        }
+        players.push(player);
+
+        DLCards::<C, E>::verify_reveal(pp, ctx, pk, token, masked_card, proof).blame(
+            player,
+            pk,
+            Operation::Reveal,
+        )?;
+    }
+
+    if players.len() < key.threshold {
+        return Err(CardProtocolError::NotEnoughRevealTokens(
+            players.len(),
+            key.threshold,
+        ));
+    }
+
+    let points = players
+        .iter()
+        .map(|&player| evaluation_point::<C>(player))
+        .collect::<Vec<_>>();
+
+    let mut aggregate_token = RevealToken::<C>::zero();
+    for (i, (token, _, _)) in decryption_key.iter().enumerate() {
+        aggregate_token = aggregate_token + *token * lagrange_coefficient(&points, i);
+    }
+
+    let decrypted = aggregate_token.reveal(masked_card)?;
+
+    Ok(decrypted)
+}
+
+/// Players are numbered from zero but polynomials are evaluated at `player + 1`, as the secret
+/// sits at zero.
+fn evaluation_point<C: CurveGroup>(player: usize) -> C::ScalarField {
+    C::ScalarField::from((player + 1) as u64)
+}
+
+fn evaluate_polynomial<F: Field>(coefficients: &[F], x: F) -> F {
+    coefficients
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, coefficient| acc * x + coefficient)
+}
+
+/// Lagrange coefficient at zero of `points[i]` for the interpolation over `points`
+fn lagrange_coefficient<F: Field>(points: &[F], i: usize) -> F {
+    let (numerator, denominator) = points
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| *j != i)
+        .fold((F::one(), F::one()), |(num, den), (_, x_j)| {
+            (num * x_j, den * (*x_j - points[i]))
+        });
+
+    numerator
+        * denominator
+            .inverse()
+            .expect("evaluation points are distinct")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::discrete_log_cards;
+    use crate::error::CardProtocolError;
+    use crate::BarnettSmartProtocol;
+
+    use ark_ff::UniformRand;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use rand::thread_rng;
+
+    type Curve = ark_bn254::G1Projective;
+    type Scalar = ark_bn254::Fr;
+    type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
+    type SessionContext = discrete_log_cards::SessionContext<Curve>;
+    type ThresholdKey = discrete_log_cards::ThresholdKey<Curve>;
+    type Card = discrete_log_cards::Card<Curve>;
+
+    #[test]
+    fn test_threshold_unmask() {
+        let rng = &mut thread_rng();
+        let num_of_players = 5;
+        let threshold = 3;
+
+        let parameters = CardProtocol::setup(rng, 2, 4).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let dealings = (0..num_of_players)
+            .map(|dealer| {
+                CardProtocol::deal(rng, &parameters, &ctx, dealer, threshold, num_of_players)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let commitments = dealings
+            .iter()
+            .map(|(commitment, _)| commitment.clone())
+            .collect::<Vec<_>>();
+
+        let key =
+            CardProtocol::combine_commitments(&parameters, &ctx, threshold, &commitments).unwrap();
+
+        let mut data = Vec::with_capacity(key.compressed_size());
+        key.serialize_compressed(&mut data).unwrap();
+        assert_eq!(
+            key,
+            ThresholdKey::deserialize_compressed(data.as_slice()).unwrap()
+        );
+
+        let secret_shares = (0..num_of_players)
+            .map(|player| {
+                let shares = dealings
+                    .iter()
+                    .map(|(_, shares)| shares.expose()[player])
+                    .collect::<Vec<_>>();
+                CardProtocol::combine_shares(&parameters, &commitments, player, &shares).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let card = Card::rand(rng);
+        let alpha = Scalar::rand(rng);
+        let (masked, _) =
+            CardProtocol::mask(rng, &parameters, &ctx, key.aggregate_key(), &card, &alpha).unwrap();
+
+        let decryption_key = [4, 1, 2, 0]
+            .iter()
+            .map(|&player| {
+                let pk = *key.verification_key(player).unwrap();
+                let (token, proof) = CardProtocol::compute_reveal_token(
+                    rng,
+                    &parameters,
+                    &ctx,
+                    &secret_shares[player],
+                    &pk,
+                    &masked,
+                )
+                .unwrap();
+                (token, proof, pk)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            CardProtocol::unmask_threshold(&parameters, &ctx, &key, &decryption_key[..3], &masked),
+            Ok(card)
+        );
+        assert_eq!(
+            CardProtocol::unmask_threshold(&parameters, &ctx, &key, &decryption_key, &masked),
+            Ok(card)
+        );
+        assert_eq!(
+            CardProtocol::unmask_threshold(&parameters, &ctx, &key, &decryption_key[..2], &masked),
+            Err(CardProtocolError::NotEnoughRevealTokens(2, 3))
+        );
+
+        let duplicate = [decryption_key[0], decryption_key[1], decryption_key[1]];
+        assert_eq!(
+            CardProtocol::unmask_threshold(&parameters, &ctx, &key, &duplicate, &masked),
+            Err(CardProtocolError::DuplicateRevealToken(1))
+        );
+    }
+
+    #[test]
+    fn test_invalid_dealing() {
+        let rng = &mut thread_rng();
+        let num_of_players = 3;
+        let threshold = 2;
+
+        let parameters = CardProtocol::setup(rng, 2, 4).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        assert_eq!(
+            CardProtocol::deal(rng, &parameters, &ctx, 0, 4, num_of_players).err(),
+            Some(CardProtocolError::InvalidThreshold(4, 3))
+        );
+
+        let dealings = (0..num_of_players)
+            .map(|dealer| {
+                CardProtocol::deal(rng, &parameters, &ctx, dealer, threshold, num_of_players)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let commitments = dealings
+            .iter()
+            .map(|(commitment, _)| commitment.clone())
+            .collect::<Vec<_>>();
+        let mut shares = dealings
+            .iter()
+            .map(|(_, shares)| shares.expose()[0])
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            CardProtocol::combine_shares(&parameters, &commitments, 0, &shares[1..]).err(),
+            Some(CardProtocolError::MismatchedShares(2, 3))
+        );
+
+        // A share that does not match the commitment of its dealer
+        shares[2] = Scalar::rand(rng);
+        assert_eq!(
+            CardProtocol::combine_shares(&parameters, &commitments, 0, &shares).err(),
+            Some(CardProtocolError::InvalidShare(2))
+        );
+
+        // A commitment whose proof was produced for another session
+        let other_ctx = ctx.next_hand();
+        let (other, _) =
+            CardProtocol::deal(rng, &parameters, &other_ctx, 1, threshold, num_of_players).unwrap();
+        let mut commitments = commitments;
+        commitments[1] = other;
+        assert_eq!(
+            CardProtocol::combine_commitments(&parameters, &ctx, threshold, &commitments),
+            Err(CardProtocolError::InvalidDealing(1))
+        );
+    }
+}
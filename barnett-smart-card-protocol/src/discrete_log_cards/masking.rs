@@ -1,11 +1,20 @@
+use super::{
+    DLCards, MaskedCard, Parameters, ProofEncoding, PublicKey, SessionContext, MASKING_RNG_SEED,
+};
 use crate::discrete_log_cards::Card;
-use crate::error::CardProtocolError;
-use crate::Mask;
-use ark_ec::CurveGroup;
+use crate::error::{Blame, CardProtocolError, Operation};
+use crate::{BarnettSmartProtocol, Mask};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_std::rand::{CryptoRng, RngCore};
 
+use proof_essentials::error::CryptoError;
 use proof_essentials::homomorphic_encryption::{
     el_gamal, el_gamal::ElGamal, HomomorphicEncryptionScheme,
 };
+use proof_essentials::utils::secret::Secret;
+use proof_essentials::zkp::proofs::chaum_pedersen_dl_equality::{
+    self, aggregate::CombinedStatement,
+};
 
 impl<C: CurveGroup> Mask<C::ScalarField, ElGamal<C>> for Card<C> {
     fn mask(
@@ -17,19 +26,165 @@ impl<C: CurveGroup> Mask<C::ScalarField, ElGamal<C>> for Card<C> {
         let ciphertext = ElGamal::<C>::encrypt(pp, shared_key, self, r)?;
         Ok(ciphertext)
     }
+
+    fn mask_prepared(
+        &self,
+        pp: &el_gamal::PreparedParameters<C>,
+        shared_key: &el_gamal::PreparedPublicKey<C>,
+        r: &C::ScalarField,
+    ) -> Result<el_gamal::Ciphertext<C>, CardProtocolError> {
+        let ciphertext = ElGamal::<C>::encrypt_prepared(pp, shared_key, self, r)?;
+        Ok(ciphertext)
+    }
+}
+
+/// Chaum-Pedersen statement of every masking: the randomness of `masked_deck[i]` is the discrete
+/// logarithm of both its first component and of its second component minus `deck[i]`
+fn masking_statements<C: CurveGroup>(
+    deck: &[Card<C>],
+    masked_deck: &[MaskedCard<C>],
+) -> Vec<(C::Affine, C::Affine)> {
+    let statement_ciphers = C::normalize_batch(
+        &deck
+            .iter()
+            .zip(masked_deck.iter())
+            .map(|(card, masked_card)| masked_card.1.into_group() - card.0)
+            .collect::<Vec<_>>(),
+    );
+
+    masked_deck
+        .iter()
+        .zip(statement_ciphers)
+        .map(|(masked_card, statement_cipher)| (masked_card.0, statement_cipher))
+        .collect()
+}
+
+impl<'a, C: CurveGroup, E: ProofEncoding<C>> DLCards<'a, C, E> {
+    /// Mask every card of `deck` with the matching masking factor. A single proof shows that each
+    /// output masks the card at the same position, instead of one proof per card with
+    /// [`crate::BarnettSmartProtocol::mask`].
+    pub fn mask_deck<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Parameters<C>,
+        ctx: &SessionContext<C>,
+        shared_key: &PublicKey<C>,
+        deck: &[Card<C>],
+        masking_factors: &Secret<Vec<C::ScalarField>>,
+    ) -> Result<(Vec<MaskedCard<C>>, E::DLEqualityProof), CardProtocolError> {
+        let masking_factors = masking_factors.expose();
+        if masking_factors.len() != deck.len() {
+            return Err(CardProtocolError::MismatchedDeckLength(
+                masking_factors.len(),
+                deck.len(),
+            ));
+        }
+
+        let masked_deck = deck
+            .iter()
+            .zip(masking_factors.iter())
+            .map(|(card, r)| card.mask(&pp.enc_parameters, shared_key, r))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Map to Chaum-Pedersen parameters and statements
+        let cp_parameters =
+            chaum_pedersen_dl_equality::Parameters::new(&pp.enc_parameters.generator, shared_key);
+        let statements = masking_statements(deck, &masked_deck);
+
+        let mut fs_rng = ctx.fiat_shamir_rng(MASKING_RNG_SEED);
+        let proof = chaum_pedersen_dl_equality::DLEquality::prove_combined(
+            rng,
+            &cp_parameters,
+            &CombinedStatement::new(&statements),
+            masking_factors,
+            &mut fs_rng,
+        )?;
+
+        Ok((masked_deck, proof))
+    }
+
+    /// Verify a proof produced by [`DLCards::mask_deck`]
+    pub fn verify_masked_deck(
+        pp: &Parameters<C>,
+        ctx: &SessionContext<C>,
+        shared_key: &PublicKey<C>,
+        deck: &[Card<C>],
+        masked_deck: &[MaskedCard<C>],
+        proof: &E::DLEqualityProof,
+    ) -> Result<(), CryptoError> {
+        if masked_deck.len() != deck.len() {
+            return Err(CryptoError::AggregateStatementLengthError(
+                deck.len(),
+                masked_deck.len(),
+            ));
+        }
+
+        // Map to Chaum-Pedersen parameters and statements
+        let cp_parameters =
+            chaum_pedersen_dl_equality::Parameters::new(&pp.enc_parameters.generator, shared_key);
+        let statements = masking_statements(deck, masked_deck);
+
+        let mut fs_rng = ctx.fiat_shamir_rng(MASKING_RNG_SEED);
+        chaum_pedersen_dl_equality::DLEquality::verify_combined(
+            &cp_parameters,
+            &CombinedStatement::new(&statements),
+            proof,
+            &mut fs_rng,
+        )
+    }
+
+    /// Same as [`BarnettSmartProtocol::verify_mask`], blaming `player` if the proof is invalid
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_mask_from(
+        pp: &Parameters<C>,
+        ctx: &SessionContext<C>,
+        shared_key: &PublicKey<C>,
+        player: usize,
+        pk: &PublicKey<C>,
+        card: &Card<C>,
+        masked_card: &MaskedCard<C>,
+        proof: &E::DLEqualityProof,
+    ) -> Result<(), CardProtocolError> {
+        Self::verify_mask(pp, ctx, shared_key, card, masked_card, proof).blame(
+            player,
+            pk,
+            Operation::Masking,
+        )
+    }
+
+    /// Same as [`DLCards::verify_masked_deck`], blaming `player` if the proof is invalid
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_masked_deck_from(
+        pp: &Parameters<C>,
+        ctx: &SessionContext<C>,
+        shared_key: &PublicKey<C>,
+        player: usize,
+        pk: &PublicKey<C>,
+        deck: &[Card<C>],
+        masked_deck: &[MaskedCard<C>],
+        proof: &E::DLEqualityProof,
+    ) -> Result<(), CardProtocolError> {
+        Self::verify_masked_deck(pp, ctx, shared_key, deck, masked_deck, proof).blame(
+            player,
+            pk,
+            Operation::Masking,
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::discrete_log_cards;
-    use crate::BarnettSmartProtocol;
+    use crate::error::{CardProtocolError, Operation};
+    use crate::{BarnettSmartProtocol, Mask};
     use ark_ec::{AffineRepr, CurveGroup};
 
     use ark_ff::UniformRand;
     use ark_serialize::CanonicalDeserialize;
     use ark_serialize::CanonicalSerialize;
-    use ark_std::rand::Rng;
+    use ark_std::rand::{CryptoRng, RngCore};
     use proof_essentials::error::CryptoError;
+    use proof_essentials::utils::rand::sample_vector;
+    use proof_essentials::utils::secret::Secret;
     use proof_essentials::zkp::proofs::chaum_pedersen_dl_equality;
     use rand::thread_rng;
 
@@ -39,7 +194,9 @@ mod test {
 
     // Instantiate concrete type for our card protocol
     type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
+    type CompactCardProtocol<'a> = discrete_log_cards::CompactDLCards<'a, Curve>;
     type CardParameters = discrete_log_cards::Parameters<Curve>;
+    type SessionContext = discrete_log_cards::SessionContext<Curve>;
     type PublicKey = discrete_log_cards::PublicKey<Curve>;
     type SecretKey = discrete_log_cards::PlayerSecretKey<Curve>;
 
@@ -48,7 +205,7 @@ mod test {
 
     type MaskingProof = chaum_pedersen_dl_equality::proof::Proof<Curve>;
 
-    fn setup_players<R: Rng>(
+    fn setup_players<R: CryptoRng + RngCore>(
         rng: &mut R,
         parameters: &CardParameters,
         num_of_players: usize,
@@ -57,8 +214,8 @@ mod test {
         let mut expected_shared_key = PublicKey::zero().into_group();
 
         for i in 0..parameters.n {
-            players.push(CardProtocol::player_keygen(rng, &parameters).unwrap());
-            expected_shared_key = expected_shared_key + players[i].0
+            players.push(CardProtocol::player_keygen(rng, parameters).unwrap());
+            expected_shared_key += players[i].0
         }
 
         (players, expected_shared_key.into_affine())
@@ -73,14 +230,22 @@ mod test {
         let num_of_players = 10;
 
         let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
 
-        let (_, aggregate_key) = setup_players(rng, &parameters, num_of_players);
+        let (players, aggregate_key) = setup_players(rng, &parameters, num_of_players);
 
         let some_card = Card::rand(rng);
         let some_random = Scalar::rand(rng);
 
-        let (masked, mut masking_proof): (MaskedCard, MaskingProof) =
-            CardProtocol::mask(rng, &parameters, &aggregate_key, &some_card, &some_random).unwrap();
+        let (masked, mut masking_proof): (MaskedCard, MaskingProof) = CardProtocol::mask(
+            rng,
+            &parameters,
+            &ctx,
+            &aggregate_key,
+            &some_card,
+            &some_random,
+        )
+        .unwrap();
 
         let mut data = Vec::with_capacity(masking_proof.compressed_size());
         masking_proof.serialize_compressed(&mut data).unwrap();
@@ -90,6 +255,7 @@ mod test {
             Ok(()),
             CardProtocol::verify_mask(
                 &parameters,
+                &ctx,
                 &aggregate_key,
                 &some_card,
                 &masked,
@@ -102,6 +268,7 @@ mod test {
         assert_eq!(
             CardProtocol::verify_mask(
                 &parameters,
+                &ctx,
                 &aggregate_key,
                 &some_card,
                 &wrong_masked,
@@ -110,6 +277,140 @@ mod test {
             Err(CryptoError::ProofVerificationError(String::from(
                 "Chaum-Pedersen"
             )))
+        );
+
+        assert_eq!(
+            CardProtocol::verify_mask_from(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                0,
+                &players[0].0,
+                &some_card,
+                &wrong_masked,
+                &masking_proof
+            ),
+            Err(CardProtocolError::invalid_player_proof(
+                0,
+                &players[0].0,
+                Operation::Masking,
+                CryptoError::ProofVerificationError(String::from("Chaum-Pedersen"))
+            ))
         )
     }
+
+    #[test]
+    fn test_mask_deck() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+        let (players, aggregate_key) = setup_players(rng, &parameters, 4);
+
+        let deck: Vec<Card> = sample_vector(rng, m * n);
+        let masking_factors = Secret::new(sample_vector(rng, m * n));
+        let (masked_deck, proof) = CardProtocol::mask_deck(
+            rng,
+            &parameters,
+            &ctx,
+            &aggregate_key,
+            &deck,
+            &masking_factors,
+        )
+        .unwrap();
+
+        for ((card, masked), r) in deck
+            .iter()
+            .zip(masked_deck.iter())
+            .zip(masking_factors.expose().iter())
+        {
+            assert_eq!(
+                card.mask(&parameters.enc_parameters, &aggregate_key, r),
+                Ok(*masked)
+            );
+        }
+        assert_eq!(
+            CardProtocol::verify_masked_deck(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &deck,
+                &masked_deck,
+                &proof
+            ),
+            Ok(())
+        );
+
+        // Every masked card must match the card at the same position
+        let mut swapped_deck = masked_deck.clone();
+        swapped_deck.swap(0, 1);
+        assert_eq!(
+            CardProtocol::verify_masked_deck(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &deck,
+                &swapped_deck,
+                &proof
+            ),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Chaum-Pedersen"
+            )))
+        );
+
+        assert_eq!(
+            CardProtocol::verify_masked_deck_from(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                1,
+                &players[1].0,
+                &deck,
+                &swapped_deck,
+                &proof
+            ),
+            Err(CardProtocolError::invalid_player_proof(
+                1,
+                &players[1].0,
+                Operation::Masking,
+                CryptoError::ProofVerificationError(String::from("Chaum-Pedersen"))
+            ))
+        );
+
+        assert_eq!(
+            CardProtocol::verify_masked_deck(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &deck,
+                &masked_deck[1..],
+                &proof
+            ),
+            Err(CryptoError::AggregateStatementLengthError(m * n, m * n - 1))
+        );
+
+        // Same with compact proofs
+        let (masked_deck, compact_proof) = CompactCardProtocol::mask_deck(
+            rng,
+            &parameters,
+            &ctx,
+            &aggregate_key,
+            &deck,
+            &masking_factors,
+        )
+        .unwrap();
+        assert_eq!(
+            CompactCardProtocol::verify_masked_deck(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &deck,
+                &masked_deck,
+                &compact_proof
+            ),
+            Ok(())
+        );
+    }
 }
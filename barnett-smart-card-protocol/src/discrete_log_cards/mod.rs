@@ -2,22 +2,25 @@ use super::BarnettSmartProtocol;
 use super::{Mask, Remask, Reveal};
 use ark_std::ops::Mul;
 
-use crate::error::CardProtocolError;
+use crate::error::{Blame, CardProtocolError, Operation};
 
 use anyhow::Result;
-use ark_ec::CurveGroup;
-use ark_ff::{One, PrimeField};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::One;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
-use ark_std::Zero;
-use blake2::Blake2s256;
+use ark_std::rand::{CryptoRng, Rng, RngCore};
+use ark_std::{cfg_iter, Zero};
+use blake2::{Blake2s256, Digest};
 use proof_essentials::error::CryptoError;
 use proof_essentials::homomorphic_encryption::{
-    el_gamal, el_gamal::ElGamal, HomomorphicEncryptionScheme,
+    el_gamal, el_gamal::ElGamal, pool::EncryptionOfZeroPool, HomomorphicEncryptionScheme,
 };
+use proof_essentials::utils::constant_time::mul_secret;
+use proof_essentials::utils::hash_to_curve::hash_to_curve;
 use proof_essentials::utils::permutation::Permutation;
 use proof_essentials::utils::rand::FiatShamirRng;
+use proof_essentials::utils::secret::Secret;
 use proof_essentials::vector_commitment::pedersen::PedersenCommitment;
 use proof_essentials::vector_commitment::{pedersen, HomomorphicCommitmentScheme};
 use proof_essentials::zkp::{
@@ -26,17 +29,55 @@ use proof_essentials::zkp::{
     ArgumentOfKnowledge,
 };
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 // mod key_ownership;
+mod ceremony;
+mod dkg;
 mod masking;
 mod remasking;
 mod reveal;
 mod tests;
 
-pub struct DLCards<'a, C: CurveGroup> {
+pub use ceremony::{Contribution, SetupTranscript};
+pub use dkg::{DealerCommitment, Dealing, ThresholdKey};
+
+/// Discrete-log instantiation of the protocol. `E` selects how the Schnorr and Chaum-Pedersen proofs
+/// are encoded, see [`FullProofs`] and [`CompactProofs`].
+pub struct DLCards<'a, C: CurveGroup, E = FullProofs> {
     _group: &'a PhantomData<C>,
+    _encoding: PhantomData<E>,
+}
+
+/// [`DLCards`] exchanging [`CompactProofs`]
+pub type CompactDLCards<'a, C> = DLCards<'a, C, CompactProofs>;
+
+/// Encoding of the key ownership, masking, remasking and reveal proofs of [`DLCards`]
+pub trait ProofEncoding<C: CurveGroup> {
+    type KeyOwnershipProof: schnorr_identification::ProofEncoding<C>;
+    type DLEqualityProof: chaum_pedersen_dl_equality::ProofEncoding<C>;
 }
 
-#[derive(CanonicalDeserialize, CanonicalSerialize)]
+/// Proofs carrying their commitments, which lets batches of proofs be verified with a single
+/// multi-scalar multiplication
+pub struct FullProofs;
+
+/// Proofs carrying the challenge and the response only: a Chaum-Pedersen proof is two scalars
+/// instead of two points and a scalar. Every proof of a batch is verified on its own.
+pub struct CompactProofs;
+
+impl<C: CurveGroup> ProofEncoding<C> for FullProofs {
+    type KeyOwnershipProof = schnorr_identification::proof::Proof<C>;
+    type DLEqualityProof = chaum_pedersen_dl_equality::proof::Proof<C>;
+}
+
+impl<C: CurveGroup> ProofEncoding<C> for CompactProofs {
+    type KeyOwnershipProof = schnorr_identification::proof::CompactProof<C>;
+    type DLEqualityProof = chaum_pedersen_dl_equality::proof::CompactProof<C>;
+}
+
+#[derive(Clone, CanonicalDeserialize, CanonicalSerialize)]
 pub struct Parameters<C: CurveGroup> {
     m: usize,
     n: usize,
@@ -61,6 +102,42 @@ impl<C: CurveGroup> Parameters<C> {
             generator,
         }
     }
+
+    /// Transparently derive the parameters from a public `domain` string. Every base is obtained
+    /// by hashing to the curve, so no party knows discrete-log relations between them.
+    pub fn from_seed(domain: &[u8], m: usize, n: usize) -> Self {
+        let base = |label: &[u8], index: usize| {
+            hash_to_curve::<C, Blake2s256>(domain, label, index as u64)
+        };
+
+        let enc_parameters = el_gamal::Parameters {
+            generator: base(b"ElGamal generator", 0),
+        };
+        let commit_parameters = pedersen::CommitKey::new(
+            (0..n).map(|i| base(b"Pedersen g", i)).collect(),
+            base(b"Pedersen h", 0),
+        );
+        let generator = el_gamal::Plaintext::<C>(base(b"Shuffle generator", 0));
+
+        Self::new(m, n, enc_parameters, commit_parameters, generator)
+    }
+
+    /// Recompute the parameters from `domain` and check that they match `self`
+    pub fn verify_parameters(&self, domain: &[u8]) -> Result<(), CardProtocolError> {
+        if self.digest() != Self::from_seed(domain, self.m, self.n).digest() {
+            return Err(CardProtocolError::InvalidParameters);
+        }
+
+        Ok(())
+    }
+
+    /// Hash of the serialized parameters, used to bind a [`SessionContext`] to these parameters
+    pub fn digest(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.serialize_compressed(&mut bytes)
+            .expect("failed to serialize parameters");
+        Blake2s256::digest(&bytes).to_vec()
+    }
 }
 
 pub type PublicKey<C> = el_gamal::PublicKey<C>;
@@ -80,13 +157,297 @@ pub type MaskedCard<C> = el_gamal::Ciphertext<C>;
 /// then be aggregated to reveal the card.
 pub type RevealToken<C> = el_gamal::Plaintext<C>;
 
+/// Proof that a deck of masked cards was correctly shuffled and remasked
+pub type ShuffleProof<C> =
+    shuffle::proof::Proof<<C as ark_ec::Group>::ScalarField, ElGamal<C>, PedersenCommitment<C>>;
+
+/// Public description of a game session. Every proof produced or verified by [`DLCards`] absorbs
+/// this context into its Fiat-Shamir transcript, which binds the proof to one table and one hand:
+/// a proof generated under one context will not verify under any other.
+///
+/// Players usually register their keys under a context with an empty roster (the roster is not
+/// known before registration completes) and then switch to the full roster for the hand itself.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalDeserialize, CanonicalSerialize)]
+pub struct SessionContext<C: CurveGroup> {
+    pub game_id: Vec<u8>,
+    pub hand_number: u64,
+    pub parameters_digest: Vec<u8>,
+    pub roster: Vec<PublicKey<C>>,
+}
+
+impl<C: CurveGroup> SessionContext<C> {
+    pub fn new(game_id: &[u8], hand_number: u64, pp: &Parameters<C>) -> Self {
+        Self {
+            game_id: game_id.to_vec(),
+            hand_number,
+            parameters_digest: pp.digest(),
+            roster: Vec::new(),
+        }
+    }
+
+    /// Set the ordered list of player public keys taking part in this session
+    pub fn with_roster(mut self, roster: &[PublicKey<C>]) -> Self {
+        self.roster = roster.to_vec();
+        self
+    }
+
+    /// Context for the next hand played at the same table by the same players
+    pub fn next_hand(&self) -> Self {
+        Self {
+            hand_number: self.hand_number + 1,
+            ..self.clone()
+        }
+    }
+
+    fn fiat_shamir_rng(&self, seed: &[u8]) -> FiatShamirRng<Blake2s256> {
+        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&seed);
+        fs_rng.absorb(self);
+        fs_rng
+    }
+}
+
+/// Encryption parameters and aggregate key of a game with precomputed tables, obtained from
+/// [`DLCards::prepare_key`]
+#[derive(Clone)]
+pub struct PreparedKey<C: CurveGroup> {
+    enc_parameters: el_gamal::PreparedParameters<C>,
+    shared_key: el_gamal::PreparedPublicKey<C>,
+}
+
+impl<C: CurveGroup> PreparedKey<C> {
+    pub fn enc_parameters(&self) -> &el_gamal::PreparedParameters<C> {
+        &self.enc_parameters
+    }
+
+    pub fn shared_key(&self) -> &el_gamal::PreparedPublicKey<C> {
+        &self.shared_key
+    }
+}
+
+/// Permutation, masking factors and the part of the shuffle proof that only depends on them,
+/// obtained from [`DLCards::precompute_shuffle`]
+pub struct ShufflePrecomputation<C: CurveGroup> {
+    permutation: Permutation,
+    masking_factors: Secret<Vec<C::ScalarField>>,
+    masking_ciphers: Secret<Vec<el_gamal::Ciphertext<C>>>,
+    proof: shuffle::prover::Precomputation<C::ScalarField, PedersenCommitment<C>>,
+}
+
+/// A player accepted during key registration. `index` is the position of the player in the
+/// registration list and `player_info` is the serialized public information bound to their key.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalDeserialize, CanonicalSerialize)]
+pub struct RosterEntry<C: CurveGroup> {
+    pub index: usize,
+    pub public_key: PublicKey<C>,
+    pub player_info: Vec<u8>,
+}
+
+/// The well-formed list of players produced by [`DLCards::register_players`]: every public key
+/// is a non-identity point with a valid proof of ownership, and no key or public information is
+/// registered twice. The roster also holds the aggregate public key of its players.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize)]
+pub struct Roster<C: CurveGroup> {
+    players: Vec<RosterEntry<C>>,
+    aggregate_key: PublicKey<C>,
+}
+
+impl<C: CurveGroup> Roster<C> {
+    pub fn players(&self) -> &[RosterEntry<C>] {
+        &self.players
+    }
+
+    pub fn aggregate_key(&self) -> &PublicKey<C> {
+        &self.aggregate_key
+    }
+
+    /// Public keys of all registered players, ordered by player index
+    pub fn public_keys(&self) -> Vec<PublicKey<C>> {
+        self.players.iter().map(|entry| entry.public_key).collect()
+    }
+
+    /// Index of the player owning the given public key, if they are registered
+    pub fn index_of(&self, pk: &PublicKey<C>) -> Option<usize> {
+        self.players
+            .iter()
+            .find(|entry| entry.public_key == *pk)
+            .map(|entry| entry.index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+/// Verifier of the shuffles made under the aggregate key of a game. It caches the values that only
+/// depend on the parameters and the key, so it is worth keeping for the whole game when verifying
+/// many shuffles.
+pub struct ShuffleVerifier<'a, C: CurveGroup> {
+    verifier: shuffle::verifier::PreparedShuffleVerifier<
+        'a,
+        C::ScalarField,
+        ElGamal<C>,
+        PedersenCommitment<C>,
+    >,
+}
+
+impl<'a, C: CurveGroup> ShuffleVerifier<'a, C> {
+    pub fn new(pp: &'a Parameters<C>, shared_key: &'a PublicKey<C>) -> Result<Self, CryptoError> {
+        let parameters = shuffle::Parameters::new(
+            &pp.enc_parameters,
+            shared_key,
+            &pp.commit_parameters,
+            &pp.generator,
+        );
+
+        Ok(Self {
+            verifier: shuffle::verifier::PreparedShuffleVerifier::new(parameters, pp.m, pp.n)?,
+        })
+    }
+
+    /// Same as [`DLCards::verify_shuffle`]
+    pub fn verify(
+        &self,
+        ctx: &SessionContext<C>,
+        original_deck: &[MaskedCard<C>],
+        shuffled_deck: &[MaskedCard<C>],
+        proof: &ShuffleProof<C>,
+    ) -> Result<(), CryptoError> {
+        let mut fs_rng = ctx.fiat_shamir_rng(SHUFFLE_RNG_SEED);
+        self.verifier
+            .verify(original_deck, shuffled_deck, proof, &mut fs_rng)
+    }
+
+    /// Same as [`DLCards::verify_shuffle_chain`]
+    pub fn verify_chain(
+        &self,
+        ctx: &SessionContext<C>,
+        decks: &[Vec<MaskedCard<C>>],
+        proofs: &[(usize, ShuffleProof<C>, PublicKey<C>)],
+    ) -> Result<(), CardProtocolError> {
+        if decks.len() != proofs.len() + 1 {
+            return Err(CardProtocolError::MismatchedShuffleChain(
+                decks.len(),
+                proofs.len(),
+            ));
+        }
+
+        let instances = decks
+            .windows(2)
+            .zip(proofs.iter())
+            .map(|(decks, (_, proof, _))| (decks[0].as_slice(), decks[1].as_slice(), proof))
+            .collect::<Vec<_>>();
+
+        let fs_rng = ctx.fiat_shamir_rng(SHUFFLE_RNG_SEED);
+        self.verifier
+            .batch_verify(&instances, &fs_rng)
+            .map_err(|error| match error {
+                CryptoError::InvalidBatchProof { index, source } => {
+                    let (player, _, pk) = &proofs[index];
+                    CardProtocolError::invalid_player_proof(
+                        *player,
+                        pk,
+                        Operation::Shuffle,
+                        *source,
+                    )
+                }
+                error => CardProtocolError::ProofVerificationError(error),
+            })
+    }
+}
+
+/// Shuffle argument parameters using the tables of `prepared_key`
+fn shuffle_parameters<'a, C: CurveGroup>(
+    pp: &'a Parameters<C>,
+    prepared_key: &'a PreparedKey<C>,
+) -> shuffle::Parameters<'a, C::ScalarField, ElGamal<C>, PedersenCommitment<C>> {
+    shuffle::Parameters::new(
+        &prepared_key.enc_parameters.parameters,
+        prepared_key.shared_key.public_key(),
+        &pp.commit_parameters,
+        &pp.generator,
+    )
+    .with_prepared(&prepared_key.enc_parameters, &prepared_key.shared_key)
+}
+
+/// Shuffle precomputation with the encryptions of zero `masking_ciphers` of `masking_factors`
+fn shuffle_precomputation<R: CryptoRng + RngCore, C: CurveGroup>(
+    rng: &mut R,
+    pp: &Parameters<C>,
+    prepared_key: &PreparedKey<C>,
+    permutation: &Permutation,
+    masking_factors: Secret<Vec<C::ScalarField>>,
+    masking_ciphers: Secret<Vec<el_gamal::Ciphertext<C>>>,
+) -> Result<ShufflePrecomputation<C>, CardProtocolError> {
+    for size in [permutation.size, masking_factors.expose().len()] {
+        if size != pp.m * pp.n {
+            return Err(CardProtocolError::MismatchedDeckLength(size, pp.m * pp.n));
+        }
+    }
+
+    let proof = shuffle::prover::Prover::precompute(
+        rng,
+        &shuffle_parameters(pp, prepared_key),
+        permutation,
+        pp.m,
+        pp.n,
+    )?;
+
+    Ok(ShufflePrecomputation {
+        permutation: permutation.clone(),
+        masking_factors,
+        masking_ciphers,
+        proof,
+    })
+}
+
+/// Chaum-Pedersen proof that `remasked` is `original` plus an encryption of zero with randomness
+/// `alpha`
+fn prove_remasking<R: CryptoRng + RngCore, C: CurveGroup, P>(
+    rng: &mut R,
+    pp: &Parameters<C>,
+    ctx: &SessionContext<C>,
+    shared_key: &PublicKey<C>,
+    original: &MaskedCard<C>,
+    remasked: &MaskedCard<C>,
+    alpha: &C::ScalarField,
+) -> Result<P, CardProtocolError>
+where
+    P: chaum_pedersen_dl_equality::ProofEncoding<C>,
+{
+    // Map to Chaum-Pedersen parameters
+    let cp_parameters =
+        chaum_pedersen_dl_equality::Parameters::new(&pp.enc_parameters.generator, shared_key);
+
+    // Map to Chaum-Pedersen statement
+    let minus_one = -C::ScalarField::one();
+    let negative_original = *original * minus_one;
+    let statement_cipher = *remasked + negative_original;
+    let cp_statement =
+        chaum_pedersen_dl_equality::Statement::new(&statement_cipher.0, &statement_cipher.1);
+
+    let mut fs_rng = ctx.fiat_shamir_rng(REMASKING_RNG_SEED);
+    let proof = chaum_pedersen_dl_equality::DLEquality::prove(
+        rng,
+        &cp_parameters,
+        &cp_statement,
+        alpha,
+        &mut fs_rng,
+    )?;
+
+    Ok(proof)
+}
+
 const KEY_OWN_RNG_SEED: &[u8] = b"Key Ownership Proof";
 const MASKING_RNG_SEED: &[u8] = b"Masking Proof";
 const REMASKING_RNG_SEED: &[u8] = b"Remasking Proof";
 const REVEAL_RNG_SEED: &[u8] = b"Reveal Proof";
 const SHUFFLE_RNG_SEED: &[u8] = b"Shuffle Proof";
 
-impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
+impl<'a, C: CurveGroup, E: ProofEncoding<C>> BarnettSmartProtocol for DLCards<'a, C, E> {
     type Scalar = C::ScalarField;
     type Enc = ElGamal<C>;
     type Comm = PedersenCommitment<C>;
@@ -94,15 +455,21 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
     type PlayerPublicKey = PublicKey<C>;
     type PlayerSecretKey = PlayerSecretKey<C>;
     type AggregatePublicKey = PublicKey<C>;
+    type PreparedKey = PreparedKey<C>;
+    type ShufflePrecomputation = ShufflePrecomputation<C>;
+    type ShuffleVerifier<'v> = ShuffleVerifier<'v, C>;
+    type SessionContext = SessionContext<C>;
+    type Roster = Roster<C>;
+    type ThresholdKey = ThresholdKey<C>;
 
     type Card = Card<C>;
     type MaskedCard = MaskedCard<C>;
     type RevealToken = RevealToken<C>;
 
-    type ZKProofKeyOwnership = schnorr_identification::proof::Proof<C>;
-    type ZKProofMasking = chaum_pedersen_dl_equality::proof::Proof<C>;
-    type ZKProofRemasking = chaum_pedersen_dl_equality::proof::Proof<C>;
-    type ZKProofReveal = chaum_pedersen_dl_equality::proof::Proof<C>;
+    type ZKProofKeyOwnership = E::KeyOwnershipProof;
+    type ZKProofMasking = E::DLEqualityProof;
+    type ZKProofRemasking = E::DLEqualityProof;
+    type ZKProofReveal = E::DLEqualityProof;
     type ZKProofShuffle = shuffle::proof::Proof<Self::Scalar, Self::Enc, Self::Comm>;
 
     fn setup<R: Rng>(
@@ -123,7 +490,7 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         ))
     }
 
-    fn player_keygen<R: Rng>(
+    fn player_keygen<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
     ) -> Result<(Self::PlayerPublicKey, Self::PlayerSecretKey), CardProtocolError> {
@@ -132,32 +499,34 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         Ok((pk, sk))
     }
 
-    fn prove_key_ownership<B: CanonicalSerialize, R: Rng>(
+    fn prove_key_ownership<B: CanonicalSerialize, R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         pk: &Self::PlayerPublicKey,
         sk: &Self::PlayerSecretKey,
         player_public_info: &B,
     ) -> Result<Self::ZKProofKeyOwnership, CryptoError> {
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&KEY_OWN_RNG_SEED);
+        let mut fs_rng = ctx.fiat_shamir_rng(KEY_OWN_RNG_SEED);
         fs_rng.absorb(player_public_info);
 
         schnorr_identification::SchnorrIdentification::prove(
             rng,
             &pp.enc_parameters.generator,
             pk,
-            sk,
+            sk.expose(),
             &mut fs_rng,
         )
     }
 
     fn verify_key_ownership<B: CanonicalSerialize>(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         pk: &Self::PlayerPublicKey,
         player_public_info: &B,
         proof: &Self::ZKProofKeyOwnership,
     ) -> Result<(), CryptoError> {
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&KEY_OWN_RNG_SEED);
+        let mut fs_rng = ctx.fiat_shamir_rng(KEY_OWN_RNG_SEED);
         fs_rng.absorb(player_public_info);
         schnorr_identification::SchnorrIdentification::verify(
             &pp.enc_parameters.generator,
@@ -167,22 +536,91 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         )
     }
 
+    fn register_players<B: CanonicalSerialize>(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        player_keys_proof_info: &[(Self::PlayerPublicKey, Self::ZKProofKeyOwnership, B)],
+    ) -> Result<Self::Roster, CardProtocolError> {
+        let mut players: Vec<RosterEntry<C>> = Vec::with_capacity(player_keys_proof_info.len());
+
+        // Check the shape of the roster before paying for proof verification
+        for (index, (pk, _, player_public_info)) in player_keys_proof_info.iter().enumerate() {
+            if pk.is_zero() {
+                return Err(CardProtocolError::IdentityPublicKey(index));
+            }
+
+            let mut player_info = Vec::new();
+            player_public_info
+                .serialize_compressed(&mut player_info)
+                .expect("failed to serialize player information");
+
+            if let Some(other) = players.iter().find(|entry| entry.public_key == *pk) {
+                return Err(CardProtocolError::DuplicatePublicKey(other.index, index));
+            }
+
+            if let Some(other) = players
+                .iter()
+                .find(|entry| entry.player_info == player_info)
+            {
+                return Err(CardProtocolError::DuplicatePlayerInfo(other.index, index));
+            }
+
+            players.push(RosterEntry {
+                index,
+                public_key: *pk,
+                player_info,
+            });
+        }
+
+        // All the proofs are checked at once, the batch blames the first invalid one
+        let instances = player_keys_proof_info
+            .iter()
+            .map(|(pk, proof, player_public_info)| {
+                let mut fs_rng = ctx.fiat_shamir_rng(KEY_OWN_RNG_SEED);
+                fs_rng.absorb(player_public_info);
+                (*pk, proof, fs_rng)
+            })
+            .collect::<Vec<_>>();
+        schnorr_identification::SchnorrIdentification::batch_verify(
+            &pp.enc_parameters.generator,
+            &instances,
+        )
+        .map_err(|error| match error {
+            CryptoError::InvalidBatchProof { index, source } => {
+                CardProtocolError::invalid_player_proof(
+                    index,
+                    &player_keys_proof_info[index].0,
+                    Operation::KeyOwnership,
+                    *source,
+                )
+            }
+            error => CardProtocolError::ProofVerificationError(error),
+        })?;
+
+        let acc = player_keys_proof_info
+            .iter()
+            .fold(C::zero(), |acc, (pk, _, _)| acc + pk);
+
+        Ok(Roster {
+            players,
+            aggregate_key: acc.into_affine(),
+        })
+    }
+
     fn compute_aggregate_key<B: CanonicalSerialize>(
         pp: &Self::Parameters,
-        player_keys_proof_info: &Vec<(Self::PlayerPublicKey, Self::ZKProofKeyOwnership, B)>,
+        ctx: &Self::SessionContext,
+        player_keys_proof_info: &[(Self::PlayerPublicKey, Self::ZKProofKeyOwnership, B)],
     ) -> Result<Self::AggregatePublicKey, CardProtocolError> {
-        let mut acc = C::zero();
-        for (pk, proof, player_public_info) in player_keys_proof_info {
-            Self::verify_key_ownership(pp, pk, player_public_info, proof)?;
-            acc += *pk;
-        }
+        let roster = Self::register_players(pp, ctx, player_keys_proof_info)?;
 
-        Ok(acc.into_affine())
+        Ok(roster.aggregate_key)
     }
 
-    fn mask<R: Rng>(
+    fn mask<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         original_card: &Self::Card,
         r: &Self::Scalar,
@@ -200,7 +638,7 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         let cp_statement =
             chaum_pedersen_dl_equality::Statement::new(&masked_card.0, &statement_cipher);
 
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&MASKING_RNG_SEED);
+        let mut fs_rng = ctx.fiat_shamir_rng(MASKING_RNG_SEED);
         let proof = chaum_pedersen_dl_equality::DLEquality::prove(
             rng,
             &cp_parameters,
@@ -214,6 +652,7 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
 
     fn verify_mask(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         card: &Self::Card,
         masked_card: &Self::MaskedCard,
@@ -230,7 +669,7 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         let cp_statement =
             chaum_pedersen_dl_equality::Statement::new(&masked_card.0, &statement_cipher);
 
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&MASKING_RNG_SEED);
+        let mut fs_rng = ctx.fiat_shamir_rng(MASKING_RNG_SEED);
         chaum_pedersen_dl_equality::DLEquality::verify(
             &cp_parameters,
             &cp_statement,
@@ -239,33 +678,49 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         )
     }
 
-    fn remask<R: Rng>(
+    fn remask<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         original_card: &Self::MaskedCard,
         alpha: &Self::Scalar,
     ) -> Result<(Self::MaskedCard, Self::ZKProofRemasking), CardProtocolError> {
         let remasked = original_card.remask(&pp.enc_parameters, shared_key, alpha)?;
+        let proof = prove_remasking(rng, pp, ctx, shared_key, original_card, &remasked, alpha)?;
 
-        // Map to Chaum-Pedersen parameters
-        let cp_parameters =
-            chaum_pedersen_dl_equality::Parameters::new(&pp.enc_parameters.generator, shared_key);
+        Ok((remasked, proof))
+    }
 
-        // Map to Chaum-Pedersen statement
-        let minus_one = -C::ScalarField::one();
-        let negative_original = *original_card * minus_one;
-        let statement_cipher = remasked + negative_original;
-        let cp_statement =
-            chaum_pedersen_dl_equality::Statement::new(&statement_cipher.0, &statement_cipher.1);
+    fn encryption_pool(
+        pp: &Self::Parameters,
+        shared_key: &Self::AggregatePublicKey,
+    ) -> EncryptionOfZeroPool<Self::Scalar, Self::Enc> {
+        EncryptionOfZeroPool::new(&pp.enc_parameters, shared_key)
+    }
 
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&REMASKING_RNG_SEED);
-        let proof = chaum_pedersen_dl_equality::DLEquality::prove(
+    fn remask_from_pool<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        shared_key: &Self::AggregatePublicKey,
+        original_card: &Self::MaskedCard,
+        pool: &mut EncryptionOfZeroPool<Self::Scalar, Self::Enc>,
+    ) -> Result<(Self::MaskedCard, Self::ZKProofRemasking), CardProtocolError> {
+        if !pool.is_for(&pp.enc_parameters, shared_key) {
+            return Err(CardProtocolError::MismatchedEncryptionPool);
+        }
+
+        let (alpha, encryption_of_zero) = pool.draw(rng, 1)?;
+        let remasked = original_card.remask_with(&encryption_of_zero.expose()[0]);
+        let proof = prove_remasking(
             rng,
-            &cp_parameters,
-            &cp_statement,
-            alpha,
-            &mut fs_rng,
+            pp,
+            ctx,
+            shared_key,
+            original_card,
+            &remasked,
+            &alpha.expose()[0],
         )?;
 
         Ok((remasked, proof))
@@ -273,6 +728,7 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
 
     fn verify_remask(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         original_masked: &Self::MaskedCard,
         remasked: &Self::MaskedCard,
@@ -289,7 +745,7 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         let cp_statement =
             chaum_pedersen_dl_equality::Statement::new(&statement_cipher.0, &statement_cipher.1);
 
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&REMASKING_RNG_SEED);
+        let mut fs_rng = ctx.fiat_shamir_rng(REMASKING_RNG_SEED);
         chaum_pedersen_dl_equality::DLEquality::verify(
             &cp_parameters,
             &cp_statement,
@@ -298,20 +754,16 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         )
     }
 
-    fn compute_reveal_token<R: Rng>(
+    fn compute_reveal_token<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         sk: &Self::PlayerSecretKey,
         pk: &Self::PlayerPublicKey,
         masked_card: &Self::MaskedCard,
     ) -> Result<(Self::RevealToken, Self::ZKProofReveal), CardProtocolError> {
-        let reveal_token: RevealToken<C> = el_gamal::Plaintext(
-            masked_card
-                .0
-                .into()
-                .mul_bigint(sk.into_bigint())
-                .into_affine(),
-        );
+        let reveal_token: RevealToken<C> =
+            el_gamal::Plaintext(mul_secret::<C>(&masked_card.0, sk.expose()).into_affine());
 
         // Map to Chaum-Pedersen parameters
         let cp_parameters = chaum_pedersen_dl_equality::Parameters::new(
@@ -322,12 +774,12 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         // Map to Chaum-Pedersen parameters
         let cp_statement = chaum_pedersen_dl_equality::Statement::new(&reveal_token.0, pk);
 
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&REVEAL_RNG_SEED);
+        let mut fs_rng = ctx.fiat_shamir_rng(REVEAL_RNG_SEED);
         let proof = chaum_pedersen_dl_equality::DLEquality::prove(
             rng,
             &cp_parameters,
             &cp_statement,
-            sk,
+            sk.expose(),
             &mut fs_rng,
         )?;
 
@@ -336,6 +788,7 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
 
     fn verify_reveal(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         pk: &Self::PlayerPublicKey,
         reveal_token: &Self::RevealToken,
         masked_card: &Self::MaskedCard,
@@ -350,7 +803,7 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         // Map to Chaum-Pedersen parameters
         let cp_statement = chaum_pedersen_dl_equality::Statement::new(&reveal_token.0, pk);
 
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&REVEAL_RNG_SEED);
+        let mut fs_rng = ctx.fiat_shamir_rng(REVEAL_RNG_SEED);
         chaum_pedersen_dl_equality::DLEquality::verify(
             &cp_parameters,
             &cp_statement,
@@ -359,21 +812,121 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         )
     }
 
+    fn verify_reveal_batch(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        reveals: &[(
+            &Self::PlayerPublicKey,
+            &Self::RevealToken,
+            &Self::MaskedCard,
+            &Self::ZKProofReveal,
+        )],
+    ) -> Result<(), CryptoError> {
+        let instances = reveals
+            .iter()
+            .map(|(pk, reveal_token, masked_card, proof)| {
+                (
+                    chaum_pedersen_dl_equality::Parameters::new(
+                        &masked_card.0,
+                        &pp.enc_parameters.generator,
+                    ),
+                    chaum_pedersen_dl_equality::Statement::new(&reveal_token.0, *pk),
+                    *proof,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let fs_rng = ctx.fiat_shamir_rng(REVEAL_RNG_SEED);
+        chaum_pedersen_dl_equality::DLEquality::batch_verify(&instances, &fs_rng)
+    }
+
+    fn compute_reveal_tokens<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        sk: &Self::PlayerSecretKey,
+        pk: &Self::PlayerPublicKey,
+        masked_cards: &[Self::MaskedCard],
+    ) -> Result<(Vec<Self::RevealToken>, Self::ZKProofReveal), CardProtocolError> {
+        let bases = masked_cards
+            .iter()
+            .map(|masked_card| masked_card.0)
+            .collect::<Vec<_>>();
+        let points = C::normalize_batch(
+            &bases
+                .iter()
+                .map(|base| mul_secret::<C>(base, sk.expose()))
+                .collect::<Vec<_>>(),
+        );
+
+        // Map to an aggregate Chaum-Pedersen statement
+        let cp_statement = chaum_pedersen_dl_equality::aggregate::AggregateStatement::new(
+            &bases,
+            &points,
+            &pp.enc_parameters.generator,
+            pk,
+        );
+
+        let mut fs_rng = ctx.fiat_shamir_rng(REVEAL_RNG_SEED);
+        let proof = chaum_pedersen_dl_equality::DLEquality::prove_aggregate(
+            rng,
+            &cp_statement,
+            sk.expose(),
+            &mut fs_rng,
+        )?;
+
+        Ok((points.into_iter().map(el_gamal::Plaintext).collect(), proof))
+    }
+
+    fn verify_reveal_tokens(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        pk: &Self::PlayerPublicKey,
+        reveal_tokens: &[Self::RevealToken],
+        masked_cards: &[Self::MaskedCard],
+        proof: &Self::ZKProofReveal,
+    ) -> Result<(), CryptoError> {
+        let bases = masked_cards
+            .iter()
+            .map(|masked_card| masked_card.0)
+            .collect::<Vec<_>>();
+        let points = reveal_tokens
+            .iter()
+            .map(|reveal_token| reveal_token.0)
+            .collect::<Vec<_>>();
+
+        // Map to an aggregate Chaum-Pedersen statement
+        let cp_statement = chaum_pedersen_dl_equality::aggregate::AggregateStatement::new(
+            &bases,
+            &points,
+            &pp.enc_parameters.generator,
+            pk,
+        );
+
+        let mut fs_rng = ctx.fiat_shamir_rng(REVEAL_RNG_SEED);
+        chaum_pedersen_dl_equality::DLEquality::verify_aggregate(&cp_statement, proof, &mut fs_rng)
+    }
+
     fn unmask(
         pp: &Self::Parameters,
-        decryption_key: &Vec<(
+        ctx: &Self::SessionContext,
+        decryption_key: &[(
             Self::RevealToken,
             Self::ZKProofReveal,
             Self::PlayerPublicKey,
-        )>,
+        )],
         masked_card: &Self::MaskedCard,
     ) -> Result<Self::Card, CardProtocolError> {
         let zero = Self::RevealToken::zero();
 
         let mut aggregate_token = zero;
 
-        for (token, proof, pk) in decryption_key {
-            Self::verify_reveal(pp, pk, token, masked_card, proof)?;
+        for (position, (token, proof, pk)) in decryption_key.iter().enumerate() {
+            Self::verify_reveal(pp, ctx, pk, token, masked_card, proof).blame(
+                position,
+                pk,
+                Operation::Reveal,
+            )?;
 
             aggregate_token = aggregate_token + *token;
         }
@@ -383,70 +936,330 @@ impl<'a, C: CurveGroup> BarnettSmartProtocol for DLCards<'a, C> {
         Ok(decrypted)
     }
 
-    fn shuffle_and_remask<R: Rng>(
+    fn unmask_many(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        decryption_keys: &[Vec<(
+            Self::RevealToken,
+            Self::ZKProofReveal,
+            Self::PlayerPublicKey,
+        )>],
+        masked_cards: &[Self::MaskedCard],
+    ) -> Result<Vec<Self::Card>, CardProtocolError> {
+        if decryption_keys.len() != masked_cards.len() {
+            return Err(CardProtocolError::MismatchedDecryptionKeys(
+                decryption_keys.len(),
+                masked_cards.len(),
+            ));
+        }
+
+        let reveals = decryption_keys
+            .iter()
+            .zip(masked_cards.iter())
+            .flat_map(|(decryption_key, masked_card)| {
+                decryption_key
+                    .iter()
+                    .map(move |(token, proof, pk)| (pk, token, masked_card, proof))
+            })
+            .collect::<Vec<_>>();
+
+        if Self::verify_reveal_batch(pp, ctx, &reveals).is_err() {
+            // Find out which player sent an invalid proof
+            return decryption_keys
+                .iter()
+                .zip(masked_cards.iter())
+                .map(|(decryption_key, masked_card)| {
+                    Self::unmask(pp, ctx, decryption_key, masked_card)
+                })
+                .collect();
+        }
+
+        decryption_keys
+            .iter()
+            .zip(masked_cards.iter())
+            .map(|(decryption_key, masked_card)| {
+                let aggregate_token = decryption_key
+                    .iter()
+                    .fold(Self::RevealToken::zero(), |aggregate, (token, _, _)| {
+                        aggregate + *token
+                    });
+
+                aggregate_token.reveal(masked_card)
+            })
+            .collect()
+    }
+
+    fn unmask_with_roster(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        roster: &Self::Roster,
+        decryption_key: &[(
+            Self::RevealToken,
+            Self::ZKProofReveal,
+            Self::PlayerPublicKey,
+        )],
+        masked_card: &Self::MaskedCard,
+        card_set: &[Self::Card],
+    ) -> Result<Self::Card, CardProtocolError> {
+        let mut has_token = vec![false; roster.len()];
+        let mut indices = Vec::with_capacity(decryption_key.len());
+        for (position, (_, _, pk)) in decryption_key.iter().enumerate() {
+            let index = roster
+                .index_of(pk)
+                .ok_or(CardProtocolError::UnregisteredRevealToken(position))?;
+
+            if has_token[index] {
+                return Err(CardProtocolError::DuplicateRevealToken(index));
+            }
+            has_token[index] = true;
+            indices.push(index);
+        }
+
+        if let Some(index) = has_token.iter().position(|&supplied| !supplied) {
+            return Err(CardProtocolError::MissingRevealToken(index));
+        }
+
+        // Blame players by their roster index rather than by their position in the token list
+        let card = Self::unmask(pp, ctx, decryption_key, masked_card).map_err(|err| match err {
+            CardProtocolError::InvalidPlayerProof {
+                player,
+                public_key,
+                operation,
+                source,
+            } => CardProtocolError::InvalidPlayerProof {
+                player: indices[player],
+                public_key,
+                operation,
+                source,
+            },
+            err => err,
+        })?;
+
+        if !card_set.contains(&card) {
+            return Err(CardProtocolError::UnknownCard);
+        }
+
+        Ok(card)
+    }
+
+    fn unmask_threshold(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        key: &Self::ThresholdKey,
+        decryption_key: &[(
+            Self::RevealToken,
+            Self::ZKProofReveal,
+            Self::PlayerPublicKey,
+        )],
+        masked_card: &Self::MaskedCard,
+    ) -> Result<Self::Card, CardProtocolError> {
+        dkg::unmask_threshold::<C, E>(pp, ctx, key, decryption_key, masked_card)
+    }
+
+    fn prepare_key(
+        pp: &Self::Parameters,
+        shared_key: &Self::AggregatePublicKey,
+    ) -> Self::PreparedKey {
+        PreparedKey {
+            enc_parameters: el_gamal::PreparedParameters::new(&pp.enc_parameters),
+            shared_key: el_gamal::PreparedPublicKey::new(shared_key),
+        }
+    }
+
+    fn shuffle_and_remask<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
-        deck: &Vec<Self::MaskedCard>,
-        masking_factors: &Vec<Self::Scalar>,
+        deck: &[Self::MaskedCard],
+        masking_factors: &Secret<Vec<Self::Scalar>>,
         permutation: &Permutation,
     ) -> Result<(Vec<Self::MaskedCard>, Self::ZKProofShuffle), CardProtocolError> {
-        let permuted_deck = permutation.permute_array(deck);
-        let masked_shuffled = permuted_deck
-            .iter()
-            .zip(masking_factors.iter())
-            .map(|(masked_card, masking_factor)| {
-                masked_card.remask(&pp.enc_parameters, shared_key, masking_factor)
-            })
+        // Remasking a deck already takes more multiplications than preparing the key
+        let prepared_key = Self::prepare_key(pp, shared_key);
+        Self::shuffle_and_remask_prepared(
+            rng,
+            pp,
+            ctx,
+            &prepared_key,
+            deck,
+            masking_factors,
+            permutation,
+        )
+    }
+
+    fn shuffle_and_remask_prepared<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        prepared_key: &Self::PreparedKey,
+        deck: &[Self::MaskedCard],
+        masking_factors: &Secret<Vec<Self::Scalar>>,
+        permutation: &Permutation,
+    ) -> Result<(Vec<Self::MaskedCard>, Self::ZKProofShuffle), CardProtocolError> {
+        let precomputation =
+            Self::precompute_shuffle(rng, pp, prepared_key, masking_factors, permutation)?;
+        Self::shuffle_and_remask_precomputed(rng, pp, ctx, prepared_key, deck, precomputation)
+    }
+
+    fn precompute_shuffle<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        prepared_key: &Self::PreparedKey,
+        masking_factors: &Secret<Vec<Self::Scalar>>,
+        permutation: &Permutation,
+    ) -> Result<Self::ShufflePrecomputation, CardProtocolError> {
+        let enc_parameters = &prepared_key.enc_parameters;
+        let shared_key = &prepared_key.shared_key;
+
+        // Encryptions of zero that remask the permuted deck
+        let zero = el_gamal::Plaintext::zero();
+        let masking_ciphers = cfg_iter!(masking_factors.expose())
+            .map(|masking_factor| zero.mask_prepared(enc_parameters, shared_key, masking_factor))
             .collect::<Result<Vec<_>, CardProtocolError>>()?;
 
-        let shuffle_parameters = shuffle::Parameters::new(
-            &pp.enc_parameters,
-            shared_key,
-            &pp.commit_parameters,
-            &pp.generator,
+        shuffle_precomputation(
+            rng,
+            pp,
+            prepared_key,
+            permutation,
+            masking_factors.clone(),
+            Secret::new(masking_ciphers),
+        )
+    }
+
+    fn shuffle_and_remask_precomputed<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        prepared_key: &Self::PreparedKey,
+        deck: &[Self::MaskedCard],
+        precomputation: Self::ShufflePrecomputation,
+    ) -> Result<(Vec<Self::MaskedCard>, Self::ZKProofShuffle), CardProtocolError> {
+        if deck.len() != pp.m * pp.n {
+            return Err(CardProtocolError::MismatchedDeckLength(
+                deck.len(),
+                pp.m * pp.n,
+            ));
+        }
+        if precomputation.permutation.size != deck.len() {
+            return Err(CardProtocolError::MismatchedDeckLength(
+                precomputation.permutation.size,
+                deck.len(),
+            ));
+        }
+
+        let ShufflePrecomputation {
+            permutation,
+            masking_factors,
+            masking_ciphers,
+            proof,
+        } = precomputation;
+        let masking_factors = masking_factors.expose();
+
+        let permuted_deck = permutation.permute_secret_array(deck);
+        let masked_shuffled = ElGamal::<C>::normalize_batch(
+            &permuted_deck
+                .iter()
+                .zip(masking_ciphers.expose().iter())
+                .map(|(&masked_card, &masking_cipher)| {
+                    el_gamal::ProjectiveCiphertext::from(masked_card) + masking_cipher.into()
+                })
+                .collect::<Vec<_>>(),
         );
 
+        let shuffle_parameters = shuffle_parameters(pp, prepared_key);
+
         let shuffle_statement = shuffle::Statement::new(deck, &masked_shuffled, pp.m, pp.n);
 
-        let witness = shuffle::Witness::new(permutation, masking_factors);
+        let witness = shuffle::Witness::new(&permutation, masking_factors);
 
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&SHUFFLE_RNG_SEED);
-        let proof = shuffle::ShuffleArgument::prove(
-            rng,
-            &shuffle_parameters,
-            &shuffle_statement,
-            &witness,
-            &mut fs_rng,
-        )?;
+        let mut fs_rng = ctx.fiat_shamir_rng(SHUFFLE_RNG_SEED);
+        let proof = shuffle::prover::Prover::new(&shuffle_parameters, &shuffle_statement, &witness)
+            .with_precomputation(proof)
+            .prove(rng, &mut fs_rng)?;
 
         Ok((masked_shuffled, proof))
     }
 
+    fn shuffle_and_remask_from_pool<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        prepared_key: &Self::PreparedKey,
+        deck: &[Self::MaskedCard],
+        permutation: &Permutation,
+        pool: &mut EncryptionOfZeroPool<Self::Scalar, Self::Enc>,
+    ) -> Result<(Vec<Self::MaskedCard>, Self::ZKProofShuffle), CardProtocolError> {
+        if !pool.is_for(&pp.enc_parameters, prepared_key.shared_key.public_key()) {
+            return Err(CardProtocolError::MismatchedEncryptionPool);
+        }
+
+        if deck.len() != pp.m * pp.n {
+            return Err(CardProtocolError::MismatchedDeckLength(
+                deck.len(),
+                pp.m * pp.n,
+            ));
+        }
+
+        let (masking_factors, masking_ciphers) = pool.draw(rng, pp.m * pp.n)?;
+        let precomputation = shuffle_precomputation(
+            rng,
+            pp,
+            prepared_key,
+            permutation,
+            masking_factors,
+            masking_ciphers,
+        )?;
+        Self::shuffle_and_remask_precomputed(rng, pp, ctx, prepared_key, deck, precomputation)
+    }
+
     fn verify_shuffle(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         original_deck: &[Self::MaskedCard],
         shuffled_deck: &[Self::MaskedCard],
         proof: &Self::ZKProofShuffle,
     ) -> Result<(), CryptoError> {
-        let shuffle_parameters = shuffle::Parameters::new(
-            &pp.enc_parameters,
-            shared_key,
-            &pp.commit_parameters,
-            &pp.generator,
-        );
+        let verifier = Self::prepare_shuffle_verifier(pp, shared_key)?;
+        Self::verify_shuffle_prepared(&verifier, ctx, original_deck, shuffled_deck, proof)
+    }
 
-        let original_deck = original_deck.to_vec();
-        let shuffled_deck = shuffled_deck.to_vec();
-        let shuffle_statement = shuffle::Statement::new(&original_deck, &shuffled_deck, pp.m, pp.n);
+    fn verify_shuffle_chain(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        shared_key: &Self::AggregatePublicKey,
+        decks: &[Vec<Self::MaskedCard>],
+        proofs: &[(usize, Self::ZKProofShuffle, Self::PlayerPublicKey)],
+    ) -> Result<(), CardProtocolError> {
+        let verifier = Self::prepare_shuffle_verifier(pp, shared_key)?;
+        Self::verify_shuffle_chain_prepared(&verifier, ctx, decks, proofs)
+    }
 
-        let mut fs_rng = FiatShamirRng::<Blake2s256>::from_seed(&SHUFFLE_RNG_SEED);
-        shuffle::ShuffleArgument::verify(
-            &shuffle_parameters,
-            &shuffle_statement,
-            proof,
-            &mut fs_rng,
-        )
+    fn prepare_shuffle_verifier<'v>(
+        pp: &'v Self::Parameters,
+        shared_key: &'v Self::AggregatePublicKey,
+    ) -> Result<Self::ShuffleVerifier<'v>, CryptoError> {
+        ShuffleVerifier::new(pp, shared_key)
+    }
+
+    fn verify_shuffle_prepared(
+        verifier: &Self::ShuffleVerifier<'_>,
+        ctx: &Self::SessionContext,
+        original_deck: &[Self::MaskedCard],
+        shuffled_deck: &[Self::MaskedCard],
+        proof: &Self::ZKProofShuffle,
+    ) -> Result<(), CryptoError> {
+        verifier.verify(ctx, original_deck, shuffled_deck, proof)
+    }
+
+    fn verify_shuffle_chain_prepared(
+        verifier: &Self::ShuffleVerifier<'_>,
+        ctx: &Self::SessionContext,
+        decks: &[Vec<Self::MaskedCard>],
+        proofs: &[(usize, Self::ZKProofShuffle, Self::PlayerPublicKey)],
+    ) -> Result<(), CardProtocolError> {
+        verifier.verify_chain(ctx, decks, proofs)
     }
 }
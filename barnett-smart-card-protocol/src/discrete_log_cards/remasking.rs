@@ -1,6 +1,7 @@
+use super::{DLCards, Parameters, ProofEncoding, PublicKey, SessionContext};
 use crate::discrete_log_cards::MaskedCard;
-use crate::error::CardProtocolError;
-use crate::{Mask, Remask};
+use crate::error::{Blame, CardProtocolError, Operation};
+use crate::{BarnettSmartProtocol, Mask, Remask};
 use ark_ec::CurveGroup;
 
 use ark_ff::Zero;
@@ -19,16 +20,55 @@ impl<C: CurveGroup> Remask<C::ScalarField, ElGamal<C>> for MaskedCard<C> {
 
         Ok(remasked_cipher)
     }
+
+    fn remask_prepared(
+        &self,
+        pp: &el_gamal::PreparedParameters<C>,
+        shared_key: &el_gamal::PreparedPublicKey<C>,
+        alpha: &C::ScalarField,
+    ) -> Result<el_gamal::Ciphertext<C>, CardProtocolError> {
+        let zero = el_gamal::Plaintext::zero();
+        let masking_point = zero.mask_prepared(pp, shared_key, alpha)?;
+        let remasked_cipher = *self + masking_point;
+
+        Ok(remasked_cipher)
+    }
+
+    fn remask_with(&self, encryption_of_zero: &el_gamal::Ciphertext<C>) -> el_gamal::Ciphertext<C> {
+        *self + *encryption_of_zero
+    }
+}
+
+impl<'a, C: CurveGroup, E: ProofEncoding<C>> DLCards<'a, C, E> {
+    /// Same as [`BarnettSmartProtocol::verify_remask`], blaming `player` if the proof is invalid
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_remask_from(
+        pp: &Parameters<C>,
+        ctx: &SessionContext<C>,
+        shared_key: &PublicKey<C>,
+        player: usize,
+        pk: &PublicKey<C>,
+        original_masked: &MaskedCard<C>,
+        remasked: &MaskedCard<C>,
+        proof: &E::DLEqualityProof,
+    ) -> Result<(), CardProtocolError> {
+        Self::verify_remask(pp, ctx, shared_key, original_masked, remasked, proof).blame(
+            player,
+            pk,
+            Operation::Remasking,
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::discrete_log_cards;
+    use crate::error::{CardProtocolError, Operation};
     use crate::BarnettSmartProtocol;
     use ark_ec::{AffineRepr, CurveGroup};
 
     use ark_ff::UniformRand;
-    use ark_std::rand::Rng;
+    use ark_std::rand::{CryptoRng, RngCore};
     use proof_essentials::error::CryptoError;
     use proof_essentials::zkp::proofs::chaum_pedersen_dl_equality;
     use rand::thread_rng;
@@ -40,6 +80,7 @@ mod test {
     // Instantiate concrete type for our card protocol
     type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
     type CardParameters = discrete_log_cards::Parameters<Curve>;
+    type SessionContext = discrete_log_cards::SessionContext<Curve>;
     type PublicKey = discrete_log_cards::PublicKey<Curve>;
     type SecretKey = discrete_log_cards::PlayerSecretKey<Curve>;
 
@@ -47,7 +88,7 @@ mod test {
 
     type RemaskingProof = chaum_pedersen_dl_equality::proof::Proof<Curve>;
 
-    fn setup_players<R: Rng>(
+    fn setup_players<R: CryptoRng + RngCore>(
         rng: &mut R,
         parameters: &CardParameters,
         num_of_players: usize,
@@ -56,8 +97,8 @@ mod test {
         let mut expected_shared_key = PublicKey::zero().into_group();
 
         for i in 0..parameters.n {
-            players.push(CardProtocol::player_keygen(rng, &parameters).unwrap());
-            expected_shared_key = expected_shared_key + players[i].0
+            players.push(CardProtocol::player_keygen(rng, parameters).unwrap());
+            expected_shared_key += players[i].0
         }
 
         (players, expected_shared_key.into_affine())
@@ -72,8 +113,9 @@ mod test {
         let num_of_players = 10;
 
         let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
 
-        let (_, aggregate_key) = setup_players(rng, &parameters, num_of_players);
+        let (players, aggregate_key) = setup_players(rng, &parameters, num_of_players);
 
         let some_masked_card = MaskedCard::rand(rng);
         let some_random = Scalar::rand(rng);
@@ -81,6 +123,7 @@ mod test {
         let (remasked, remasking_proof): (MaskedCard, RemaskingProof) = CardProtocol::remask(
             rng,
             &parameters,
+            &ctx,
             &aggregate_key,
             &some_masked_card,
             &some_random,
@@ -91,6 +134,7 @@ mod test {
             Ok(()),
             CardProtocol::verify_remask(
                 &parameters,
+                &ctx,
                 &aggregate_key,
                 &some_masked_card,
                 &remasked,
@@ -103,6 +147,7 @@ mod test {
         assert_eq!(
             CardProtocol::verify_remask(
                 &parameters,
+                &ctx,
                 &aggregate_key,
                 &some_masked_card,
                 &wrong_output,
@@ -111,6 +156,25 @@ mod test {
             Err(CryptoError::ProofVerificationError(String::from(
                 "Chaum-Pedersen"
             )))
+        );
+
+        assert_eq!(
+            CardProtocol::verify_remask_from(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                3,
+                &players[3].0,
+                &some_masked_card,
+                &wrong_output,
+                &remasking_proof
+            ),
+            Err(CardProtocolError::invalid_player_proof(
+                3,
+                &players[3].0,
+                Operation::Remasking,
+                CryptoError::ProofVerificationError(String::from("Chaum-Pedersen"))
+            ))
         )
     }
 }
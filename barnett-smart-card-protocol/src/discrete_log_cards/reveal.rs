@@ -36,6 +36,7 @@ mod test {
 
     // Instantiate concrete type for our card protocol
     type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
+    type SessionContext = discrete_log_cards::SessionContext<Curve>;
 
     type MaskedCard = discrete_log_cards::MaskedCard<Curve>;
     type RevealToken = discrete_log_cards::RevealToken<Curve>;
@@ -49,13 +50,14 @@ mod test {
         let n = 13;
 
         let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
 
         let (pk, sk) = CardProtocol::player_keygen(rng, &parameters).unwrap();
 
         let some_masked_card = MaskedCard::rand(rng);
 
         let (reveal_token, mut reveal_proof): (RevealToken, RevealProof) =
-            CardProtocol::compute_reveal_token(rng, &parameters, &sk, &pk, &some_masked_card)
+            CardProtocol::compute_reveal_token(rng, &parameters, &ctx, &sk, &pk, &some_masked_card)
                 .unwrap();
 
         let mut data = Vec::with_capacity(reveal_proof.compressed_size());
@@ -66,6 +68,7 @@ mod test {
             Ok(()),
             CardProtocol::verify_reveal(
                 &parameters,
+                &ctx,
                 &pk,
                 &reveal_token,
                 &some_masked_card,
@@ -78,6 +81,7 @@ mod test {
         assert_eq!(
             CardProtocol::verify_reveal(
                 &parameters,
+                &ctx,
                 &pk,
                 &wrong_reveal,
                 &some_masked_card,
@@ -1,18 +1,21 @@
 #[cfg(test)]
 mod test {
     use crate::discrete_log_cards;
-    use crate::error::CardProtocolError;
-    use crate::BarnettSmartProtocol;
+    use crate::error::{CardProtocolError, Operation};
+    use crate::{BarnettSmartProtocol, Mask, Reveal};
 
     use ark_ec::{AffineRepr, CurveGroup};
     use ark_ff::UniformRand;
     use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
     use ark_std::iter::Iterator;
-    use ark_std::rand::Rng;
-    use proof_essentials::error::CryptoError;
+    use ark_std::rand::{CryptoRng, RngCore};
+    use ark_std::Zero;
+    use proof_essentials::error::{Argument, Check, CryptoError};
     use proof_essentials::utils::permutation::Permutation;
     use proof_essentials::utils::rand::sample_vector;
-    use rand::thread_rng;
+    use proof_essentials::utils::secret::Secret;
+    use rand::rngs::StdRng;
+    use rand::{thread_rng, SeedableRng};
 
     // Choose elliptic curve setting
     type Curve = ark_bn254::G1Projective;
@@ -20,7 +23,9 @@ mod test {
 
     // Instantiate concrete type for our card protocol
     type CardProtocol<'a> = discrete_log_cards::DLCards<'a, Curve>;
+    type CompactCardProtocol<'a> = discrete_log_cards::CompactDLCards<'a, Curve>;
     type CardParameters = discrete_log_cards::Parameters<Curve>;
+    type SessionContext = discrete_log_cards::SessionContext<Curve>;
     type PublicKey = discrete_log_cards::PublicKey<Curve>;
     type SecretKey = discrete_log_cards::PlayerSecretKey<Curve>;
 
@@ -29,7 +34,7 @@ mod test {
     type RevealToken = discrete_log_cards::RevealToken<Curve>;
 
     /// Setup `n` players. We use a Scalar to represent player public information
-    fn setup_players<R: Rng>(
+    fn setup_players<R: CryptoRng + RngCore>(
         rng: &mut R,
         parameters: &CardParameters,
         num_of_players: usize,
@@ -39,10 +44,10 @@ mod test {
         let mut expected_shared_key = PublicKey::zero().into_group();
 
         for i in 0..num_of_players {
-            let (pk, sk) = CardProtocol::player_keygen(rng, &parameters).unwrap();
+            let (pk, sk) = CardProtocol::player_keygen(rng, parameters).unwrap();
             let player_info = Scalar::rand(rng);
             players.push((pk, sk, player_info));
-            expected_shared_key = expected_shared_key + players[i].0
+            expected_shared_key += players[i].0
         }
 
         (players, expected_shared_key.into_affine())
@@ -55,12 +60,14 @@ mod test {
         let n = 13;
 
         let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
 
         let (pk, sk) = CardProtocol::player_keygen(rng, &parameters).unwrap();
         let player_name = b"Alice";
 
         let mut p1_keyproof =
-            CardProtocol::prove_key_ownership(rng, &parameters, &pk, &sk, player_name).unwrap();
+            CardProtocol::prove_key_ownership(rng, &parameters, &ctx, &pk, &sk, player_name)
+                .unwrap();
 
         let mut data = Vec::with_capacity(p1_keyproof.compressed_size());
         p1_keyproof.serialize_compressed(&mut data).unwrap();
@@ -68,16 +75,16 @@ mod test {
 
         assert_eq!(
             Ok(()),
-            CardProtocol::verify_key_ownership(&parameters, &pk, player_name, &p1_keyproof)
+            CardProtocol::verify_key_ownership(&parameters, &ctx, &pk, player_name, &p1_keyproof)
         );
 
-        let other_key = Scalar::rand(rng);
+        let other_key = Secret::new(Scalar::rand(rng));
         let wrong_proof =
-            CardProtocol::prove_key_ownership(rng, &parameters, &pk, &other_key, player_name)
+            CardProtocol::prove_key_ownership(rng, &parameters, &ctx, &pk, &other_key, player_name)
                 .unwrap();
 
         assert_eq!(
-            CardProtocol::verify_key_ownership(&parameters, &pk, player_name, &wrong_proof),
+            CardProtocol::verify_key_ownership(&parameters, &ctx, &pk, player_name, &wrong_proof),
             Err(CryptoError::ProofVerificationError(String::from(
                 "Schnorr Identification"
             )))
@@ -93,21 +100,29 @@ mod test {
         let num_of_players = 10;
 
         let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
 
         let (players, expected_shared_key) = setup_players(rng, &parameters, num_of_players);
 
         let proofs = players
             .iter()
             .map(|player| {
-                CardProtocol::prove_key_ownership(rng, &parameters, &player.0, &player.1, &player.2)
-                    .unwrap()
+                CardProtocol::prove_key_ownership(
+                    rng,
+                    &parameters,
+                    &ctx,
+                    &player.0,
+                    &player.1,
+                    &player.2,
+                )
+                .unwrap()
             })
             .collect::<Vec<_>>();
 
         let mut key_proof_info = players
             .iter()
             .zip(proofs.iter())
-            .map(|(player, &proof)| (player.0, proof.clone(), player.2))
+            .map(|(player, &proof)| (player.0, proof, player.2))
             .collect::<Vec<(PublicKey, _, _)>>();
 
         let mut data = Vec::with_capacity(key_proof_info.compressed_size());
@@ -121,29 +136,94 @@ mod test {
                 let mut data = Vec::with_capacity(proof.compressed_size());
                 proof.serialize_compressed(&mut data).unwrap();
                 proof = CanonicalDeserialize::deserialize_compressed(data.as_slice()).unwrap();
-                (vkey_proof_info.0, proof.clone(), vkey_proof_info.2)
+                (vkey_proof_info.0, proof, vkey_proof_info.2)
             })
             .collect();
 
         let test_aggregate =
-            CardProtocol::compute_aggregate_key(&parameters, &key_proof_info).unwrap();
+            CardProtocol::compute_aggregate_key(&parameters, &ctx, &key_proof_info).unwrap();
 
         assert_eq!(test_aggregate, expected_shared_key);
 
         let mut bad_key_proof_pairs = key_proof_info;
-        bad_key_proof_pairs[0].0 = PublicKey::zero();
+        bad_key_proof_pairs[0].0 = players[1].0;
+        bad_key_proof_pairs[1].0 = players[0].0;
 
         let test_fail_aggregate =
-            CardProtocol::compute_aggregate_key(&parameters, &bad_key_proof_pairs);
+            CardProtocol::compute_aggregate_key(&parameters, &ctx, &bad_key_proof_pairs);
 
         assert_eq!(
             test_fail_aggregate,
-            Err(CardProtocolError::ProofVerificationError(
+            Err(CardProtocolError::invalid_player_proof(
+                0,
+                &players[1].0,
+                Operation::KeyOwnership,
                 CryptoError::ProofVerificationError(String::from("Schnorr Identification"))
             ))
         )
     }
 
+    #[test]
+    fn register_players() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let num_of_players = 4;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let (players, expected_shared_key) = setup_players(rng, &parameters, num_of_players);
+
+        let key_proof_info = players
+            .iter()
+            .map(|player| {
+                let proof = CardProtocol::prove_key_ownership(
+                    rng,
+                    &parameters,
+                    &ctx,
+                    &player.0,
+                    &player.1,
+                    &player.2,
+                )
+                .unwrap();
+                (player.0, proof, player.2)
+            })
+            .collect::<Vec<_>>();
+
+        let roster = CardProtocol::register_players(&parameters, &ctx, &key_proof_info).unwrap();
+
+        assert_eq!(roster.len(), num_of_players);
+        assert_eq!(*roster.aggregate_key(), expected_shared_key);
+        for (i, player) in players.iter().enumerate() {
+            assert_eq!(roster.players()[i].index, i);
+            assert_eq!(roster.index_of(&player.0), Some(i));
+        }
+
+        let mut identity_key = key_proof_info.clone();
+        identity_key[2].0 = PublicKey::zero();
+        assert_eq!(
+            CardProtocol::register_players(&parameters, &ctx, &identity_key),
+            Err(CardProtocolError::IdentityPublicKey(2))
+        );
+
+        let mut duplicate_key = key_proof_info.clone();
+        duplicate_key[3] = duplicate_key[1];
+        duplicate_key[3].2 = Scalar::rand(rng);
+        assert_eq!(
+            CardProtocol::register_players(&parameters, &ctx, &duplicate_key),
+            Err(CardProtocolError::DuplicatePublicKey(1, 3))
+        );
+
+        let mut duplicate_info = key_proof_info;
+        duplicate_info[2].2 = duplicate_info[0].2;
+        assert_eq!(
+            CardProtocol::register_players(&parameters, &ctx, &duplicate_info),
+            Err(CardProtocolError::DuplicatePlayerInfo(0, 2))
+        );
+    }
+
     #[test]
     fn test_unmask() {
         let rng = &mut thread_rng();
@@ -153,13 +233,15 @@ mod test {
         let num_of_players = 10;
 
         let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
 
         let (players, expected_shared_key) = setup_players(rng, &parameters, num_of_players);
 
         let card = Card::rand(rng);
         let alpha = Scalar::rand(rng);
         let (masked, _) =
-            CardProtocol::mask(rng, &parameters, &expected_shared_key, &card, &alpha).unwrap();
+            CardProtocol::mask(rng, &parameters, &ctx, &expected_shared_key, &card, &alpha)
+                .unwrap();
 
         let decryption_key = players
             .iter()
@@ -167,6 +249,7 @@ mod test {
                 let (token, proof) = CardProtocol::compute_reveal_token(
                     rng,
                     &parameters,
+                    &ctx,
                     &player.1,
                     &player.0,
                     &masked,
@@ -177,23 +260,260 @@ mod test {
             })
             .collect::<Vec<_>>();
 
-        let unmasked = CardProtocol::unmask(&parameters, &decryption_key, &masked).unwrap();
+        let unmasked = CardProtocol::unmask(&parameters, &ctx, &decryption_key, &masked).unwrap();
 
         assert_eq!(card, unmasked);
 
         let mut bad_decryption_key = decryption_key;
-        bad_decryption_key[0].0 = RevealToken::rand(rng);
+        bad_decryption_key[2].0 = RevealToken::rand(rng);
 
-        let failed_decryption = CardProtocol::unmask(&parameters, &bad_decryption_key, &masked);
+        let failed_decryption =
+            CardProtocol::unmask(&parameters, &ctx, &bad_decryption_key, &masked);
 
         assert_eq!(
             failed_decryption,
-            Err(CardProtocolError::ProofVerificationError(
+            Err(CardProtocolError::invalid_player_proof(
+                2,
+                &bad_decryption_key[2].2,
+                Operation::Reveal,
                 CryptoError::ProofVerificationError(String::from("Chaum-Pedersen"))
             ))
         )
     }
 
+    #[test]
+    fn test_unmask_many() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let num_of_players = 9;
+        let num_of_cards = 5;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let (players, expected_shared_key) = setup_players(rng, &parameters, num_of_players);
+
+        let cards: Vec<Card> = sample_vector(rng, num_of_cards);
+        let masked_cards = cards
+            .iter()
+            .map(|card| {
+                let alpha = Scalar::rand(rng);
+                CardProtocol::mask(rng, &parameters, &ctx, &expected_shared_key, card, &alpha)
+                    .unwrap()
+                    .0
+            })
+            .collect::<Vec<MaskedCard>>();
+
+        let decryption_keys = masked_cards
+            .iter()
+            .map(|masked| {
+                players
+                    .iter()
+                    .map(|player| {
+                        let (token, proof) = CardProtocol::compute_reveal_token(
+                            rng,
+                            &parameters,
+                            &ctx,
+                            &player.1,
+                            &player.0,
+                            masked,
+                        )
+                        .unwrap();
+
+                        (token, proof, player.0)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let unmasked =
+            CardProtocol::unmask_many(&parameters, &ctx, &decryption_keys, &masked_cards).unwrap();
+        assert_eq!(cards, unmasked);
+
+        assert_eq!(
+            CardProtocol::unmask_many(&parameters, &ctx, &decryption_keys[1..], &masked_cards),
+            Err(CardProtocolError::MismatchedDecryptionKeys(
+                num_of_cards - 1,
+                num_of_cards
+            ))
+        );
+
+        // Tokens swapped between two cards
+        let mut bad_decryption_keys = decryption_keys;
+        bad_decryption_keys[3][6].0 = bad_decryption_keys[4][6].0;
+        assert!(CardProtocol::verify_reveal_batch(
+            &parameters,
+            &ctx,
+            &[(
+                &bad_decryption_keys[3][6].2,
+                &bad_decryption_keys[3][6].0,
+                &masked_cards[3],
+                &bad_decryption_keys[3][6].1,
+            )]
+        )
+        .is_err());
+
+        let err = CardProtocol::unmask_many(&parameters, &ctx, &bad_decryption_keys, &masked_cards)
+            .unwrap_err();
+        assert_eq!(err.culprit(), Some(6));
+    }
+
+    #[test]
+    fn test_unmask_with_roster() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let num_of_players = 4;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let (players, _) = setup_players(rng, &parameters, num_of_players);
+
+        let key_proof_info = players
+            .iter()
+            .map(|player| {
+                let proof = CardProtocol::prove_key_ownership(
+                    rng,
+                    &parameters,
+                    &ctx,
+                    &player.0,
+                    &player.1,
+                    &player.2,
+                )
+                .unwrap();
+                (player.0, proof, player.2)
+            })
+            .collect::<Vec<_>>();
+
+        let roster = CardProtocol::register_players(&parameters, &ctx, &key_proof_info).unwrap();
+        let ctx = ctx.with_roster(&roster.public_keys());
+
+        let card_set: Vec<Card> = sample_vector(rng, 10);
+        let card = card_set[3];
+        let alpha = Scalar::rand(rng);
+        let (masked, _) = CardProtocol::mask(
+            rng,
+            &parameters,
+            &ctx,
+            roster.aggregate_key(),
+            &card,
+            &alpha,
+        )
+        .unwrap();
+
+        let decryption_key = players
+            .iter()
+            .map(|player| {
+                let (token, proof) = CardProtocol::compute_reveal_token(
+                    rng,
+                    &parameters,
+                    &ctx,
+                    &player.1,
+                    &player.0,
+                    &masked,
+                )
+                .unwrap();
+
+                (token, proof, player.0)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            CardProtocol::unmask_with_roster(
+                &parameters,
+                &ctx,
+                &roster,
+                &decryption_key,
+                &masked,
+                &card_set
+            ),
+            Ok(card)
+        );
+
+        let missing = decryption_key[1..].to_vec();
+        assert_eq!(
+            CardProtocol::unmask_with_roster(
+                &parameters,
+                &ctx,
+                &roster,
+                &missing,
+                &masked,
+                &card_set
+            ),
+            Err(CardProtocolError::MissingRevealToken(0))
+        );
+
+        let mut duplicate = decryption_key.clone();
+        duplicate[3] = duplicate[2];
+        assert_eq!(
+            CardProtocol::unmask_with_roster(
+                &parameters,
+                &ctx,
+                &roster,
+                &duplicate,
+                &masked,
+                &card_set
+            ),
+            Err(CardProtocolError::DuplicateRevealToken(2))
+        );
+
+        let (outsider_pk, outsider_sk) = CardProtocol::player_keygen(rng, &parameters).unwrap();
+        let (outsider_token, outsider_proof) = CardProtocol::compute_reveal_token(
+            rng,
+            &parameters,
+            &ctx,
+            &outsider_sk,
+            &outsider_pk,
+            &masked,
+        )
+        .unwrap();
+        let mut extra = decryption_key.clone();
+        extra.push((outsider_token, outsider_proof, outsider_pk));
+        assert_eq!(
+            CardProtocol::unmask_with_roster(
+                &parameters,
+                &ctx,
+                &roster,
+                &extra,
+                &masked,
+                &card_set
+            ),
+            Err(CardProtocolError::UnregisteredRevealToken(4))
+        );
+
+        // Cheaters are blamed by roster index, whatever the order of the tokens
+        let mut reordered = decryption_key.clone();
+        reordered.reverse();
+        reordered[2].0 = RevealToken::rand(rng);
+        let err = CardProtocol::unmask_with_roster(
+            &parameters,
+            &ctx,
+            &roster,
+            &reordered,
+            &masked,
+            &card_set,
+        )
+        .unwrap_err();
+        assert_eq!(err.culprit(), Some(1));
+
+        let other_cards: Vec<Card> = sample_vector(rng, 10);
+        assert_eq!(
+            CardProtocol::unmask_with_roster(
+                &parameters,
+                &ctx,
+                &roster,
+                &decryption_key,
+                &masked,
+                &other_cards
+            ),
+            Err(CardProtocolError::UnknownCard)
+        );
+    }
+
     #[test]
     fn test_shuffle() {
         let rng = &mut thread_rng();
@@ -203,17 +523,19 @@ mod test {
         let num_of_players = 10;
 
         let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
 
         let (_, aggregate_key) = setup_players(rng, &parameters, num_of_players);
 
         let deck: Vec<MaskedCard> = sample_vector(rng, m * n);
 
         let permutation = Permutation::new(rng, m * n);
-        let masking_factors: Vec<Scalar> = sample_vector(rng, m * n);
+        let masking_factors = Secret::new(sample_vector(rng, m * n));
 
         let (shuffled_deck, mut shuffle_proof) = CardProtocol::shuffle_and_remask(
             rng,
             &parameters,
+            &ctx,
             &aggregate_key,
             &deck,
             &masking_factors,
@@ -229,6 +551,7 @@ mod test {
             Ok(()),
             CardProtocol::verify_shuffle(
                 &parameters,
+                &ctx,
                 &aggregate_key,
                 &deck,
                 &shuffled_deck,
@@ -241,14 +564,736 @@ mod test {
         assert_eq!(
             CardProtocol::verify_shuffle(
                 &parameters,
+                &ctx,
                 &aggregate_key,
                 &deck,
                 &wrong_output,
                 &shuffle_proof
             ),
+            Err(CryptoError::ArgumentVerificationError {
+                argument: Argument::HadamardProduct,
+                check: Check::Mismatch {
+                    name: "commitments to B",
+                    index: Some(0),
+                },
+            })
+        )
+    }
+
+    #[test]
+    fn test_shuffle_verifier() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let (_, aggregate_key) = setup_players(rng, &parameters, 4);
+        let verifier = CardProtocol::prepare_shuffle_verifier(&parameters, &aggregate_key).unwrap();
+
+        let mut deck: Vec<MaskedCard> = sample_vector(rng, m * n);
+        for _ in 0..3 {
+            let permutation = Permutation::new(rng, m * n);
+            let masking_factors = Secret::new(sample_vector(rng, m * n));
+            let (shuffled_deck, proof) = CardProtocol::shuffle_and_remask(
+                rng,
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &deck,
+                &masking_factors,
+                &permutation,
+            )
+            .unwrap();
+
+            assert_eq!(
+                CardProtocol::verify_shuffle_prepared(
+                    &verifier,
+                    &ctx,
+                    &deck,
+                    &shuffled_deck,
+                    &proof
+                ),
+                Ok(())
+            );
+            assert!(CardProtocol::verify_shuffle_prepared(
+                &verifier,
+                &ctx.next_hand(),
+                &deck,
+                &shuffled_deck,
+                &proof
+            )
+            .is_err());
+
+            deck = shuffled_deck;
+        }
+    }
+
+    #[test]
+    fn test_shuffle_chain() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let num_of_players = 4;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let (players, aggregate_key) = setup_players(rng, &parameters, num_of_players);
+
+        // Players shuffle in reverse roster order, so positions in the chain are not their indices
+        let mut decks: Vec<Vec<MaskedCard>> = vec![sample_vector(rng, m * n)];
+        let mut proofs = Vec::with_capacity(num_of_players);
+        for i in 0..num_of_players {
+            let player = num_of_players - 1 - i;
+            let permutation = Permutation::new(rng, m * n);
+            let masking_factors = Secret::new(sample_vector(rng, m * n));
+            let (shuffled_deck, proof) = CardProtocol::shuffle_and_remask(
+                rng,
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &decks[i],
+                &masking_factors,
+                &permutation,
+            )
+            .unwrap();
+            decks.push(shuffled_deck);
+            proofs.push((player, proof, players[player].0));
+        }
+
+        assert_eq!(
+            CardProtocol::verify_shuffle_chain(&parameters, &ctx, &aggregate_key, &decks, &proofs),
+            Ok(())
+        );
+
+        let verifier = CardProtocol::prepare_shuffle_verifier(&parameters, &aggregate_key).unwrap();
+        assert_eq!(
+            CardProtocol::verify_shuffle_chain_prepared(&verifier, &ctx, &decks, &proofs),
+            Ok(())
+        );
+
+        assert_eq!(
+            CardProtocol::verify_shuffle_chain(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &decks,
+                &proofs[1..]
+            ),
+            Err(CardProtocolError::MismatchedShuffleChain(
+                num_of_players + 1,
+                num_of_players - 1
+            ))
+        );
+
+        // The third player to shuffle, at index 1 of the roster, replaces its output deck
+        decks[3] = sample_vector(rng, m * n);
+        let err =
+            CardProtocol::verify_shuffle_chain(&parameters, &ctx, &aggregate_key, &decks, &proofs)
+                .unwrap_err();
+        assert_eq!(err.culprit(), Some(1));
+
+        let mut key = Vec::new();
+        players[1].0.serialize_compressed(&mut key).unwrap();
+        assert!(matches!(
+            err,
+            CardProtocolError::InvalidPlayerProof { public_key, .. } if public_key == key
+        ));
+    }
+
+    #[test]
+    fn test_prepared_key() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let (_, aggregate_key) = setup_players(rng, &parameters, 4);
+        let prepared_key = CardProtocol::prepare_key(&parameters, &aggregate_key);
+
+        let card = Card::rand(rng);
+        let alpha = Scalar::rand(rng);
+        let (masked, _) =
+            CardProtocol::mask(rng, &parameters, &ctx, &aggregate_key, &card, &alpha).unwrap();
+        assert_eq!(
+            card.mask_prepared(
+                prepared_key.enc_parameters(),
+                prepared_key.shared_key(),
+                &alpha
+            ),
+            Ok(masked)
+        );
+
+        // The same prepared key is used for every shuffle of the hand
+        let mut deck: Vec<MaskedCard> = sample_vector(rng, m * n);
+        for _ in 0..2 {
+            let permutation = Permutation::new(rng, m * n);
+            let masking_factors = Secret::new(sample_vector(rng, m * n));
+            let (shuffled_deck, proof) = CardProtocol::shuffle_and_remask_prepared(
+                rng,
+                &parameters,
+                &ctx,
+                &prepared_key,
+                &deck,
+                &masking_factors,
+                &permutation,
+            )
+            .unwrap();
+
+            assert_eq!(
+                CardProtocol::verify_shuffle(
+                    &parameters,
+                    &ctx,
+                    &aggregate_key,
+                    &deck,
+                    &shuffled_deck,
+                    &proof
+                ),
+                Ok(())
+            );
+            deck = shuffled_deck;
+        }
+    }
+
+    #[test]
+    fn test_precomputed_shuffle() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let (_, aggregate_key) = setup_players(rng, &parameters, 4);
+        let prepared_key = CardProtocol::prepare_key(&parameters, &aggregate_key);
+
+        // While waiting for the deck
+        let permutation = Permutation::new(rng, m * n);
+        let masking_factors = Secret::new(sample_vector(rng, m * n));
+        let prover_rng = &mut StdRng::seed_from_u64(42);
+        let precomputation = CardProtocol::precompute_shuffle(
+            prover_rng,
+            &parameters,
+            &prepared_key,
+            &masking_factors,
+            &permutation,
+        )
+        .unwrap();
+
+        let deck: Vec<MaskedCard> = sample_vector(rng, m * n);
+        let (shuffled_deck, proof) = CardProtocol::shuffle_and_remask_precomputed(
+            prover_rng,
+            &parameters,
+            &ctx,
+            &prepared_key,
+            &deck,
+            precomputation,
+        )
+        .unwrap();
+
+        assert_eq!(
+            CardProtocol::verify_shuffle(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &deck,
+                &shuffled_deck,
+                &proof
+            ),
+            Ok(())
+        );
+
+        let (expected_deck, expected_proof) = CardProtocol::shuffle_and_remask(
+            &mut StdRng::seed_from_u64(42),
+            &parameters,
+            &ctx,
+            &aggregate_key,
+            &deck,
+            &masking_factors,
+            &permutation,
+        )
+        .unwrap();
+        assert_eq!(shuffled_deck, expected_deck);
+
+        let [proof, expected_proof] = [proof, expected_proof].map(|proof| {
+            let mut data = Vec::new();
+            proof.serialize_compressed(&mut data).unwrap();
+            data
+        });
+        assert_eq!(proof, expected_proof);
+
+        // A deck that does not have `m * n` cards is rejected before being permuted
+        let precomputation = CardProtocol::precompute_shuffle(
+            rng,
+            &parameters,
+            &prepared_key,
+            &masking_factors,
+            &permutation,
+        )
+        .unwrap();
+        assert!(matches!(
+            CardProtocol::shuffle_and_remask_precomputed(
+                rng,
+                &parameters,
+                &ctx,
+                &prepared_key,
+                &deck[1..],
+                precomputation,
+            ),
+            Err(CardProtocolError::MismatchedDeckLength(found, expected))
+                if found == m * n - 1 && expected == m * n
+        ));
+    }
+
+    #[test]
+    fn test_encryption_pool() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+
+        let (players, aggregate_key) = setup_players(rng, &parameters, 4);
+        let prepared_key = CardProtocol::prepare_key(&parameters, &aggregate_key);
+
+        // While waiting for our turn
+        let mut pool = CardProtocol::encryption_pool(&parameters, &aggregate_key);
+        pool.fill(rng, m * n + 1).unwrap();
+
+        let card: MaskedCard = MaskedCard::rand(rng);
+        let (remasked, remasking_proof) = CardProtocol::remask_from_pool(
+            rng,
+            &parameters,
+            &ctx,
+            &aggregate_key,
+            &card,
+            &mut pool,
+        )
+        .unwrap();
+        assert_eq!(
+            CardProtocol::verify_remask(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &card,
+                &remasked,
+                &remasking_proof
+            ),
+            Ok(())
+        );
+
+        let deck: Vec<MaskedCard> = sample_vector(rng, m * n);
+        let permutation = Permutation::new(rng, m * n);
+        let (shuffled_deck, shuffle_proof) = CardProtocol::shuffle_and_remask_from_pool(
+            rng,
+            &parameters,
+            &ctx,
+            &prepared_key,
+            &deck,
+            &permutation,
+            &mut pool,
+        )
+        .unwrap();
+        assert!(pool.is_empty());
+        assert_eq!(
+            CardProtocol::verify_shuffle(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &deck,
+                &shuffled_deck,
+                &shuffle_proof
+            ),
+            Ok(())
+        );
+
+        // A deck that does not have `m * n` cards is rejected without drawing from the pool
+        pool.fill(rng, m * n).unwrap();
+        assert!(matches!(
+            CardProtocol::shuffle_and_remask_from_pool(
+                rng,
+                &parameters,
+                &ctx,
+                &prepared_key,
+                &deck[1..],
+                &permutation,
+                &mut pool,
+            ),
+            Err(CardProtocolError::MismatchedDeckLength(found, expected))
+                if found == m * n - 1 && expected == m * n
+        ));
+        assert_eq!(pool.len(), m * n);
+
+        // A pool filled under a single player's key cannot remask the shared deck
+        let mut other_pool = CardProtocol::encryption_pool(&parameters, &players[0].0);
+        assert_eq!(
+            CardProtocol::remask_from_pool(
+                rng,
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &card,
+                &mut other_pool
+            ),
+            Err(CardProtocolError::MismatchedEncryptionPool)
+        );
+    }
+
+    #[test]
+    fn parameters_from_seed() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let parameters = CardParameters::from_seed(b"test table", m, n);
+        assert_eq!(parameters.verify_parameters(b"test table"), Ok(()));
+        assert_eq!(
+            parameters.verify_parameters(b"other table"),
+            Err(CardProtocolError::InvalidParameters)
+        );
+
+        let mut data = Vec::with_capacity(parameters.compressed_size());
+        parameters.serialize_compressed(&mut data).unwrap();
+        let received = CardParameters::deserialize_compressed(data.as_slice()).unwrap();
+        assert_eq!(received.verify_parameters(b"test table"), Ok(()));
+
+        let random_parameters = CardProtocol::setup(rng, m, n).unwrap();
+        assert_eq!(
+            random_parameters.verify_parameters(b"test table"),
+            Err(CardProtocolError::InvalidParameters)
+        );
+
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+        let (_, aggregate_key) = setup_players(rng, &parameters, 3);
+
+        let deck: Vec<MaskedCard> = sample_vector(rng, m * n);
+        let permutation = Permutation::new(rng, m * n);
+        let masking_factors = Secret::new(sample_vector(rng, m * n));
+
+        let (shuffled_deck, shuffle_proof) = CardProtocol::shuffle_and_remask(
+            rng,
+            &parameters,
+            &ctx,
+            &aggregate_key,
+            &deck,
+            &masking_factors,
+            &permutation,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Ok(()),
+            CardProtocol::verify_shuffle(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &deck,
+                &shuffled_deck,
+                &shuffle_proof
+            )
+        );
+    }
+
+    #[test]
+    fn proofs_are_bound_to_session() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let num_of_players = 4;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+
+        let (players, aggregate_key) = setup_players(rng, &parameters, num_of_players);
+        let roster = players.iter().map(|player| player.0).collect::<Vec<_>>();
+
+        let ctx = SessionContext::new(b"table 1", 0, &parameters).with_roster(&roster);
+        let next_hand = ctx.next_hand();
+        let other_table = SessionContext::new(b"table 2", 0, &parameters).with_roster(&roster);
+
+        let card = Card::rand(rng);
+        let alpha = Scalar::rand(rng);
+        let (masked, masking_proof) =
+            CardProtocol::mask(rng, &parameters, &ctx, &aggregate_key, &card, &alpha).unwrap();
+
+        assert_eq!(
+            Ok(()),
+            CardProtocol::verify_mask(
+                &parameters,
+                &ctx,
+                &aggregate_key,
+                &card,
+                &masked,
+                &masking_proof
+            )
+        );
+
+        for other_ctx in [&next_hand, &other_table] {
+            assert_eq!(
+                CardProtocol::verify_mask(
+                    &parameters,
+                    other_ctx,
+                    &aggregate_key,
+                    &card,
+                    &masked,
+                    &masking_proof
+                ),
+                Err(CryptoError::ProofVerificationError(String::from(
+                    "Chaum-Pedersen"
+                )))
+            );
+        }
+
+        let (pk, sk, _) = &players[0];
+        let (token, reveal_proof) =
+            CardProtocol::compute_reveal_token(rng, &parameters, &ctx, sk, pk, &masked).unwrap();
+
+        assert_eq!(
+            Ok(()),
+            CardProtocol::verify_reveal(&parameters, &ctx, pk, &token, &masked, &reveal_proof)
+        );
+        assert_eq!(
+            CardProtocol::verify_reveal(
+                &parameters,
+                &next_hand,
+                pk,
+                &token,
+                &masked,
+                &reveal_proof
+            ),
             Err(CryptoError::ProofVerificationError(String::from(
-                "Hadamard Product (5.1)"
+                "Chaum-Pedersen"
             )))
+        );
+
+        let deck: Vec<MaskedCard> = sample_vector(rng, m * n);
+        let permutation = Permutation::new(rng, m * n);
+        let masking_factors = Secret::new(sample_vector(rng, m * n));
+
+        let (shuffled_deck, shuffle_proof) = CardProtocol::shuffle_and_remask(
+            rng,
+            &parameters,
+            &ctx,
+            &aggregate_key,
+            &deck,
+            &masking_factors,
+            &permutation,
         )
+        .unwrap();
+
+        assert_ne!(
+            Ok(()),
+            CardProtocol::verify_shuffle(
+                &parameters,
+                &other_table,
+                &aggregate_key,
+                &deck,
+                &shuffled_deck,
+                &shuffle_proof
+            )
+        );
+    }
+
+    #[test]
+    fn test_compact_proofs() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+
+        let parameters = CompactCardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+        let (players, _) = setup_players(rng, &parameters, 4);
+
+        let key_proof_info = players
+            .iter()
+            .map(|(pk, sk, player_info)| {
+                let proof = CompactCardProtocol::prove_key_ownership(
+                    rng,
+                    &parameters,
+                    &ctx,
+                    pk,
+                    sk,
+                    player_info,
+                )
+                .unwrap();
+                (*pk, proof, *player_info)
+            })
+            .collect::<Vec<_>>();
+        let roster =
+            CompactCardProtocol::register_players(&parameters, &ctx, &key_proof_info).unwrap();
+
+        let card = Card::rand(rng);
+        let alpha = Scalar::rand(rng);
+        let (masked, masking_proof) = CompactCardProtocol::mask(
+            rng,
+            &parameters,
+            &ctx,
+            roster.aggregate_key(),
+            &card,
+            &alpha,
+        )
+        .unwrap();
+        assert_eq!(
+            Ok(()),
+            CompactCardProtocol::verify_mask(
+                &parameters,
+                &ctx,
+                roster.aggregate_key(),
+                &card,
+                &masked,
+                &masking_proof
+            )
+        );
+
+        // Compact proofs are smaller than the default ones
+        let (_, full_proof) = CardProtocol::mask(
+            rng,
+            &parameters,
+            &ctx,
+            roster.aggregate_key(),
+            &card,
+            &alpha,
+        )
+        .unwrap();
+        assert!(masking_proof.compressed_size() < full_proof.compressed_size());
+
+        let decryption_key = players
+            .iter()
+            .map(|(pk, sk, _)| {
+                let (token, proof) = CompactCardProtocol::compute_reveal_token(
+                    rng,
+                    &parameters,
+                    &ctx,
+                    sk,
+                    pk,
+                    &masked,
+                )
+                .unwrap();
+                (token, proof, *pk)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            CompactCardProtocol::unmask(&parameters, &ctx, &decryption_key, &masked),
+            Ok(card)
+        );
+
+        let mut bad_decryption_key = decryption_key;
+        bad_decryption_key[1].0 = RevealToken::rand(rng);
+        assert_eq!(
+            CompactCardProtocol::unmask(&parameters, &ctx, &bad_decryption_key, &masked),
+            Err(CardProtocolError::invalid_player_proof(
+                1,
+                &bad_decryption_key[1].2,
+                Operation::Reveal,
+                CryptoError::ProofVerificationError(String::from("Chaum-Pedersen"))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_reveal_tokens() {
+        let rng = &mut thread_rng();
+        let m = 4;
+        let n = 13;
+        let num_of_cards = 5;
+
+        let parameters = CardProtocol::setup(rng, m, n).unwrap();
+        let ctx = SessionContext::new(b"test game", 0, &parameters);
+        let (players, expected_shared_key) = setup_players(rng, &parameters, 3);
+
+        let cards: Vec<Card> = sample_vector(rng, num_of_cards);
+        let masked_cards = cards
+            .iter()
+            .map(|card| {
+                let alpha = Scalar::rand(rng);
+                CardProtocol::mask(rng, &parameters, &ctx, &expected_shared_key, card, &alpha)
+                    .unwrap()
+                    .0
+            })
+            .collect::<Vec<MaskedCard>>();
+
+        // Each player reveals all the cards with a single proof
+        let reveals = players
+            .iter()
+            .map(|(pk, sk, _)| {
+                CardProtocol::compute_reveal_tokens(rng, &parameters, &ctx, sk, pk, &masked_cards)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        for ((pk, _, _), (tokens, proof)) in players.iter().zip(reveals.iter()) {
+            assert_eq!(
+                CardProtocol::verify_reveal_tokens(
+                    &parameters,
+                    &ctx,
+                    pk,
+                    tokens,
+                    &masked_cards,
+                    proof
+                ),
+                Ok(())
+            );
+        }
+
+        let unmasked = masked_cards
+            .iter()
+            .enumerate()
+            .map(|(i, masked)| {
+                let aggregate_token = reveals
+                    .iter()
+                    .fold(RevealToken::zero(), |acc, (tokens, _)| acc + tokens[i]);
+                aggregate_token.reveal(masked).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(cards, unmasked);
+
+        let (pk, _, _) = &players[0];
+        let (tokens, proof) = &reveals[0];
+        assert!(CardProtocol::verify_reveal_tokens(
+            &parameters,
+            &ctx,
+            &players[1].0,
+            tokens,
+            &masked_cards,
+            proof
+        )
+        .is_err());
+
+        let mut bad_tokens = tokens.clone();
+        bad_tokens.swap(1, 2);
+        assert_eq!(
+            CardProtocol::verify_reveal_tokens(
+                &parameters,
+                &ctx,
+                pk,
+                &bad_tokens,
+                &masked_cards,
+                proof
+            ),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Chaum-Pedersen"
+            )))
+        );
+
+        assert_eq!(
+            CardProtocol::verify_reveal_tokens(
+                &parameters,
+                &ctx,
+                pk,
+                &tokens[1..],
+                &masked_cards,
+                proof
+            ),
+            Err(CryptoError::AggregateStatementLengthError(
+                num_of_cards,
+                num_of_cards - 1
+            ))
+        );
     }
 }
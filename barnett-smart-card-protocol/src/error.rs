@@ -1,16 +1,164 @@
+use ark_serialize::CanonicalSerialize;
 use proof_essentials::error::CryptoError;
+use std::fmt;
 use thiserror::Error;
 
+/// Protocol operation whose correctness a player proves in zero knowledge
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    KeyOwnership,
+    Masking,
+    Remasking,
+    Reveal,
+    Shuffle,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::KeyOwnership => "key ownership",
+            Self::Masking => "masking",
+            Self::Remasking => "remasking",
+            Self::Reveal => "reveal",
+            Self::Shuffle => "shuffle",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// This is an error that could occur when running a cryptographic primitive
 #[derive(Error, Debug, PartialEq)]
 pub enum CardProtocolError {
     #[error("Failed to verify proof")]
     ProofVerificationError(#[from] CryptoError),
 
+    #[error("Player {player} sent an invalid {operation} proof")]
+    InvalidPlayerProof {
+        player: usize,
+        /// Compressed serialization of the public key of the player
+        public_key: Vec<u8>,
+        operation: Operation,
+        source: CryptoError,
+    },
+
+    #[error("Player {0} registered the identity point as public key")]
+    IdentityPublicKey(usize),
+
+    #[error("Players {0} and {1} registered the same public key")]
+    DuplicatePublicKey(usize, usize),
+
+    #[error("Players {0} and {1} registered the same public information")]
+    DuplicatePlayerInfo(usize, usize),
+
+    #[error("Reveal token {0} was issued by a key that is not in the roster")]
+    UnregisteredRevealToken(usize),
+
+    #[error("Player {0} supplied more than one reveal token")]
+    DuplicateRevealToken(usize),
+
+    #[error("Missing reveal token from player {0}")]
+    MissingRevealToken(usize),
+
+    #[error("Got {0} decryption keys for {1} masked cards")]
+    MismatchedDecryptionKeys(usize, usize),
+
+    #[error("Got {0} values for a deck of {1} cards")]
+    MismatchedDeckLength(usize, usize),
+
+    #[error("Got {0} decks for {1} shuffle proofs")]
+    MismatchedShuffleChain(usize, usize),
+
+    #[error("Unmasked value is not a card of the deck")]
+    UnknownCard,
+
+    #[error("Parameters were not derived from the expected seed")]
+    InvalidParameters,
+
+    #[error("Contribution {0} of the setup transcript is invalid")]
+    InvalidContribution(usize),
+
+    #[error("Threshold {0} is invalid for {1} players")]
+    InvalidThreshold(usize, usize),
+
+    #[error("Dealer {0} published an invalid commitment")]
+    InvalidDealing(usize),
+
+    #[error("Share dealt by dealer {0} does not match its commitment")]
+    InvalidShare(usize),
+
+    #[error("Got {0} shares for {1} dealers")]
+    MismatchedShares(usize, usize),
+
+    #[error("Got {0} reveal tokens but {1} are needed")]
+    NotEnoughRevealTokens(usize, usize),
+
+    #[error("Encryptions of zero were computed under other parameters or another key")]
+    MismatchedEncryptionPool,
+
     #[error("IoError: {0}")]
     IoError(String),
 }
 
+impl CardProtocolError {
+    pub(crate) fn invalid_player_proof<K: CanonicalSerialize>(
+        player: usize,
+        public_key: &K,
+        operation: Operation,
+        source: CryptoError,
+    ) -> Self {
+        let mut bytes = Vec::with_capacity(public_key.compressed_size());
+        public_key
+            .serialize_compressed(&mut bytes)
+            .expect("failed to serialize public key");
+
+        Self::InvalidPlayerProof {
+            player,
+            public_key: bytes,
+            operation,
+            source,
+        }
+    }
+
+    /// Index of the player responsible for the error, if one can be blamed
+    pub fn culprit(&self) -> Option<usize> {
+        match self {
+            Self::InvalidPlayerProof { player, .. } => Some(*player),
+            Self::IdentityPublicKey(player)
+            | Self::DuplicatePublicKey(_, player)
+            | Self::DuplicatePlayerInfo(_, player)
+            | Self::DuplicateRevealToken(player)
+            | Self::InvalidDealing(player)
+            | Self::InvalidShare(player) => Some(*player),
+            _ => None,
+        }
+    }
+}
+
+/// Attach the player and operation responsible for a failed proof verification to its error.
+/// Verifying a single proof, e.g. with `verify_shuffle`, does not tell who sent it: callers who know
+/// it use `.blame(player, &public_key, Operation::Shuffle)` to get an attributed error.
+pub trait Blame<T> {
+    fn blame<K: CanonicalSerialize>(
+        self,
+        player: usize,
+        public_key: &K,
+        operation: Operation,
+    ) -> Result<T, CardProtocolError>;
+}
+
+impl<T> Blame<T> for Result<T, CryptoError> {
+    fn blame<K: CanonicalSerialize>(
+        self,
+        player: usize,
+        public_key: &K,
+        operation: Operation,
+    ) -> Result<T, CardProtocolError> {
+        self.map_err(|source| {
+            CardProtocolError::invalid_player_proof(player, public_key, operation, source)
+        })
+    }
+}
+
 impl From<std::io::Error> for CardProtocolError {
     fn from(err: std::io::Error) -> Self {
         Self::IoError(err.to_string())
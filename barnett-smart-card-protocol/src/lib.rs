@@ -4,10 +4,12 @@ use ark_ff::Field;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::hash::Hash;
 use ark_std::ops::{Add, Mul};
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng, RngCore};
 use proof_essentials::error::CryptoError;
+use proof_essentials::homomorphic_encryption::pool::EncryptionOfZeroPool;
 use proof_essentials::homomorphic_encryption::HomomorphicEncryptionScheme;
 use proof_essentials::utils::permutation::Permutation;
+use proof_essentials::utils::secret::Secret;
 use proof_essentials::vector_commitment::HomomorphicCommitmentScheme;
 
 pub mod discrete_log_cards;
@@ -20,6 +22,14 @@ pub trait Mask<Scalar: Field, Enc: HomomorphicEncryptionScheme<Scalar>> {
         shared_key: &Enc::PublicKey,
         r: &Scalar,
     ) -> Result<Enc::Ciphertext, CardProtocolError>;
+
+    /// Same as [`Mask::mask`] with prepared parameters and shared key
+    fn mask_prepared(
+        &self,
+        pp: &Enc::PreparedParameters,
+        shared_key: &Enc::PreparedPublicKey,
+        r: &Scalar,
+    ) -> Result<Enc::Ciphertext, CardProtocolError>;
 }
 
 pub trait Remask<Scalar: Field, Enc: HomomorphicEncryptionScheme<Scalar>> {
@@ -29,6 +39,18 @@ pub trait Remask<Scalar: Field, Enc: HomomorphicEncryptionScheme<Scalar>> {
         shared_key: &Enc::PublicKey,
         r: &Scalar,
     ) -> Result<Enc::Ciphertext, CardProtocolError>;
+
+    /// Same as [`Remask::remask`] with prepared parameters and shared key
+    fn remask_prepared(
+        &self,
+        pp: &Enc::PreparedParameters,
+        shared_key: &Enc::PreparedPublicKey,
+        r: &Scalar,
+    ) -> Result<Enc::Ciphertext, CardProtocolError>;
+
+    /// Remask by adding an encryption of zero computed in advance, e.g. drawn from an
+    /// [`EncryptionOfZeroPool`]
+    fn remask_with(&self, encryption_of_zero: &Enc::Ciphertext) -> Enc::Ciphertext;
 }
 
 pub trait Reveal<F: Field, Enc: HomomorphicEncryptionScheme<F>> {
@@ -45,6 +67,20 @@ pub trait BarnettSmartProtocol {
     type PlayerPublicKey: CanonicalDeserialize + CanonicalSerialize;
     type PlayerSecretKey;
     type AggregatePublicKey: CanonicalDeserialize + CanonicalSerialize;
+    /// Parameters and aggregate key with precomputed data, to speed up the many encryptions
+    /// performed under the same key during a game
+    type PreparedKey;
+    /// Work of a shuffle that does not depend on the deck to shuffle
+    type ShufflePrecomputation;
+    /// Parameters and aggregate key with the values needed to verify shuffles under them
+    type ShuffleVerifier<'v>;
+    /// Public data identifying a game session (table, hand, parameters, players). It is absorbed
+    /// into the Fiat-Shamir transcript of every proof so proofs cannot be replayed across sessions.
+    type SessionContext: CanonicalDeserialize + CanonicalSerialize;
+    type Roster;
+    /// Aggregate key whose secret is shared among the players, any `threshold` of them being
+    /// enough to unmask a card
+    type ThresholdKey;
     type Enc: HomomorphicEncryptionScheme<Self::Scalar>;
     type Comm: HomomorphicCommitmentScheme<Self::Scalar>;
 
@@ -78,15 +114,16 @@ pub trait BarnettSmartProtocol {
     ) -> Result<Self::Parameters, CardProtocolError>;
 
     /// Generate keys for a player.
-    fn player_keygen<R: Rng>(
+    fn player_keygen<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
     ) -> Result<(Self::PlayerPublicKey, Self::PlayerSecretKey), CardProtocolError>;
 
     /// Prove in zero knowledge that the owner of a public key `pk` knows the corresponding secret key `sk`
-    fn prove_key_ownership<B: CanonicalSerialize, R: Rng>(
+    fn prove_key_ownership<B: CanonicalSerialize, R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         pk: &Self::PlayerPublicKey,
         sk: &Self::PlayerSecretKey,
         player_public_info: &B,
@@ -95,22 +132,34 @@ pub trait BarnettSmartProtocol {
     /// Verify a proof od key ownership
     fn verify_key_ownership<B: CanonicalSerialize>(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         pk: &Self::PlayerPublicKey,
         player_public_info: &B,
         proof: &Self::ZKProofKeyOwnership,
     ) -> Result<(), CryptoError>;
 
+    /// Verify all the public keys and zk-proofs and build the roster of registered players.
+    /// Registration fails if a player uses the identity point as public key, or if two players
+    /// share a public key or the same public information.
+    fn register_players<B: CanonicalSerialize>(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        player_keys_proof_info: &[(Self::PlayerPublicKey, Self::ZKProofKeyOwnership, B)],
+    ) -> Result<Self::Roster, CardProtocolError>;
+
     /// Use all the public keys and zk-proofs to compute a verified aggregate public key
     fn compute_aggregate_key<B: CanonicalSerialize>(
         pp: &Self::Parameters,
-        player_keys_proof_info: &Vec<(Self::PlayerPublicKey, Self::ZKProofKeyOwnership, B)>,
+        ctx: &Self::SessionContext,
+        player_keys_proof_info: &[(Self::PlayerPublicKey, Self::ZKProofKeyOwnership, B)],
     ) -> Result<Self::AggregatePublicKey, CardProtocolError>;
 
     /// Use the shared public key and a (private) random scalar `alpha` to mask a card.
     /// Returns a masked card and a zk-proof that the masking operation was applied correctly.
-    fn mask<R: Rng>(
+    fn mask<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         original_card: &Self::Card,
         alpha: &Self::Scalar,
@@ -119,6 +168,7 @@ pub trait BarnettSmartProtocol {
     /// Verify a proof of masking
     fn verify_mask(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         card: &Self::Card,
         masked_card: &Self::MaskedCard,
@@ -127,17 +177,36 @@ pub trait BarnettSmartProtocol {
 
     /// Use the shared public key and a (private) random scalar `alpha` to remask a masked card.
     /// Returns a masked card and a zk-proof that the remasking operation was applied correctly.
-    fn remask<R: Rng>(
+    fn remask<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         original_masked: &Self::MaskedCard,
         alpha: &Self::Scalar,
     ) -> Result<(Self::MaskedCard, Self::ZKProofRemasking), CardProtocolError>;
 
+    /// Pool of encryptions of zero under `shared_key`, to be filled while waiting for one's turn
+    fn encryption_pool(
+        pp: &Self::Parameters,
+        shared_key: &Self::AggregatePublicKey,
+    ) -> EncryptionOfZeroPool<Self::Scalar, Self::Enc>;
+
+    /// Same as [`BarnettSmartProtocol::remask`] with an encryption of zero drawn from `pool`,
+    /// whose randomness is used as `alpha`
+    fn remask_from_pool<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        shared_key: &Self::AggregatePublicKey,
+        original_masked: &Self::MaskedCard,
+        pool: &mut EncryptionOfZeroPool<Self::Scalar, Self::Enc>,
+    ) -> Result<(Self::MaskedCard, Self::ZKProofRemasking), CardProtocolError>;
+
     /// Verify a proof of remasking
     fn verify_remask(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         original_masked: &Self::MaskedCard,
         remasked: &Self::MaskedCard,
@@ -147,9 +216,10 @@ pub trait BarnettSmartProtocol {
     /// Players can use this function to compute their reveal token for a given masked card.
     /// The token is accompanied by a proof that it is a valid reveal for the specified card issued
     /// by the player who ran the computation.
-    fn compute_reveal_token<R: Rng>(
+    fn compute_reveal_token<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         sk: &Self::PlayerSecretKey,
         pk: &Self::PlayerPublicKey,
         masked_card: &Self::MaskedCard,
@@ -158,41 +228,222 @@ pub trait BarnettSmartProtocol {
     /// Verify a proof of correctly computed reveal token
     fn verify_reveal(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         pk: &Self::PlayerPublicKey,
         reveal_token: &Self::RevealToken,
         masked_card: &Self::MaskedCard,
         proof: &Self::ZKProofReveal,
     ) -> Result<(), CryptoError>;
 
+    /// Verify many proofs of correctly computed reveal tokens at once, for example all the tokens
+    /// needed to open the board. This is much faster than calling
+    /// [`BarnettSmartProtocol::verify_reveal`] on each of them, but a failure does not tell which
+    /// proof is invalid.
+    #[allow(clippy::type_complexity)]
+    fn verify_reveal_batch(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        reveals: &[(
+            &Self::PlayerPublicKey,
+            &Self::RevealToken,
+            &Self::MaskedCard,
+            &Self::ZKProofReveal,
+        )],
+    ) -> Result<(), CryptoError>;
+
+    /// Same as [`BarnettSmartProtocol::compute_reveal_token`] for several cards, e.g. when a player
+    /// opens their hand. A single proof covers all the tokens, whatever their number.
+    fn compute_reveal_tokens<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        sk: &Self::PlayerSecretKey,
+        pk: &Self::PlayerPublicKey,
+        masked_cards: &[Self::MaskedCard],
+    ) -> Result<(Vec<Self::RevealToken>, Self::ZKProofReveal), CardProtocolError>;
+
+    /// Verify a proof produced by [`BarnettSmartProtocol::compute_reveal_tokens`], where
+    /// `reveal_tokens[i]` is the token of `masked_cards[i]`
+    fn verify_reveal_tokens(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        pk: &Self::PlayerPublicKey,
+        reveal_tokens: &[Self::RevealToken],
+        masked_cards: &[Self::MaskedCard],
+        proof: &Self::ZKProofReveal,
+    ) -> Result<(), CryptoError>;
+
     /// After collecting all the necessary reveal tokens and proofs that these are correctly issued,
     /// players can unmask a masked card to recover the underlying card.
     fn unmask(
         pp: &Self::Parameters,
-        decryption_key: &Vec<(
+        ctx: &Self::SessionContext,
+        decryption_key: &[(
             Self::RevealToken,
             Self::ZKProofReveal,
             Self::PlayerPublicKey,
-        )>,
+        )],
         masked_card: &Self::MaskedCard,
     ) -> Result<Self::Card, CardProtocolError>;
 
+    /// Same as [`BarnettSmartProtocol::unmask`] for several cards, where `decryption_keys[i]` holds
+    /// the reveal tokens of `masked_cards[i]`. All the reveal proofs are verified in a single
+    /// batch. If the batch is invalid, the proofs are checked one by one to blame the player who
+    /// sent an invalid one.
+    #[allow(clippy::type_complexity)]
+    fn unmask_many(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        decryption_keys: &[Vec<(
+            Self::RevealToken,
+            Self::ZKProofReveal,
+            Self::PlayerPublicKey,
+        )>],
+        masked_cards: &[Self::MaskedCard],
+    ) -> Result<Vec<Self::Card>, CardProtocolError>;
+
+    /// Same as [`BarnettSmartProtocol::unmask`], but also checks that exactly one reveal token was
+    /// supplied by each player of the roster and that the unmasked card belongs to `card_set`.
+    fn unmask_with_roster(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        roster: &Self::Roster,
+        decryption_key: &[(
+            Self::RevealToken,
+            Self::ZKProofReveal,
+            Self::PlayerPublicKey,
+        )],
+        masked_card: &Self::MaskedCard,
+        card_set: &[Self::Card],
+    ) -> Result<Self::Card, CardProtocolError>;
+
+    /// Same as [`BarnettSmartProtocol::unmask`] for cards masked under a threshold key. The reveal
+    /// tokens of any `threshold` players are combined by Lagrange interpolation, every token
+    /// being proven against the verification key of the player who issued it.
+    fn unmask_threshold(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        key: &Self::ThresholdKey,
+        decryption_key: &[(
+            Self::RevealToken,
+            Self::ZKProofReveal,
+            Self::PlayerPublicKey,
+        )],
+        masked_card: &Self::MaskedCard,
+    ) -> Result<Self::Card, CardProtocolError>;
+
+    /// Precompute the data used to mask, remask and shuffle under `shared_key`. Preparing the key
+    /// pays off as soon as it is used for a few operations, e.g. once per game.
+    fn prepare_key(
+        pp: &Self::Parameters,
+        shared_key: &Self::AggregatePublicKey,
+    ) -> Self::PreparedKey;
+
     /// Shuffle and remask a deck of masked cards using a player-chosen permutation and vector of
     /// masking factors.
-    fn shuffle_and_remask<R: Rng>(
+    fn shuffle_and_remask<R: CryptoRng + RngCore>(
         rng: &mut R,
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
-        deck: &Vec<Self::MaskedCard>,
-        masking_factors: &Vec<Self::Scalar>,
+        deck: &[Self::MaskedCard],
+        masking_factors: &Secret<Vec<Self::Scalar>>,
+        permutation: &Permutation,
+    ) -> Result<(Vec<Self::MaskedCard>, Self::ZKProofShuffle), CardProtocolError>;
+
+    /// Same as [`BarnettSmartProtocol::shuffle_and_remask`] with a key obtained from
+    /// [`BarnettSmartProtocol::prepare_key`]
+    fn shuffle_and_remask_prepared<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        prepared_key: &Self::PreparedKey,
+        deck: &[Self::MaskedCard],
+        masking_factors: &Secret<Vec<Self::Scalar>>,
         permutation: &Permutation,
     ) -> Result<(Vec<Self::MaskedCard>, Self::ZKProofShuffle), CardProtocolError>;
 
+    /// Do the part of [`BarnettSmartProtocol::shuffle_and_remask_prepared`] that does not depend on
+    /// the input deck, for example while waiting for the previous player to shuffle. The shuffle
+    /// is finished with [`BarnettSmartProtocol::shuffle_and_remask_precomputed`] once the deck
+    /// arrives.
+    fn precompute_shuffle<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        prepared_key: &Self::PreparedKey,
+        masking_factors: &Secret<Vec<Self::Scalar>>,
+        permutation: &Permutation,
+    ) -> Result<Self::ShufflePrecomputation, CardProtocolError>;
+
+    /// Shuffle and remask a deck with the permutation and masking factors of `precomputation`.
+    /// Using the same `rng` for both phases gives the same result as
+    /// [`BarnettSmartProtocol::shuffle_and_remask_prepared`]. The precomputation is consumed, as
+    /// reusing it would make the shuffles linkable.
+    fn shuffle_and_remask_precomputed<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        prepared_key: &Self::PreparedKey,
+        deck: &[Self::MaskedCard],
+        precomputation: Self::ShufflePrecomputation,
+    ) -> Result<(Vec<Self::MaskedCard>, Self::ZKProofShuffle), CardProtocolError>;
+
+    /// Same as [`BarnettSmartProtocol::shuffle_and_remask_prepared`] with encryptions of zero
+    /// drawn from `pool`, whose randomness is used as masking factors
+    fn shuffle_and_remask_from_pool<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        prepared_key: &Self::PreparedKey,
+        deck: &[Self::MaskedCard],
+        permutation: &Permutation,
+        pool: &mut EncryptionOfZeroPool<Self::Scalar, Self::Enc>,
+    ) -> Result<(Vec<Self::MaskedCard>, Self::ZKProofShuffle), CardProtocolError>;
+
     /// Verify a proof of correct shuffle
     fn verify_shuffle(
         pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
         shared_key: &Self::AggregatePublicKey,
         original_deck: &[Self::MaskedCard],
         shuffled_deck: &[Self::MaskedCard],
         proof: &Self::ZKProofShuffle,
     ) -> Result<(), CryptoError>;
+
+    /// Precompute the values used to verify shuffles under `shared_key`. Like
+    /// [`BarnettSmartProtocol::prepare_key`], this pays off when the verifier is kept for the whole
+    /// game.
+    fn prepare_shuffle_verifier<'v>(
+        pp: &'v Self::Parameters,
+        shared_key: &'v Self::AggregatePublicKey,
+    ) -> Result<Self::ShuffleVerifier<'v>, CryptoError>;
+
+    /// Same as [`BarnettSmartProtocol::verify_shuffle`] with a prepared verifier
+    fn verify_shuffle_prepared(
+        verifier: &Self::ShuffleVerifier<'_>,
+        ctx: &Self::SessionContext,
+        original_deck: &[Self::MaskedCard],
+        shuffled_deck: &[Self::MaskedCard],
+        proof: &Self::ZKProofShuffle,
+    ) -> Result<(), CryptoError>;
+
+    /// Verify the proofs of successive shuffles of a deck, where `proofs[i]` shows that
+    /// `decks[i + 1]` is a shuffle of `decks[i]` and comes with the index in the roster and the
+    /// public key of the player who made it. All the proofs are verified in a single batch. If one
+    /// of them is invalid, the error blames that player, whatever its position in the chain.
+    fn verify_shuffle_chain(
+        pp: &Self::Parameters,
+        ctx: &Self::SessionContext,
+        shared_key: &Self::AggregatePublicKey,
+        decks: &[Vec<Self::MaskedCard>],
+        proofs: &[(usize, Self::ZKProofShuffle, Self::PlayerPublicKey)],
+    ) -> Result<(), CardProtocolError>;
+
+    /// Same as [`BarnettSmartProtocol::verify_shuffle_chain`] with a prepared verifier
+    fn verify_shuffle_chain_prepared(
+        verifier: &Self::ShuffleVerifier<'_>,
+        ctx: &Self::SessionContext,
+        decks: &[Vec<Self::MaskedCard>],
+        proofs: &[(usize, Self::ZKProofShuffle, Self::PlayerPublicKey)],
+    ) -> Result<(), CardProtocolError>;
 }
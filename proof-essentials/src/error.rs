@@ -1,3 +1,4 @@
+use std::fmt;
 use thiserror::Error;
 
 /// This is an error that could occur when running a cryptographic primitive
@@ -6,6 +7,15 @@ pub enum CryptoError {
     #[error("Failed to verify {0} proof")]
     ProofVerificationError(String),
 
+    #[error("Failed to verify {argument}: {check}")]
+    ArgumentVerificationError { argument: Argument, check: Check },
+
+    #[error("Proof {index} of the batch is invalid: {source}")]
+    InvalidBatchProof {
+        index: usize,
+        source: Box<CryptoError>,
+    },
+
     #[error("Failed to output a {0} commitment: values {1} > bases {2}")]
     CommitmentLengthError(String, usize, usize),
 
@@ -24,16 +34,125 @@ pub enum CryptoError {
     #[error("Diagonals Error: left = {0} - right = {1}")]
     DiagonalLengthError(usize, usize),
 
+    #[error("Aggregate Statement error: bases = {0} - points = {1}")]
+    AggregateStatementLengthError(usize, usize),
+
     #[error("InvalidProductArgumentStatement")]
     InvalidProductArgumentStatement,
 
     #[error("InvalidShuffleStatement")]
     InvalidShuffleStatement,
 
+    #[error("Precomputed data does not match the statement or the witness")]
+    InvalidPrecomputation,
+
+    #[error("Linear relation refers to a scalar that does not exist")]
+    InvalidLinearRelation,
+
+    #[error("Sigma protocol state was not produced for this witness")]
+    InvalidSigmaState,
+
+    #[error("{0} has {1} scalars, expected {2}")]
+    ScalarsLengthError(String, usize, usize),
+
     #[error("IoError: {0}")]
     IoError(String),
 }
 
+/// Arguments composing the proof of a correct shuffle
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum Argument {
+    Shuffle,
+    MatrixElementsProduct,
+    HadamardProduct,
+    ZeroValue,
+    SingleValueProduct,
+    MultiExponentiation,
+}
+
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Shuffle => "Shuffle Argument (4)",
+            Self::MatrixElementsProduct => "Product Argument (5)",
+            Self::HadamardProduct => "Hadamard Product (5.1)",
+            Self::ZeroValue => "Zero Argument (5.2)",
+            Self::SingleValueProduct => "Single Value Product Argument (5.3)",
+            Self::MultiExponentiation => "Multi Exponentiation",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Verification equation of an argument that did not hold
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum Check {
+    /// A vector of the proof does not have the expected number of elements
+    Length {
+        name: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// A commitment that must open to zero does not. `index` locates it in its vector, if any.
+    CommitmentToZero {
+        name: &'static str,
+        index: Option<usize>,
+    },
+    /// A commitment does not open to the blinded vector sent in the last round. `index` is the row
+    /// whose commitment does not open, `None` when the rows are checked together.
+    Opening {
+        name: &'static str,
+        index: Option<usize>,
+    },
+    /// An element of the proof does not match the corresponding element of the statement. `index`
+    /// locates it in its vector, if any.
+    Mismatch {
+        name: &'static str,
+        index: Option<usize>,
+    },
+}
+
+/// Name of a checked value, followed by its index when it is an element of a vector
+struct Located(&'static str, Option<usize>);
+
+impl fmt::Display for Located {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 {
+            Some(index) => write!(f, "{}[{}]", self.0, index),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length {
+                name,
+                expected,
+                found,
+            } => write!(f, "{} has {} elements, expected {}", name, found, expected),
+            Self::CommitmentToZero { name, index } => {
+                write!(f, "{} is not a commitment to zero", Located(name, *index))
+            }
+            Self::Opening { name, index } => write!(
+                f,
+                "{} does not open to the blinded values",
+                Located(name, *index)
+            ),
+            Self::Mismatch { name, index } => {
+                write!(f, "{} does not match the statement", Located(name, *index))
+            }
+        }
+    }
+}
+
+impl CryptoError {
+    pub(crate) fn failed_check(argument: Argument, check: Check) -> Self {
+        Self::ArgumentVerificationError { argument, check }
+    }
+}
+
 impl From<std::io::Error> for CryptoError {
     fn from(err: std::io::Error) -> Self {
         Self::IoError(err.to_string())
@@ -1,8 +1,9 @@
-use super::super::Ciphertext;
+use super::super::{Ciphertext, ProjectiveCiphertext};
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_std::ops::Mul;
 use ark_std::{UniformRand, Zero};
 use rand::Rng;
+use zeroize::Zeroize;
 
 impl<C: CurveGroup> std::ops::Add<Ciphertext<C>> for Ciphertext<C> {
     type Output = Self;
@@ -35,6 +36,13 @@ impl<C: CurveGroup> Zero for Ciphertext<C> {
     }
 }
 
+impl<C: CurveGroup> Zeroize for Ciphertext<C> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+        self.1.zeroize();
+    }
+}
+
 impl<C: CurveGroup> UniformRand for Ciphertext<C> {
     fn rand<R: Rng + ?Sized>(rng: &mut R) -> Self {
         let c0 = C::rand(rng).into_affine();
@@ -44,6 +52,43 @@ impl<C: CurveGroup> UniformRand for Ciphertext<C> {
     }
 }
 
+impl<C: CurveGroup> From<Ciphertext<C>> for ProjectiveCiphertext<C> {
+    fn from(ciphertext: Ciphertext<C>) -> Self {
+        Self(ciphertext.0.into(), ciphertext.1.into())
+    }
+}
+
+impl<C: CurveGroup> std::ops::Add<ProjectiveCiphertext<C>> for ProjectiveCiphertext<C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<C: CurveGroup> Mul<C::ScalarField> for ProjectiveCiphertext<C> {
+    type Output = Self;
+    fn mul(self, x: C::ScalarField) -> Self::Output {
+        Self(self.0 * x, self.1 * x)
+    }
+}
+
+impl<C: CurveGroup> std::iter::Sum for ProjectiveCiphertext<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |a, b| a + b)
+    }
+}
+
+impl<C: CurveGroup> Zero for ProjectiveCiphertext<C> {
+    fn zero() -> Self {
+        Self(C::zero(), C::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -1,12 +1,15 @@
 use crate::error::CryptoError;
 use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
+use crate::utils::constant_time::mul_secret;
+use crate::utils::fixed_base::FixedBaseTable;
+use crate::utils::secret::Secret;
 
-use ark_ec::{AffineRepr, CurveGroup, Group};
-use ark_ff::{fields::PrimeField, UniformRand};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::UniformRand;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::hash::Hash;
-use ark_std::ops::Mul;
-use ark_std::{marker::PhantomData, rand::Rng};
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng, RngCore};
 
 pub mod arithmetic_definitions;
 mod tests;
@@ -27,11 +30,49 @@ pub struct Plaintext<C: CurveGroup>(pub C::Affine);
 
 pub type Generator<C> = Plaintext<C>;
 
-pub type SecretKey<C> = <C as Group>::ScalarField;
+pub type SecretKey<C> = Secret<<C as Group>::ScalarField>;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Ciphertext<C: CurveGroup>(pub C::Affine, pub C::Affine);
 
+/// Ciphertext in projective coordinates, see [`HomomorphicEncryptionScheme::ProjectiveCiphertext`]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ProjectiveCiphertext<C: CurveGroup>(pub C, pub C);
+
+/// Encryption parameters with a precomputed table for the generator
+#[derive(Clone)]
+pub struct PreparedParameters<C: CurveGroup> {
+    pub parameters: Parameters<C>,
+    generator: FixedBaseTable<C>,
+}
+
+impl<C: CurveGroup> PreparedParameters<C> {
+    pub fn new(parameters: &Parameters<C>) -> Self {
+        Self {
+            parameters: *parameters,
+            generator: FixedBaseTable::new(&parameters.generator),
+        }
+    }
+}
+
+/// Public key with a precomputed table, for example the aggregate key of a game
+#[derive(Clone)]
+pub struct PreparedPublicKey<C: CurveGroup> {
+    table: FixedBaseTable<C>,
+}
+
+impl<C: CurveGroup> PreparedPublicKey<C> {
+    pub fn new(public_key: &PublicKey<C>) -> Self {
+        Self {
+            table: FixedBaseTable::new(public_key),
+        }
+    }
+
+    pub fn public_key(&self) -> &PublicKey<C> {
+        self.table.base()
+    }
+}
+
 impl<C: CurveGroup> HomomorphicEncryptionScheme<C::ScalarField> for ElGamal<C> {
     type Parameters = Parameters<C>;
     type Generator = Generator<C>;
@@ -39,6 +80,9 @@ impl<C: CurveGroup> HomomorphicEncryptionScheme<C::ScalarField> for ElGamal<C> {
     type SecretKey = SecretKey<C>;
     type Plaintext = Plaintext<C>;
     type Ciphertext = Ciphertext<C>;
+    type ProjectiveCiphertext = ProjectiveCiphertext<C>;
+    type PreparedParameters = PreparedParameters<C>;
+    type PreparedPublicKey = PreparedPublicKey<C>;
 
     fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, CryptoError> {
         // get a random generator
@@ -51,7 +95,7 @@ impl<C: CurveGroup> HomomorphicEncryptionScheme<C::ScalarField> for ElGamal<C> {
         Ok(Generator::rand(rng))
     }
 
-    fn keygen<R: Rng>(
+    fn keygen<R: CryptoRng + RngCore>(
         pp: &Self::Parameters,
         rng: &mut R,
     ) -> Result<(Self::PublicKey, Self::SecretKey), CryptoError> {
@@ -59,9 +103,9 @@ impl<C: CurveGroup> HomomorphicEncryptionScheme<C::ScalarField> for ElGamal<C> {
         let secret_key: <C as Group>::ScalarField = C::ScalarField::rand(rng);
 
         // compute secret_key*generator to derive the public key
-        let public_key = pp.generator.mul(secret_key).into();
+        let public_key = mul_secret::<C>(&pp.generator, &secret_key).into();
 
-        Ok((public_key, secret_key))
+        Ok((public_key, Secret::new(secret_key)))
     }
 
     fn encrypt(
@@ -71,15 +115,39 @@ impl<C: CurveGroup> HomomorphicEncryptionScheme<C::ScalarField> for ElGamal<C> {
         r: &C::ScalarField,
     ) -> Result<Self::Ciphertext, CryptoError> {
         // compute s = r*pk
-        let s = Plaintext(pk.mul_bigint(r.into_bigint()).into_affine());
+        let s = mul_secret::<C>(pk, r);
 
         // compute c1 = r*generator
-        let c1 = pp.generator.mul_bigint(r.into_bigint()).into();
+        let c1 = mul_secret::<C>(&pp.generator, r);
+
+        // compute c2 = m + s
+        let c2 = s + message.0;
+
+        Ok(Self::normalize_batch(&[ProjectiveCiphertext(c1, c2)])[0])
+    }
+
+    fn prepare_parameters(pp: &Self::Parameters) -> Self::PreparedParameters {
+        PreparedParameters::new(pp)
+    }
+
+    fn prepare_public_key(pk: &Self::PublicKey) -> Self::PreparedPublicKey {
+        PreparedPublicKey::new(pk)
+    }
+
+    fn encrypt_prepared(
+        pp: &Self::PreparedParameters,
+        pk: &Self::PreparedPublicKey,
+        message: &Self::Plaintext,
+        r: &C::ScalarField,
+    ) -> Result<Self::Ciphertext, CryptoError> {
+        // compute c1 = r*generator and s = r*pk from the tables
+        let c1 = pp.generator.mul(r);
+        let s = pk.table.mul(r);
 
         // compute c2 = m + s
-        let c2 = *message + s;
+        let c2 = s + message.0;
 
-        Ok(Ciphertext(c1, c2.0))
+        Ok(Self::normalize_batch(&[ProjectiveCiphertext(c1, c2)])[0])
     }
 
     fn decrypt(
@@ -91,7 +159,7 @@ impl<C: CurveGroup> HomomorphicEncryptionScheme<C::ScalarField> for ElGamal<C> {
         let c2: <C as CurveGroup>::Affine = ciphertext.1;
 
         // compute s = secret_key * c1
-        let s = c1.mul_bigint(sk.into_bigint());
+        let s = mul_secret::<C>(&c1, sk.expose());
         let s_inv = -s;
 
         // compute message = c2 - s
@@ -99,4 +167,35 @@ impl<C: CurveGroup> HomomorphicEncryptionScheme<C::ScalarField> for ElGamal<C> {
 
         Ok(Plaintext(m.into()))
     }
+
+    fn normalize_batch(ciphertexts: &[Self::ProjectiveCiphertext]) -> Vec<Self::Ciphertext> {
+        let points = ciphertexts
+            .iter()
+            .flat_map(|ciphertext| [ciphertext.0, ciphertext.1])
+            .collect::<Vec<C>>();
+
+        C::normalize_batch(&points)
+            .chunks(2)
+            .map(|pair| Ciphertext(pair[0], pair[1]))
+            .collect()
+    }
+
+    /// Compute the dot product with one multi-scalar multiplication per ciphertext component
+    fn dot_product_projective(
+        scalars: &[C::ScalarField],
+        ciphertexts: &[Self::Ciphertext],
+    ) -> Result<Self::ProjectiveCiphertext, CryptoError> {
+        if scalars.len() != ciphertexts.len() {
+            return Err(CryptoError::DotProductLengthError(
+                scalars.len(),
+                ciphertexts.len(),
+            ));
+        }
+
+        let (c1, c2): (Vec<_>, Vec<_>) = ciphertexts.iter().map(|c| (c.0, c.1)).unzip();
+        Ok(ProjectiveCiphertext(
+            C::msm_unchecked(&c1, scalars),
+            C::msm_unchecked(&c2, scalars),
+        ))
+    }
 }
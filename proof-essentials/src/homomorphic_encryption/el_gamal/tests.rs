@@ -2,15 +2,20 @@
 mod test {
     use super::super::super::{el_gamal, HomomorphicEncryptionScheme};
     use crate::utils::rand::sample_vector;
+    use crate::utils::secret::Secret;
+    use crate::utils::vector_arithmetic::dot_product;
 
     use ark_std::ops::Mul;
     use ark_std::rand::thread_rng;
+    use ark_std::Zero;
 
     // Define type aliases for succinctness
     type Curve = ark_bn254::G1Projective;
     type Scalar = ark_bn254::Fr;
     type ElGamal = el_gamal::ElGamal<Curve>;
     type Plaintext = el_gamal::Plaintext<Curve>;
+    type Ciphertext = el_gamal::Ciphertext<Curve>;
+    type ProjectiveCiphertext = el_gamal::ProjectiveCiphertext<Curve>;
     use ark_std::UniformRand;
 
     #[test]
@@ -39,6 +44,27 @@ mod test {
             assert_eq!(computed, expected)
         }
     }
+    #[test]
+    fn prepared_encrypt() {
+        let rng = &mut thread_rng();
+        let n = 10;
+
+        let parameters = ElGamal::setup(rng).unwrap();
+        let (pk, _) = ElGamal::keygen(&parameters, rng).unwrap();
+        let prepared_parameters = ElGamal::prepare_parameters(&parameters);
+        let prepared_pk = ElGamal::prepare_public_key(&pk);
+
+        let messages: Vec<Plaintext> = sample_vector(rng, n);
+        let randoms: Vec<Scalar> = sample_vector(rng, n);
+
+        for (m, r) in messages.iter().zip(randoms.iter()) {
+            assert_eq!(
+                ElGamal::encrypt_prepared(&prepared_parameters, &prepared_pk, m, r).unwrap(),
+                ElGamal::encrypt(&parameters, &pk, m, r).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn failed_encrypt_decrypt() {
         let rng = &mut thread_rng();
@@ -56,7 +82,7 @@ mod test {
             .map(|(m, r)| ElGamal::encrypt(&parameters, &pk, m, r).unwrap())
             .collect::<Vec<_>>();
 
-        let wrong_sk = Scalar::rand(rng);
+        let wrong_sk = Secret::new(Scalar::rand(rng));
 
         let decrypted = encrypted
             .iter()
@@ -94,4 +120,55 @@ mod test {
 
         assert_eq!(m3, decrypted)
     }
+
+    #[test]
+    fn msm_dot_product() {
+        let rng = &mut thread_rng();
+        let n = 52;
+
+        let scalars: Vec<Scalar> = sample_vector(rng, n);
+        let ciphers: Vec<Ciphertext> = sample_vector(rng, n);
+
+        assert_eq!(
+            ElGamal::dot_product(&scalars, &ciphers),
+            dot_product(&scalars, &ciphers)
+        );
+        assert_eq!(
+            ElGamal::dot_product(&scalars[1..], &ciphers),
+            dot_product(&scalars[1..], &ciphers)
+        );
+    }
+
+    #[test]
+    fn projective_arithmetic() {
+        let rng = &mut thread_rng();
+        let n = 20;
+
+        let scalars: Vec<Scalar> = sample_vector(rng, n);
+        let ciphers: Vec<Ciphertext> = sample_vector(rng, n);
+
+        // Horner evaluation, once with affine and once with projective ciphertexts
+        let affine = ciphers
+            .iter()
+            .zip(scalars.iter())
+            .fold(Ciphertext::zero(), |acc, (&c, &x)| acc * x + c);
+        let projective = ciphers
+            .iter()
+            .zip(scalars.iter())
+            .fold(ProjectiveCiphertext::zero(), |acc, (&c, &x)| {
+                acc * x + c.into()
+            });
+        assert_eq!(ElGamal::normalize_batch(&[projective]), vec![affine]);
+
+        let projective_ciphers = ciphers
+            .iter()
+            .map(|&c| ProjectiveCiphertext::from(c))
+            .collect::<Vec<_>>();
+        assert_eq!(ElGamal::normalize_batch(&projective_ciphers), ciphers);
+        assert_eq!(
+            ElGamal::dot_product_projective(&scalars, &ciphers)
+                .map(|product| ElGamal::normalize_batch(&[product])[0]),
+            dot_product(&scalars, &ciphers)
+        );
+    }
 }
@@ -1,23 +1,28 @@
 use crate::error::CryptoError;
+use crate::utils::vector_arithmetic;
 use ark_ff::{Field, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::iter::Sum;
 use ark_std::ops;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng, RngCore};
+use zeroize::Zeroize;
 
 pub mod el_gamal;
+pub mod pool;
 
 /// Trait defining the types and functions needed for an additively homomorphic encryption scheme.
 /// The scheme is defined with respect to a finite field `F` for which scalar multiplication is preserved.
 pub trait HomomorphicEncryptionScheme<Scalar: Field> {
-    type Parameters: CanonicalSerialize + CanonicalDeserialize;
-    type PublicKey: CanonicalSerialize + CanonicalDeserialize;
-    type SecretKey: CanonicalSerialize + CanonicalDeserialize;
+    type Parameters: CanonicalSerialize + CanonicalDeserialize + Send + Sync;
+    type PublicKey: CanonicalSerialize + CanonicalDeserialize + Send + Sync;
+    type SecretKey;
     type Generator: Copy
         + ops::Add
         + ops::Mul<Scalar, Output = Self::Plaintext>
         + CanonicalSerialize
-        + CanonicalDeserialize;
+        + CanonicalDeserialize
+        + Send
+        + Sync;
 
     /// Represent a plaintext from a generic homomorphic encryption scheme. To manifest the homomorphic
     /// property of the scheme, we require that some arithmetic operations (add and multiply by scalar) are implemented.
@@ -26,7 +31,9 @@ pub trait HomomorphicEncryptionScheme<Scalar: Field> {
         + ops::Mul<Scalar, Output = Self::Plaintext>
         + CanonicalSerialize
         + CanonicalDeserialize
-        + Zero;
+        + Zero
+        + Send
+        + Sync;
 
     /// Represent a ciphertext from a generic homomorphic encryption scheme. To manifest the homomorphic
     /// property of the scheme, we require that some arithmetic operations (add and multiply by scalar) are implemented.
@@ -37,7 +44,29 @@ pub trait HomomorphicEncryptionScheme<Scalar: Field> {
         + CanonicalSerialize
         + CanonicalDeserialize
         + Sum
-        + Zero;
+        + Zero
+        + Zeroize
+        + Send
+        + Sync;
+
+    /// Working representation of ciphertexts for chains of homomorphic operations. Unlike
+    /// [`HomomorphicEncryptionScheme::Ciphertext`], adding or multiplying does not normalise the
+    /// result, which is left to [`HomomorphicEncryptionScheme::normalize_batch`].
+    type ProjectiveCiphertext: Copy
+        + PartialEq
+        + ops::Add<Output = Self::ProjectiveCiphertext>
+        + ops::Mul<Scalar, Output = Self::ProjectiveCiphertext>
+        + From<Self::Ciphertext>
+        + Sum
+        + Zero
+        + Send
+        + Sync;
+
+    /// Parameters with precomputed data that speeds up repeated encryptions
+    type PreparedParameters: Send + Sync;
+
+    /// Public key with precomputed data that speeds up repeated encryptions under that key
+    type PreparedPublicKey: Send + Sync;
 
     /// Generate the scheme's parameters.
     fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, CryptoError>;
@@ -46,7 +75,7 @@ pub trait HomomorphicEncryptionScheme<Scalar: Field> {
     fn generator<R: Rng>(rng: &mut R) -> Result<Self::Generator, CryptoError>;
 
     /// Generate a public key and a private key.
-    fn keygen<R: Rng>(
+    fn keygen<R: CryptoRng + RngCore>(
         pp: &Self::Parameters,
         rng: &mut R,
     ) -> Result<(Self::PublicKey, Self::SecretKey), CryptoError>;
@@ -59,10 +88,50 @@ pub trait HomomorphicEncryptionScheme<Scalar: Field> {
         r: &Scalar,
     ) -> Result<Self::Ciphertext, CryptoError>;
 
+    /// Precompute the data used by [`HomomorphicEncryptionScheme::encrypt_prepared`]
+    fn prepare_parameters(pp: &Self::Parameters) -> Self::PreparedParameters;
+
+    /// Precompute the data used by [`HomomorphicEncryptionScheme::encrypt_prepared`]
+    fn prepare_public_key(pk: &Self::PublicKey) -> Self::PreparedPublicKey;
+
+    /// Same as [`HomomorphicEncryptionScheme::encrypt`] with prepared parameters and public key
+    fn encrypt_prepared(
+        pp: &Self::PreparedParameters,
+        pk: &Self::PreparedPublicKey,
+        message: &Self::Plaintext,
+        r: &Scalar,
+    ) -> Result<Self::Ciphertext, CryptoError>;
+
     /// Recover a message from the provided ciphertext using a private key.
     fn decrypt(
         pp: &Self::Parameters,
         sk: &Self::SecretKey,
         ciphertext: &Self::Ciphertext,
     ) -> Result<Self::Plaintext, CryptoError>;
+
+    /// Convert projective ciphertexts back to their normal form, all at once so that schemes can
+    /// share the cost of the conversion.
+    fn normalize_batch(ciphertexts: &[Self::ProjectiveCiphertext]) -> Vec<Self::Ciphertext>;
+
+    /// Compute the dot product of a vector of scalars and a vector of ciphertexts
+    fn dot_product(
+        scalars: &[Scalar],
+        ciphertexts: &[Self::Ciphertext],
+    ) -> Result<Self::Ciphertext, CryptoError> {
+        let product = Self::dot_product_projective(scalars, ciphertexts)?;
+        Ok(Self::normalize_batch(&[product])[0])
+    }
+
+    /// Same as [`HomomorphicEncryptionScheme::dot_product`] without normalising the result. Schemes
+    /// can override it with something faster than multiplying and adding the ciphertexts one by one.
+    fn dot_product_projective(
+        scalars: &[Scalar],
+        ciphertexts: &[Self::Ciphertext],
+    ) -> Result<Self::ProjectiveCiphertext, CryptoError> {
+        let projective = ciphertexts
+            .iter()
+            .map(|&ciphertext| ciphertext.into())
+            .collect::<Vec<Self::ProjectiveCiphertext>>();
+        vector_arithmetic::dot_product(scalars, &projective)
+    }
 }
@@ -0,0 +1,183 @@
+use crate::error::CryptoError;
+use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
+use crate::utils::rand::sample_vector;
+use crate::utils::secret::Secret;
+
+use ark_ff::{Field, Zero};
+use ark_serialize::CanonicalSerialize;
+use ark_std::cfg_iter;
+use ark_std::rand::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Encryptions of zero under one set of parameters and public key, computed in advance together
+/// with their randomness.
+/// Remasking a ciphertext is adding an encryption of zero to it, so players can fill the pool
+/// while waiting for their turn and remask without any scalar multiplication once it comes.
+/// Every entry must be used at most once.
+pub struct EncryptionOfZeroPool<Scalar, Enc>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+{
+    /// Parameters and public key as serialized when the pool was created
+    parameters_bytes: Vec<u8>,
+    public_key_bytes: Vec<u8>,
+    encrypt_parameters: Enc::PreparedParameters,
+    prepared_key: Enc::PreparedPublicKey,
+    randomness: Vec<Scalar>,
+    ciphertexts: Vec<Enc::Ciphertext>,
+}
+
+impl<Scalar, Enc> EncryptionOfZeroPool<Scalar, Enc>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+{
+    /// Empty pool of encryptions under `encrypt_parameters` and `public_key`
+    pub fn new(encrypt_parameters: &Enc::Parameters, public_key: &Enc::PublicKey) -> Self {
+        Self {
+            parameters_bytes: serialize(encrypt_parameters),
+            public_key_bytes: serialize(public_key),
+            encrypt_parameters: Enc::prepare_parameters(encrypt_parameters),
+            prepared_key: Enc::prepare_public_key(public_key),
+            randomness: Vec::new(),
+            ciphertexts: Vec::new(),
+        }
+    }
+
+    /// Whether the pool holds encryptions under `encrypt_parameters` and `public_key`. Both are
+    /// compared, as the same key used with another generator gives different encryptions.
+    pub fn is_for(
+        &self,
+        encrypt_parameters: &Enc::Parameters,
+        public_key: &Enc::PublicKey,
+    ) -> bool {
+        self.public_key_bytes == serialize(public_key)
+            && self.parameters_bytes == serialize(encrypt_parameters)
+    }
+
+    pub fn len(&self) -> usize {
+        self.randomness.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.randomness.is_empty()
+    }
+
+    /// Compute `count` more encryptions of zero
+    pub fn fill<R: CryptoRng + RngCore>(
+        &mut self,
+        rng: &mut R,
+        count: usize,
+    ) -> Result<(), CryptoError> {
+        // Reserve first so that growing the pool does not leave copies of its entries behind
+        self.randomness.reserve(count);
+        self.ciphertexts.reserve(count);
+
+        let randomness: Secret<Vec<Scalar>> = Secret::new(sample_vector(rng, count));
+        let ciphertexts = Secret::new(self.encrypt_zeros(randomness.expose())?);
+
+        self.randomness.extend_from_slice(randomness.expose());
+        self.ciphertexts.extend_from_slice(ciphertexts.expose());
+
+        Ok(())
+    }
+
+    /// Take `count` encryptions of zero out of the pool, together with the randomness needed as
+    /// witness to prove a remasking. Entries missing from the pool are computed on the spot.
+    #[allow(clippy::type_complexity)]
+    pub fn draw<R: CryptoRng + RngCore>(
+        &mut self,
+        rng: &mut R,
+        count: usize,
+    ) -> Result<(Secret<Vec<Scalar>>, Secret<Vec<Enc::Ciphertext>>), CryptoError> {
+        if self.len() < count {
+            self.fill(rng, count - self.len())?;
+        }
+
+        let start = self.len() - count;
+        let randomness = self.randomness[start..].to_vec();
+        let ciphertexts = self.ciphertexts[start..].to_vec();
+
+        // Wipe the drawn entries so they cannot be reused
+        self.randomness[start..]
+            .iter_mut()
+            .for_each(Zeroize::zeroize);
+        self.ciphertexts[start..]
+            .iter_mut()
+            .for_each(Zeroize::zeroize);
+        self.randomness.truncate(start);
+        self.ciphertexts.truncate(start);
+
+        Ok((Secret::new(randomness), Secret::new(ciphertexts)))
+    }
+
+    fn encrypt_zeros(&self, randomness: &[Scalar]) -> Result<Vec<Enc::Ciphertext>, CryptoError> {
+        let zero = Enc::Plaintext::zero();
+        cfg_iter!(randomness)
+            .map(|r| Enc::encrypt_prepared(&self.encrypt_parameters, &self.prepared_key, &zero, r))
+            .collect()
+    }
+}
+
+impl<Scalar, Enc> Drop for EncryptionOfZeroPool<Scalar, Enc>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+{
+    fn drop(&mut self) {
+        self.randomness.zeroize();
+        self.ciphertexts.zeroize();
+    }
+}
+
+fn serialize<T: CanonicalSerialize>(data: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(data.compressed_size());
+    data.serialize_compressed(&mut bytes)
+        .expect("failed to serialize public data");
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::homomorphic_encryption::el_gamal;
+    use ark_std::rand::thread_rng;
+
+    type Curve = ark_bn254::G1Projective;
+    type Scalar = ark_bn254::Fr;
+    type ElGamal = el_gamal::ElGamal<Curve>;
+    type Plaintext = el_gamal::Plaintext<Curve>;
+
+    #[test]
+    fn pool_test() {
+        let rng = &mut thread_rng();
+
+        let parameters = ElGamal::setup(rng).unwrap();
+        let other_parameters = ElGamal::setup(rng).unwrap();
+        let (pk, _) = ElGamal::keygen(&parameters, rng).unwrap();
+        let (other_pk, _) = ElGamal::keygen(&parameters, rng).unwrap();
+
+        let mut pool = EncryptionOfZeroPool::<Scalar, ElGamal>::new(&parameters, &pk);
+        assert!(pool.is_for(&parameters, &pk));
+        assert!(!pool.is_for(&parameters, &other_pk));
+        assert!(!pool.is_for(&other_parameters, &pk));
+
+        pool.fill(rng, 5).unwrap();
+        assert_eq!(pool.len(), 5);
+
+        // Draw more than the pool holds
+        let (randomness, ciphertexts) = pool.draw(rng, 8).unwrap();
+        assert!(pool.is_empty());
+        assert_eq!(randomness.expose().len(), 8);
+        for (r, ciphertext) in randomness.expose().iter().zip(ciphertexts.expose().iter()) {
+            assert_eq!(
+                ElGamal::encrypt(&parameters, &pk, &Plaintext::zero(), r),
+                Ok(*ciphertext)
+            );
+        }
+    }
+}
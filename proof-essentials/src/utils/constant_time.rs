@@ -0,0 +1,246 @@
+//! Data-independent implementations of the group operations applied to secrets. They are used in
+//! place of the default variable-time code paths when the `constant-time` feature is enabled.
+//!
+//! The guarantee is structural: the sequence of group operations and the memory accesses of these
+//! functions only depend on public sizes, with secret-dependent choices made by the conditional
+//! assignments of `subtle`. The group law itself is still the one provided by arkworks, whose
+//! field arithmetic is not audited for constant time, and the compiler is not prevented from
+//! reintroducing branches. With the feature enabled, the tests count the group operations and
+//! table reads made for secrets of different shapes and check that they do not change; timing
+//! must still be measured on the target platform, e.g. with a dudect-style harness.
+
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+const WINDOW_SIZE: usize = 4;
+const TABLE_SIZE: usize = 1 << WINDOW_SIZE;
+
+/// Multiply `base` by a secret `scalar`, using [`fixed_window_mul`] if the `constant-time`
+/// feature is enabled.
+pub fn mul_secret<C: CurveGroup>(base: &C::Affine, scalar: &C::ScalarField) -> C {
+    #[cfg(feature = "constant-time")]
+    {
+        fixed_window_mul::<C>(base, scalar)
+    }
+
+    #[cfg(not(feature = "constant-time"))]
+    {
+        use ark_ec::AffineRepr;
+        base.mul_bigint(scalar.into_bigint())
+    }
+}
+
+/// Scalar multiplication with a fixed window ladder: every window of the scalar costs the same
+/// number of doublings, one addition and a lookup that reads the whole precomputed table.
+pub fn fixed_window_mul<C: CurveGroup>(base: &C::Affine, scalar: &C::ScalarField) -> C {
+    fixed_window_mul_counted::<C>(base, scalar, &mut OpCount::default())
+}
+
+/// Return `output[i] = input[mapping[i]]` while reading every element of `input` for each output,
+/// so the memory access pattern does not depend on `mapping`.
+pub fn oblivious_permute<T: CanonicalSerialize + CanonicalDeserialize>(
+    mapping: &[usize],
+    input: &[T],
+) -> Vec<T> {
+    oblivious_permute_counted(mapping, input, &mut OpCount::default())
+}
+
+/// Number of elementary operations performed, used to check that they do not depend on secrets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct OpCount {
+    doublings: usize,
+    additions: usize,
+    table_reads: usize,
+}
+
+fn fixed_window_mul_counted<C: CurveGroup>(
+    base: &C::Affine,
+    scalar: &C::ScalarField,
+    ops: &mut OpCount,
+) -> C {
+    let mut multiples = Vec::with_capacity(TABLE_SIZE);
+    let mut multiple = C::zero();
+    for _ in 0..TABLE_SIZE {
+        multiples.push(multiple);
+        multiple += base;
+        ops.additions += 1;
+    }
+    let table = serialize_all(&C::normalize_batch(&multiples));
+
+    let bytes = scalar.into_bigint().to_bytes_le();
+    let num_of_windows = (C::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(WINDOW_SIZE);
+
+    let mut result = C::zero();
+    for window in (0..num_of_windows).rev() {
+        for _ in 0..WINDOW_SIZE {
+            result.double_in_place();
+            ops.doublings += 1;
+        }
+
+        let shift = (window * WINDOW_SIZE) % 8;
+        let digit = (bytes[window * WINDOW_SIZE / 8] >> shift) as usize & (TABLE_SIZE - 1);
+        let entry: C::Affine = lookup(&table, digit, ops);
+        result += entry;
+        ops.additions += 1;
+    }
+
+    result
+}
+
+fn oblivious_permute_counted<T: CanonicalSerialize + CanonicalDeserialize>(
+    mapping: &[usize],
+    input: &[T],
+    ops: &mut OpCount,
+) -> Vec<T> {
+    let table = serialize_all(input);
+
+    mapping
+        .iter()
+        .map(|&index| lookup(&table, index, ops))
+        .collect()
+}
+
+pub(crate) fn serialize_all<T: CanonicalSerialize>(elements: &[T]) -> Vec<Vec<u8>> {
+    elements
+        .iter()
+        .map(|element| {
+            let mut bytes = Vec::with_capacity(element.uncompressed_size());
+            element
+                .serialize_uncompressed(&mut bytes)
+                .expect("failed to serialize element");
+            bytes
+        })
+        .collect()
+}
+
+/// Read `table[index]` from a table built with [`serialize_all`], touching every entry
+#[cfg(feature = "constant-time")]
+pub(crate) fn select<T: CanonicalDeserialize>(table: &[Vec<u8>], index: usize) -> T {
+    lookup(table, index, &mut OpCount::default())
+}
+
+/// Read `table[index]` by conditionally copying every entry of the table
+fn lookup<T: CanonicalDeserialize>(table: &[Vec<u8>], index: usize, ops: &mut OpCount) -> T {
+    let mut selected = vec![0u8; table[0].len()];
+    for (i, entry) in table.iter().enumerate() {
+        let choice = (i as u64).ct_eq(&(index as u64));
+        for (byte, entry_byte) in selected.iter_mut().zip(entry.iter()) {
+            byte.conditional_assign(entry_byte, choice);
+        }
+        ops.table_reads += 1;
+    }
+
+    T::deserialize_uncompressed_unchecked(selected.as_slice())
+        .expect("failed to deserialize table entry")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::homomorphic_encryption::el_gamal;
+    use crate::utils::permutation::Permutation;
+    use crate::utils::rand::sample_vector;
+    use ark_ff::{Field, One, Zero};
+    use ark_std::{rand::thread_rng, UniformRand};
+    use std::ops::Mul;
+
+    type Curve = ark_bn254::G1Projective;
+    type Scalar = ark_bn254::Fr;
+    type Ciphertext = el_gamal::Ciphertext<Curve>;
+
+    /// Scalars with different bit patterns: zero, one, all the low bits set, the largest scalar
+    /// and random ones
+    fn scalars() -> Vec<Scalar> {
+        let rng = &mut thread_rng();
+        let low_bits =
+            Scalar::from(2u64).pow([Scalar::MODULUS_BIT_SIZE as u64 - 2]) - Scalar::one();
+        vec![
+            Scalar::zero(),
+            Scalar::one(),
+            low_bits,
+            -Scalar::one(),
+            Scalar::rand(rng),
+            Scalar::rand(rng),
+        ]
+    }
+
+    /// The identity and random permutations
+    fn permutations(size: usize) -> Vec<Permutation> {
+        let rng = &mut thread_rng();
+        vec![
+            Permutation::identity(size),
+            Permutation::new(rng, size),
+            Permutation::new(rng, size),
+        ]
+    }
+
+    #[test]
+    fn fixed_window_mul_test() {
+        let rng = &mut thread_rng();
+        let base = Curve::rand(rng).into_affine();
+
+        for scalar in scalars() {
+            assert_eq!(fixed_window_mul::<Curve>(&base, &scalar), base.mul(scalar));
+        }
+    }
+
+    #[test]
+    fn oblivious_permute_test() {
+        let rng = &mut thread_rng();
+        let size = 20;
+
+        let ciphers: Vec<Ciphertext> = sample_vector(rng, size);
+
+        for permutation in permutations(size) {
+            assert_eq!(
+                oblivious_permute(permutation.mapping(), &ciphers),
+                permutation.permute_array(&ciphers)
+            );
+        }
+    }
+
+    #[cfg(feature = "constant-time")]
+    #[test]
+    fn fixed_window_mul_op_count_test() {
+        let rng = &mut thread_rng();
+        let base = Curve::rand(rng).into_affine();
+
+        let counts = scalars()
+            .iter()
+            .map(|scalar| {
+                let mut ops = OpCount::default();
+                let _ = fixed_window_mul_counted::<Curve>(&base, scalar, &mut ops);
+                ops
+            })
+            .collect::<Vec<_>>();
+
+        let num_of_windows = (Scalar::MODULUS_BIT_SIZE as usize).div_ceil(WINDOW_SIZE);
+        assert!(counts.iter().all(|ops| *ops == counts[0]));
+        assert_eq!(counts[0].doublings, num_of_windows * WINDOW_SIZE);
+        assert_eq!(counts[0].additions, TABLE_SIZE + num_of_windows);
+        assert_eq!(counts[0].table_reads, num_of_windows * TABLE_SIZE);
+    }
+
+    #[cfg(feature = "constant-time")]
+    #[test]
+    fn oblivious_permute_op_count_test() {
+        let rng = &mut thread_rng();
+        let size = 20;
+
+        let ciphers: Vec<Ciphertext> = sample_vector(rng, size);
+
+        let counts = permutations(size)
+            .iter()
+            .map(|permutation| {
+                let mut ops = OpCount::default();
+                oblivious_permute_counted(permutation.mapping(), &ciphers, &mut ops);
+                ops
+            })
+            .collect::<Vec<_>>();
+
+        assert!(counts.iter().all(|ops| *ops == counts[0]));
+        assert_eq!(counts[0].table_reads, size * size);
+    }
+}
@@ -0,0 +1,120 @@
+//! Precomputed multiples of a point that is multiplied by many different scalars, such as the
+//! encryption generator or an aggregate public key.
+
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+
+const WINDOW_SIZE: usize = 4;
+const TABLE_SIZE: usize = 1 << WINDOW_SIZE;
+
+/// Table holding `digit * 2^(WINDOW_SIZE * window) * base` for every window of a scalar and every
+/// digit. A multiplication then costs one addition per window and no doubling.
+#[derive(Clone)]
+pub struct FixedBaseTable<C: CurveGroup> {
+    base: C::Affine,
+    #[cfg(not(feature = "constant-time"))]
+    windows: Vec<Vec<C::Affine>>,
+    /// Serialized entries, read with [`crate::utils::constant_time::select`]
+    #[cfg(feature = "constant-time")]
+    windows: Vec<Vec<Vec<u8>>>,
+}
+
+impl<C: CurveGroup> FixedBaseTable<C> {
+    pub fn new(base: &C::Affine) -> Self {
+        let num_of_windows = num_of_windows::<C>();
+
+        let mut multiples = Vec::with_capacity(num_of_windows * TABLE_SIZE);
+        let mut window_base: C = (*base).into();
+        for _ in 0..num_of_windows {
+            let mut multiple = C::zero();
+            for _ in 0..TABLE_SIZE {
+                multiples.push(multiple);
+                multiple += window_base;
+            }
+            window_base = multiple;
+        }
+
+        let affine = C::normalize_batch(&multiples);
+        let windows = affine
+            .chunks(TABLE_SIZE)
+            .map(|window| {
+                #[cfg(feature = "constant-time")]
+                {
+                    crate::utils::constant_time::serialize_all(window)
+                }
+
+                #[cfg(not(feature = "constant-time"))]
+                {
+                    window.to_vec()
+                }
+            })
+            .collect();
+
+        Self {
+            base: *base,
+            windows,
+        }
+    }
+
+    /// Point the table was computed for
+    pub fn base(&self) -> &C::Affine {
+        &self.base
+    }
+
+    /// Multiply the base by `scalar`. If the `constant-time` feature is enabled, every window
+    /// reads its whole row of the table so the access pattern does not depend on `scalar`.
+    pub fn mul(&self, scalar: &C::ScalarField) -> C {
+        let bytes = scalar.into_bigint().to_bytes_le();
+
+        let mut result = C::zero();
+        for (window, entries) in self.windows.iter().enumerate() {
+            let shift = (window * WINDOW_SIZE) % 8;
+            let digit = (bytes[window * WINDOW_SIZE / 8] >> shift) as usize & (TABLE_SIZE - 1);
+
+            #[cfg(feature = "constant-time")]
+            {
+                let entry: C::Affine = crate::utils::constant_time::select(entries, digit);
+                result += entry;
+            }
+
+            #[cfg(not(feature = "constant-time"))]
+            {
+                result += entries[digit];
+            }
+        }
+
+        result
+    }
+}
+
+fn num_of_windows<C: CurveGroup>() -> usize {
+    (C::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(WINDOW_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{One, Zero};
+    use ark_std::{rand::thread_rng, UniformRand};
+    use std::ops::Mul;
+
+    type Curve = ark_bn254::G1Projective;
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn fixed_base_mul_test() {
+        let rng = &mut thread_rng();
+        let base = Curve::rand(rng).into_affine();
+        let table = FixedBaseTable::<Curve>::new(&base);
+
+        for scalar in [
+            Scalar::zero(),
+            Scalar::one(),
+            -Scalar::one(),
+            Scalar::rand(rng),
+            Scalar::rand(rng),
+        ] {
+            assert_eq!(table.mul(&scalar), base.mul(scalar));
+        }
+    }
+}
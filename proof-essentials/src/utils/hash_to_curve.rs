@@ -0,0 +1,78 @@
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::CanonicalSerialize;
+use digest::Digest;
+
+/// Deterministically map `(domain, label, index)` to a curve point of the prime order subgroup
+/// using try-and-increment. Nobody knows the discrete logarithm of the output with respect to any
+/// other point, which makes it suitable for deriving independent bases from a public seed.
+pub fn hash_to_curve<C: CurveGroup, D: Digest>(
+    domain: &[u8],
+    label: &[u8],
+    index: u64,
+) -> C::Affine {
+    let point_size = C::Affine::generator().compressed_size();
+
+    let mut counter = 0u64;
+    loop {
+        let bytes = expand::<D>(domain, label, index, counter, point_size);
+        if let Some(point) = C::Affine::from_random_bytes(&bytes) {
+            let point = point.clear_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+        counter += 1;
+    }
+}
+
+/// Derive `len` bytes from the inputs by hashing them together with a block counter
+fn expand<D: Digest>(domain: &[u8], label: &[u8], index: u64, counter: u64, len: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(len);
+    let mut block = 0u64;
+    while bytes.len() < len {
+        let mut hasher = D::new();
+        hasher.update((domain.len() as u64).to_le_bytes());
+        hasher.update(domain);
+        hasher.update((label.len() as u64).to_le_bytes());
+        hasher.update(label);
+        hasher.update(index.to_le_bytes());
+        hasher.update(counter.to_le_bytes());
+        hasher.update(block.to_le_bytes());
+        bytes.extend_from_slice(&hasher.finalize());
+        block += 1;
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blake2::Blake2s256;
+
+    type Curve = ark_bn254::G1Projective;
+
+    #[test]
+    fn hash_to_curve_test() {
+        let point = hash_to_curve::<Curve, Blake2s256>(b"domain", b"label", 0);
+        assert!(point.is_on_curve());
+        assert!(point.is_in_correct_subgroup_assuming_on_curve());
+
+        assert_eq!(
+            point,
+            hash_to_curve::<Curve, Blake2s256>(b"domain", b"label", 0)
+        );
+        assert_ne!(
+            point,
+            hash_to_curve::<Curve, Blake2s256>(b"domain", b"label", 1)
+        );
+        assert_ne!(
+            point,
+            hash_to_curve::<Curve, Blake2s256>(b"domain", b"other", 0)
+        );
+        assert_ne!(
+            point,
+            hash_to_curve::<Curve, Blake2s256>(b"other", b"label", 0)
+        );
+    }
+}
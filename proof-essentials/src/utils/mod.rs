@@ -1,3 +1,7 @@
+pub mod constant_time;
+pub mod fixed_base;
+pub mod hash_to_curve;
 pub mod permutation;
 pub mod rand;
+pub mod secret;
 pub mod vector_arithmetic;
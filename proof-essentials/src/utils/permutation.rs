@@ -1,13 +1,21 @@
-use rand::{seq::SliceRandom, Rng};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{seq::SliceRandom, CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
-/// Represent a permutation pi as a vector such that for all indices i, vec(i) = pi(i)
+#[cfg(feature = "constant-time")]
+use crate::utils::constant_time::oblivious_permute;
+
+/// Represent a permutation pi as a vector such that for all indices i, vec(i) = pi(i).
+/// A permutation used to shuffle a deck is secret, so its mapping is wiped from memory on drop and
+/// only the crate can read it.
+#[derive(Clone)]
 pub struct Permutation {
-    pub mapping: Vec<usize>,
+    mapping: Vec<usize>,
     pub size: usize,
 }
 
 impl Permutation {
-    pub fn new<R: Rng>(rng: &mut R, size: usize) -> Self {
+    pub fn new<R: CryptoRng + RngCore>(rng: &mut R, size: usize) -> Self {
         let mut mapping: Vec<usize> = Vec::with_capacity(size);
         for i in 0..size {
             mapping.push(i);
@@ -16,7 +24,7 @@ impl Permutation {
         Self { mapping, size }
     }
 
-    pub fn from(permutation_vec: &Vec<usize>) -> Self {
+    pub fn from(permutation_vec: &[usize]) -> Self {
         Self {
             mapping: permutation_vec[..].to_vec(),
             size: permutation_vec.len(),
@@ -30,10 +38,46 @@ impl Permutation {
         }
     }
 
-    pub fn permute_array<T: Copy>(&self, input_vector: &Vec<T>) -> Vec<T> {
+    pub(crate) fn mapping(&self) -> &[usize] {
+        &self.mapping
+    }
+
+    pub fn permute_array<T: Copy>(&self, input_vector: &[T]) -> Vec<T> {
         self.mapping
             .iter()
             .map(|&pi_i| input_vector[pi_i])
             .collect::<Vec<T>>()
     }
+
+    /// Same as [`Permutation::permute_array`] when the permutation is secret. With the
+    /// `constant-time` feature, the memory access pattern does not depend on the permutation.
+    pub fn permute_secret_array<T: Copy + CanonicalSerialize + CanonicalDeserialize>(
+        &self,
+        input_vector: &[T],
+    ) -> Vec<T> {
+        #[cfg(feature = "constant-time")]
+        {
+            oblivious_permute(&self.mapping, input_vector)
+        }
+
+        #[cfg(not(feature = "constant-time"))]
+        {
+            self.permute_array(input_vector)
+        }
+    }
 }
+
+impl Zeroize for Permutation {
+    fn zeroize(&mut self) {
+        self.mapping.zeroize();
+        self.size.zeroize();
+    }
+}
+
+impl Drop for Permutation {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Permutation {}
@@ -22,6 +22,16 @@ pub struct FiatShamirRng<D: Digest> {
     digest: PhantomData<D>,
 }
 
+impl<D: Digest> Clone for FiatShamirRng<D> {
+    fn clone(&self) -> Self {
+        Self {
+            r: self.r.clone(),
+            seed: self.seed.clone(),
+            digest: PhantomData,
+        }
+    }
+}
+
 impl<D: Digest> RngCore for FiatShamirRng<D> {
     #[inline]
     fn next_u32(&mut self) -> u32 {
@@ -71,6 +81,15 @@ impl<D: Digest> FiatShamirRng<D> {
         let mut bytes = Vec::new();
         seed.serialize_with_mode(&mut bytes, Compress::Yes)
             .expect("failed to convert to bytes");
+        self.absorb_serialized(&bytes);
+    }
+
+    /// Same as [`FiatShamirRng::absorb`] for material that is already serialized in compressed
+    /// form, e.g. public data absorbed into many transcripts.
+    #[inline]
+    pub fn absorb_serialized(&mut self, serialized: &[u8]) {
+        let mut bytes = Vec::with_capacity(serialized.len() + self.seed.len());
+        bytes.extend_from_slice(serialized);
         bytes.extend_from_slice(&self.seed);
         self.seed = D::digest(&bytes);
         let mut seed = [0u8; 32];
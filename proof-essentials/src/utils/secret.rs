@@ -0,0 +1,75 @@
+use ark_std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Wrapper around secret material such as secret keys or masking factors. The wrapped value is
+/// wiped from memory on drop, is redacted when formatted and cannot be serialized.
+#[derive(Clone)]
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Access the secret value. Copies made from the returned reference are not wiped.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> ZeroizeOnDrop for Secret<T> {}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::{cell::Cell, rand::thread_rng, rc::Rc, UniformRand};
+
+    type Scalar = ark_bn254::Fr;
+
+    /// Records when it is wiped
+    struct Tracked(Rc<Cell<bool>>);
+
+    impl Zeroize for Tracked {
+        fn zeroize(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn secret_is_redacted() {
+        let rng = &mut thread_rng();
+        let value = Scalar::rand(rng);
+
+        let secret = Secret::new(vec![value; 3]);
+        assert_eq!(secret.expose(), &vec![value; 3]);
+        assert_eq!(format!("{:?}", secret), "Secret(<redacted>)");
+    }
+
+    #[test]
+    fn secret_is_wiped_on_drop() {
+        let wiped = Rc::new(Cell::new(false));
+        let secret = Secret::new(Tracked(wiped.clone()));
+        assert!(!wiped.get());
+
+        drop(secret);
+        assert!(wiped.get());
+    }
+}
@@ -4,7 +4,7 @@ use ark_std::iter::Sum;
 use ark_std::ops::Mul;
 
 /// Compute the dot product (inner product) of two vectors
-pub fn dot_product<S, T>(scalars: &Vec<S>, rhs: &Vec<T>) -> Result<T, CryptoError>
+pub fn dot_product<S, T>(scalars: &[S], rhs: &[T]) -> Result<T, CryptoError>
 where
     S: Field,
     T: Copy + Sum<T> + Mul<S, Output = T>,
@@ -21,7 +21,7 @@ where
 }
 
 // Compute the Hadamard product (elemet-wise multiplication) of two vectors
-pub fn hadamard_product<S: Field>(scalars: &Vec<S>, rhs: &Vec<S>) -> Result<Vec<S>, CryptoError> {
+pub fn hadamard_product<S: Field>(scalars: &[S], rhs: &[S]) -> Result<Vec<S>, CryptoError> {
     if scalars.len() != rhs.len() {
         return Err(CryptoError::HadamardProductLengthError(
             scalars.len(),
@@ -37,11 +37,7 @@ pub fn hadamard_product<S: Field>(scalars: &Vec<S>, rhs: &Vec<S>) -> Result<Vec<
 }
 
 /// Reshape a vector of length N into a matrix of m-by-n (m chunks of length n). Requires that N = m*n
-pub fn reshape<T: Clone>(
-    in_vector: &Vec<T>,
-    m: usize,
-    n: usize,
-) -> Result<Vec<Vec<T>>, CryptoError> {
+pub fn reshape<T: Clone>(in_vector: &[T], m: usize, n: usize) -> Result<Vec<Vec<T>>, CryptoError> {
     if in_vector.len() != m * n {
         return Err(CryptoError::VectorCastingError(in_vector.len(), m, n));
     }
@@ -9,7 +9,7 @@ use rand::Rng;
 /// Trait defining the types and functions needed for an additively homomorphic commitment scheme.
 /// The scheme is defined with respect to a finite field `F` for which scalar multiplication is preserved.
 pub trait HomomorphicCommitmentScheme<Scalar: Field> {
-    type CommitKey: Clone + CanonicalSerialize + CanonicalDeserialize;
+    type CommitKey: Clone + CanonicalSerialize + CanonicalDeserialize + Send + Sync;
 
     /// Represent a ciphertext from a generic homomorphic encryption scheme. To manifest the homomorphic
     /// property of the scheme, we require that some arithmetic operations (add and multiply by scalar) are implemented.
@@ -20,7 +20,9 @@ pub trait HomomorphicCommitmentScheme<Scalar: Field> {
         + CanonicalSerialize
         + CanonicalDeserialize
         + Zero
-        + Sum;
+        + Sum
+        + Send
+        + Sync;
 
     /// Generate a commit key using the provided length
     fn setup<R: Rng>(public_randomess: &mut R, len: usize) -> Self::CommitKey;
@@ -28,7 +30,7 @@ pub trait HomomorphicCommitmentScheme<Scalar: Field> {
     /// Commit to a vector of scalars using the commit key
     fn commit(
         commit_key: &Self::CommitKey,
-        x: &Vec<Scalar>,
+        x: &[Scalar],
         r: Scalar,
     ) -> Result<Self::Commitment, CryptoError>;
 }
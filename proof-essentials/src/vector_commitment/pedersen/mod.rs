@@ -24,6 +24,16 @@ impl<C: CurveGroup> CommitKey<C> {
     pub fn new(g: Vec<C::Affine>, h: C::Affine) -> Self {
         Self { g, h }
     }
+
+    /// Bases used to commit to the vector entries
+    pub fn g(&self) -> &[C::Affine] {
+        &self.g
+    }
+
+    /// Base used to commit to the blinding factor
+    pub fn h(&self) -> &C::Affine {
+        &self.h
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
@@ -44,7 +54,7 @@ impl<C: CurveGroup> HomomorphicCommitmentScheme<C::ScalarField> for PedersenComm
 
     fn commit(
         commit_key: &CommitKey<C>,
-        x: &Vec<C::ScalarField>,
+        x: &[C::ScalarField],
         r: C::ScalarField,
     ) -> Result<Self::Commitment, CryptoError> {
         if x.len() > commit_key.g.len() {
@@ -55,7 +65,7 @@ impl<C: CurveGroup> HomomorphicCommitmentScheme<C::ScalarField> for PedersenComm
             ));
         }
 
-        let scalars = [&[r], x.as_slice()]
+        let scalars = [&[r], x]
             .concat()
             .iter()
             .map(|x| x.into_bigint())
@@ -59,7 +59,7 @@ mod test {
 
         let commit_v1 = Pedersen::commit(&commit_key, &v1, r).unwrap();
 
-        let commit_s1 = Pedersen::commit(&commit_key, &vec![s1], r).unwrap();
+        let commit_s1 = Pedersen::commit(&commit_key, &[s1], r).unwrap();
 
         assert_eq!(v1[0], s1);
         assert_eq!(commit_v1, commit_s1);
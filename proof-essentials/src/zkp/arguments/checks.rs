@@ -0,0 +1,205 @@
+use crate::error::{Argument, Check, CryptoError};
+use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
+use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::multi_exponentiation;
+
+use ark_ff::Field;
+use ark_std::rand::RngCore;
+
+/// Opening equations `commitment == commit(values, randomness)` produced while verifying an
+/// argument. They are either checked as soon as they are produced, or multiplied by random weights
+/// and folded together so that all of them cost a single commitment in [`OpeningChecks::finalize`].
+/// Folding relies on the commitment scheme being homomorphic, with shorter vectors padded with
+/// zeros.
+pub(crate) struct OpeningChecks<'r, Scalar, Comm>
+where
+    Scalar: Field,
+    Comm: HomomorphicCommitmentScheme<Scalar>,
+{
+    weights: Option<&'r mut dyn RngCore>,
+    commitments: Vec<Comm::Commitment>,
+    values: Vec<Scalar>,
+    randomness: Scalar,
+}
+
+impl<'r, Scalar, Comm> OpeningChecks<'r, Scalar, Comm>
+where
+    Scalar: Field,
+    Comm: HomomorphicCommitmentScheme<Scalar>,
+{
+    /// Check every equation immediately
+    pub(crate) fn new() -> Self {
+        Self {
+            weights: None,
+            commitments: Vec::new(),
+            values: Vec::new(),
+            randomness: Scalar::zero(),
+        }
+    }
+
+    /// Defer every equation until [`OpeningChecks::finalize`], using weights sampled from `weights`
+    pub(crate) fn deferred(weights: &'r mut dyn RngCore) -> Self {
+        Self {
+            weights: Some(weights),
+            ..Self::new()
+        }
+    }
+
+    /// Fresh random weight for a deferred equation, `None` if equations are checked immediately
+    pub(crate) fn weight(&mut self) -> Option<Scalar> {
+        self.weights
+            .as_mut()
+            .map(|weights| Scalar::rand(&mut **weights))
+    }
+
+    pub(crate) fn check(
+        &mut self,
+        commit_key: &Comm::CommitKey,
+        argument: Argument,
+        name: &'static str,
+        commitment: Comm::Commitment,
+        values: &[Scalar],
+        randomness: Scalar,
+    ) -> Result<(), CryptoError> {
+        match self.weight() {
+            None => {
+                if commitment != Comm::commit(commit_key, values, randomness)? {
+                    return Err(CryptoError::failed_check(
+                        argument,
+                        Check::Opening { name, index: None },
+                    ));
+                }
+            }
+            Some(weight) => {
+                if self.values.len() < values.len() {
+                    self.values.resize(values.len(), Scalar::zero());
+                }
+                for (accumulated, value) in self.values.iter_mut().zip(values.iter()) {
+                    *accumulated += weight * value;
+                }
+                self.randomness += weight * randomness;
+                self.commitments.push(commitment * weight);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stop sampling weights, keeping the equations deferred so far
+    pub(crate) fn detach(self) -> OpeningChecks<'static, Scalar, Comm> {
+        OpeningChecks {
+            weights: None,
+            commitments: self.commitments,
+            values: self.values,
+            randomness: self.randomness,
+        }
+    }
+
+    /// Add the equations deferred by `other` to those of `self`
+    pub(crate) fn merge(&mut self, other: &OpeningChecks<Scalar, Comm>) {
+        if self.values.len() < other.values.len() {
+            self.values.resize(other.values.len(), Scalar::zero());
+        }
+        for (accumulated, value) in self.values.iter_mut().zip(other.values.iter()) {
+            *accumulated += value;
+        }
+        self.randomness += other.randomness;
+        self.commitments.extend_from_slice(&other.commitments);
+    }
+
+    /// Check all the deferred equations at once
+    pub(crate) fn finalize(self, commit_key: &Comm::CommitKey) -> Result<bool, CryptoError> {
+        if self.commitments.is_empty() {
+            return Ok(true);
+        }
+
+        let folded = Comm::commit(commit_key, &self.values, self.randomness)?;
+        Ok(self.commitments.into_iter().sum::<Comm::Commitment>() == folded)
+    }
+}
+
+/// Equations `left == encrypt(generator * message, randomness) + right` of the
+/// multi-exponentiation argument, checked immediately or folded like [`OpeningChecks`].
+pub(crate) struct EncryptionChecks<Scalar, Enc>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+{
+    left: Vec<Enc::ProjectiveCiphertext>,
+    right: Vec<Enc::ProjectiveCiphertext>,
+    message: Scalar,
+    randomness: Scalar,
+}
+
+impl<Scalar, Enc> EncryptionChecks<Scalar, Enc>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            left: Vec::new(),
+            right: Vec::new(),
+            message: Scalar::zero(),
+            randomness: Scalar::zero(),
+        }
+    }
+
+    /// Check the equation now if `weight` is `None`, otherwise defer it
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn check<Comm: HomomorphicCommitmentScheme<Scalar>>(
+        &mut self,
+        weight: Option<Scalar>,
+        parameters: &multi_exponentiation::Parameters<Scalar, Enc, Comm>,
+        name: &'static str,
+        left: Enc::ProjectiveCiphertext,
+        message: Scalar,
+        randomness: Scalar,
+        right: Enc::ProjectiveCiphertext,
+    ) -> Result<(), CryptoError> {
+        match weight {
+            None => {
+                let masking_cipher =
+                    parameters.encrypt(&(*parameters.generator * message), &randomness)?;
+                if left != Enc::ProjectiveCiphertext::from(masking_cipher) + right {
+                    return Err(CryptoError::failed_check(
+                        Argument::MultiExponentiation,
+                        Check::Mismatch { name, index: None },
+                    ));
+                }
+            }
+            Some(weight) => {
+                self.left.push(left * weight);
+                self.right.push(right * weight);
+                self.message += weight * message;
+                self.randomness += weight * randomness;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add the equations deferred by `other` to those of `self`
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.left.extend_from_slice(&other.left);
+        self.right.extend_from_slice(&other.right);
+        self.message += other.message;
+        self.randomness += other.randomness;
+    }
+
+    /// Check all the deferred equations at once
+    pub(crate) fn finalize<Comm: HomomorphicCommitmentScheme<Scalar>>(
+        self,
+        parameters: &multi_exponentiation::Parameters<Scalar, Enc, Comm>,
+    ) -> Result<bool, CryptoError> {
+        if self.left.is_empty() {
+            return Ok(true);
+        }
+
+        let masking_cipher =
+            parameters.encrypt(&(*parameters.generator * self.message), &self.randomness)?;
+        let right = Enc::ProjectiveCiphertext::from(masking_cipher)
+            + self.right.into_iter().sum::<Enc::ProjectiveCiphertext>();
+        Ok(self.left.into_iter().sum::<Enc::ProjectiveCiphertext>() == right)
+    }
+}
@@ -1,9 +1,10 @@
 use super::{Parameters, Statement};
 
-use crate::error::CryptoError;
+use crate::error::{Argument, Check, CryptoError};
 use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::checks::OpeningChecks;
+use crate::zkp::arguments::scalar_powers;
 use crate::zkp::arguments::{zero_value_bilinear_map, zero_value_bilinear_map::YMapping};
-use crate::zkp::{arguments::scalar_powers, ArgumentOfKnowledge};
 
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::{Field, Zero};
@@ -33,21 +34,56 @@ where
         proof_parameters: &Parameters<Scalar, Comm>,
         statement: &Statement<Scalar, Comm>,
         fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        self.verify_with(
+            proof_parameters,
+            statement,
+            fs_rng,
+            &mut OpeningChecks::new(),
+        )
+    }
+
+    /// Same as [`Proof::verify`], with the opening equations handled by `openings`
+    pub(crate) fn verify_with<D: Digest>(
+        &self,
+        proof_parameters: &Parameters<Scalar, Comm>,
+        statement: &Statement<Scalar, Comm>,
+        fs_rng: &mut FiatShamirRng<D>,
+        openings: &mut OpeningChecks<Scalar, Comm>,
     ) -> Result<(), CryptoError> {
         fs_rng.absorb(b"hadamard_product_argument");
 
+        if self.b_commits.len() != proof_parameters.m {
+            return Err(CryptoError::failed_check(
+                Argument::HadamardProduct,
+                Check::Length {
+                    name: "commitments to B",
+                    expected: proof_parameters.m,
+                    found: self.b_commits.len(),
+                },
+            ));
+        }
+
         // check c_b_1 = c_a_1
         if statement.commitment_to_a[0] != self.b_commits[0] {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Hadamard Product (5.1)",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::HadamardProduct,
+                Check::Mismatch {
+                    name: "commitments to B",
+                    index: Some(0),
+                },
+            ));
         }
 
         // check c_b_m = c_b
         if statement.commitment_to_b != self.b_commits[proof_parameters.m - 1] {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Hadamard Product (5.1)",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::HadamardProduct,
+                Check::Mismatch {
+                    name: "commitments to B",
+                    index: Some(proof_parameters.m - 1),
+                },
+            ));
         }
 
         // Public parameters
@@ -107,16 +143,7 @@ where
         let zero_arg_statement =
             zero_value_bilinear_map::Statement::new(&vec_commits_to_a, &c_d_i, &prover_mapping);
 
-        match zero_value_bilinear_map::ZeroValueArgument::verify(
-            &zero_arg_parameters,
-            &zero_arg_statement,
-            &self.zero_arg_proof,
-            fs_rng,
-        ) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(CryptoError::ProofVerificationError(String::from(
-                "Hadamard Product (5.1)",
-            ))),
-        }
+        self.zero_arg_proof
+            .verify_with(&zero_arg_parameters, &zero_arg_statement, fs_rng, openings)
     }
 }
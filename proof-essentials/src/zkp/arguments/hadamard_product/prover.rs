@@ -9,9 +9,12 @@ use crate::zkp::ArgumentOfKnowledge;
 
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::{Field, Zero};
-use ark_std::iter;
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{cfg_iter, iter};
 use digest::Digest;
-use rand::Rng;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 pub struct Prover<'a, Scalar, Comm>
 where
@@ -40,7 +43,7 @@ where
         }
     }
 
-    pub fn prove<R: Rng, D: Digest>(
+    pub fn prove<R: CryptoRng + RngCore, D: Digest>(
         &self,
         rng: &mut R,
         fs_rng: &mut FiatShamirRng<D>,
@@ -65,18 +68,16 @@ where
 
         let mut s: Vec<Scalar> = sample_vector(rng, self.parameters.m - 2);
 
-        let b_commit_middle = b
-            .iter()
-            .take(b.len() - 1)
-            .skip(1)
-            .zip(s.iter())
+        let commit_key = self.parameters.commit_key;
+        let b_commit_middle = cfg_iter!(b[1..b.len() - 1])
+            .zip(cfg_iter!(s))
             .map(|(b_i, &s_i)| -> Result<Comm::Commitment, CryptoError> {
-                Comm::commit(self.parameters.commit_key, b_i, s_i)
+                Comm::commit(commit_key, b_i, s_i)
             })
             .collect::<Result<Vec<_>, _>>()?;
 
         let b_commits = iter::once(self.statement.commitment_to_a[0])
-            .chain(b_commit_middle.into_iter())
+            .chain(b_commit_middle)
             .chain(iter::once(self.statement.commitment_to_b))
             .collect::<Vec<Comm::Commitment>>();
 
@@ -145,8 +146,8 @@ where
             .to_vec();
 
         let final_t = dot_product(
-            &x_challenge_powers[1..=self.parameters.m - 1].to_vec(),
-            &s[1..=self.parameters.m - 1].to_vec(),
+            &x_challenge_powers[1..=self.parameters.m - 1],
+            &s[1..=self.parameters.m - 1],
         )?;
 
         let vec_randoms_for_d = x_challenge_powers
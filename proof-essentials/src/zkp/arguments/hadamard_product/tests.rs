@@ -1,8 +1,7 @@
 #[cfg(test)]
-
 mod test {
 
-    use crate::error::CryptoError;
+    use crate::error::{Argument, Check, CryptoError};
     use crate::utils::rand::sample_vector;
     use crate::utils::vector_arithmetic::hadamard_product;
     use crate::vector_commitment::{pedersen, HomomorphicCommitmentScheme};
@@ -49,7 +48,7 @@ mod test {
             .collect::<Vec<_>>();
 
         let b = a_chunks.iter().fold(vec![Scalar::one(); n], |x, y| {
-            hadamard_product(&x, &y).unwrap()
+            hadamard_product(&x, y).unwrap()
         });
 
         let product = b.iter().fold(Scalar::one(), |x, y| x * y);
@@ -102,9 +101,13 @@ mod test {
 
         let mut fs_rng = FS::from_seed(b"Initialised with some input");
         assert_eq!(
-            Err(CryptoError::ProofVerificationError(String::from(
-                "Hadamard Product (5.1)",
-            ))),
+            Err(CryptoError::ArgumentVerificationError {
+                argument: Argument::ZeroValue,
+                check: Check::Opening {
+                    name: "commitment to B",
+                    index: None,
+                },
+            }),
             HadamardProductArgument::verify(
                 &proof_parameters,
                 &statement,
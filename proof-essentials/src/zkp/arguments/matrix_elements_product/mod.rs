@@ -7,7 +7,8 @@ use crate::utils::rand::FiatShamirRng;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
 use crate::zkp::ArgumentOfKnowledge;
 use ark_ff::Field;
-use ark_std::{marker::PhantomData, rand::Rng};
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
 pub struct ProductArgument<'a, F, Comm>
@@ -29,7 +30,7 @@ where
     type Witness = Witness<'a, Scalar>;
     type Proof = proof::Proof<Scalar, Comm>;
 
-    fn prove<R: Rng, D: Digest>(
+    fn prove<R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         common_reference_string: &Self::CommonReferenceString,
         statement: &Self::Statement,
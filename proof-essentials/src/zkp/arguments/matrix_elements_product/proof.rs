@@ -2,6 +2,7 @@ use super::{Parameters, Statement};
 
 use crate::error::CryptoError;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::checks::OpeningChecks;
 use crate::zkp::arguments::{hadamard_product, single_value_product};
 
 use crate::utils::rand::FiatShamirRng;
@@ -30,6 +31,22 @@ where
         proof_parameters: &Parameters<Scalar, Comm>,
         statement: &Statement<Scalar, Comm>,
         fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        self.verify_with(
+            proof_parameters,
+            statement,
+            fs_rng,
+            &mut OpeningChecks::new(),
+        )
+    }
+
+    /// Same as [`Proof::verify`], with the opening equations handled by `openings`
+    pub(crate) fn verify_with<D: Digest>(
+        &self,
+        proof_parameters: &Parameters<Scalar, Comm>,
+        statement: &Statement<Scalar, Comm>,
+        fs_rng: &mut FiatShamirRng<D>,
+        openings: &mut OpeningChecks<Scalar, Comm>,
     ) -> Result<(), CryptoError> {
         statement.is_valid(proof_parameters)?;
         fs_rng.absorb(b"matrix_elements_product");
@@ -44,10 +61,11 @@ where
         let hadamard_product_statement =
             hadamard_product::Statement::new(statement.commitments_to_a, self.b_commit);
 
-        self.hadamard_product_proof.verify(
+        self.hadamard_product_proof.verify_with(
             &hadamard_product_parameters,
             &hadamard_product_statement,
             fs_rng,
+            openings,
         )?;
         // verify single value product argument
         let single_value_product_parameters =
@@ -56,10 +74,11 @@ where
         let single_value_product_statement =
             single_value_product::Statement::new(&self.b_commit, statement.b);
 
-        self.single_value_proof.verify(
+        self.single_value_proof.verify_with(
             &single_value_product_parameters,
             &single_value_product_statement,
             fs_rng,
+            openings,
         )?;
 
         Ok(())
@@ -1,7 +1,6 @@
 use super::{proof::Proof, Parameters, Statement, Witness};
 
 use crate::error::CryptoError;
-use crate::utils::vector_arithmetic::hadamard_product as compute_hadamard_product;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
 use crate::zkp::{
     arguments::{hadamard_product, single_value_product},
@@ -10,9 +9,13 @@ use crate::zkp::{
 
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::Field;
-use ark_std::rand::Rng;
+use ark_std::cfg_into_iter;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 pub struct Prover<'a, Scalar, Comm>
 where
     Scalar: Field,
@@ -40,7 +43,7 @@ where
         }
     }
 
-    pub fn prove<R: Rng, D: Digest>(
+    pub fn prove<R: CryptoRng + RngCore, D: Digest>(
         &self,
         rng: &mut R,
         fs_rng: &mut FiatShamirRng<D>,
@@ -49,11 +52,17 @@ where
 
         let s = Scalar::rand(rng);
 
-        let mut product_along_rows = vec![Scalar::one(); self.parameters.n];
-        for x in self.witness.matrix_a {
-            product_along_rows = compute_hadamard_product(x, &product_along_rows)?;
+        let n = self.parameters.n;
+        let matrix_a = self.witness.matrix_a;
+        if let Some(row) = matrix_a.iter().find(|row| row.len() != n) {
+            return Err(CryptoError::HadamardProductLengthError(row.len(), n));
         }
 
+        // Columns are multiplied independently of each other
+        let product_along_rows = cfg_into_iter!(0..n)
+            .map(|j| matrix_a.iter().map(|row| row[j]).product())
+            .collect::<Vec<Scalar>>();
+
         let b_commit = Comm::commit(self.parameters.commit_key, &product_along_rows, s)?;
 
         // Engage in Hadamard Product Argument for b_commit and the `product_along_rows` as its witness:
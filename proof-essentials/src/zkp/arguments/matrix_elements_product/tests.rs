@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 mod test {
     use crate::utils::rand::sample_vector;
     use crate::vector_commitment::{pedersen, HomomorphicCommitmentScheme};
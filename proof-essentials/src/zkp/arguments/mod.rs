@@ -1,3 +1,4 @@
+mod checks;
 pub mod hadamard_product;
 pub mod matrix_elements_product;
 pub mod multi_exponentiation;
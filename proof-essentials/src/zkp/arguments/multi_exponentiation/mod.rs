@@ -8,7 +8,8 @@ use crate::utils::rand::FiatShamirRng;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
 use crate::zkp::ArgumentOfKnowledge;
 use ark_ff::Field;
-use ark_std::{marker::PhantomData, rand::Rng};
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
 pub struct MultiExponentiation<
@@ -33,7 +34,7 @@ where
     type Witness = Witness<'a, F>;
     type Proof = proof::Proof<F, Enc, Comm>;
 
-    fn prove<R: Rng, D: Digest>(
+    fn prove<R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         common_reference_string: &Self::CommonReferenceString,
         statement: &Self::Statement,
@@ -56,6 +57,19 @@ where
     }
 }
 
+/// How the prover computes the 2m diagonals of the matrix of dot products between the rows of
+/// exponents and the rows of ciphertexts. Both strategies produce the same proof.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DiagonalStrategy {
+    /// Compute every dot product of the matrix: m^2 multi-exponentiations of size n
+    #[default]
+    Direct,
+    /// Evaluate the polynomial whose coefficients are the diagonals at 2m small integers and
+    /// interpolate (Bayer-Groth, section 5.3): 2m multi-exponentiations of size n, the other
+    /// ciphertext exponentiations being by small integers. Faster for larger m.
+    Interpolation,
+}
+
 /// Parameters for the multi-exponentiation argument. Contains the encryption public key, a commitment key
 /// and a public group generator which will be used for masking.
 pub struct Parameters<'a, F, Enc, Comm>
@@ -68,6 +82,8 @@ where
     pub public_key: &'a Enc::PublicKey,
     pub commit_key: &'a Comm::CommitKey,
     pub generator: &'a Enc::Generator,
+    /// Tables for `encrypt_parameters` and `public_key`, see [`Parameters::with_prepared`]
+    pub prepared: Option<(&'a Enc::PreparedParameters, &'a Enc::PreparedPublicKey)>,
 }
 
 impl<'a, F, Enc, Comm> Parameters<'a, F, Enc, Comm>
@@ -87,6 +103,36 @@ where
             public_key,
             commit_key,
             generator,
+            prepared: None,
+        }
+    }
+
+    /// Use precomputed tables for the encryptions of the argument. They must have been prepared
+    /// from `encrypt_parameters` and `public_key`.
+    pub fn with_prepared(
+        mut self,
+        encrypt_parameters: &'a Enc::PreparedParameters,
+        public_key: &'a Enc::PreparedPublicKey,
+    ) -> Self {
+        self.prepared = Some((encrypt_parameters, public_key));
+        self
+    }
+
+    pub(crate) fn encrypt(
+        &self,
+        message: &Enc::Plaintext,
+        randomness: &F,
+    ) -> Result<Enc::Ciphertext, CryptoError> {
+        match self.prepared {
+            Some((encrypt_parameters, public_key)) => {
+                Enc::encrypt_prepared(encrypt_parameters, public_key, message, randomness)
+            }
+            None => Enc::encrypt(
+                self.encrypt_parameters,
+                self.public_key,
+                message,
+                randomness,
+            ),
         }
     }
 }
@@ -1,14 +1,15 @@
 use super::{Parameters, Statement};
 
-use crate::error::CryptoError;
+use crate::error::{Argument, Check, CryptoError};
 use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
 use crate::utils::rand::FiatShamirRng;
 use crate::utils::vector_arithmetic::dot_product;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::checks::{EncryptionChecks, OpeningChecks};
 use crate::zkp::arguments::scalar_powers;
 use digest::Digest;
 
-use ark_ff::{Field, Zero};
+use ark_ff::Field;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 #[derive(CanonicalDeserialize, CanonicalSerialize)]
@@ -42,6 +43,24 @@ where
         proof_parameters: &Parameters<Scalar, Enc, Comm>,
         statement: &Statement<Scalar, Enc, Comm>,
         fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        self.verify_with(
+            proof_parameters,
+            statement,
+            fs_rng,
+            &mut OpeningChecks::new(),
+            &mut EncryptionChecks::new(),
+        )
+    }
+
+    /// Same as [`Proof::verify`], with the opening equations handled by `openings`
+    pub(crate) fn verify_with<D: Digest>(
+        &self,
+        proof_parameters: &Parameters<Scalar, Enc, Comm>,
+        statement: &Statement<Scalar, Enc, Comm>,
+        fs_rng: &mut FiatShamirRng<D>,
+        openings: &mut OpeningChecks<Scalar, Comm>,
+        encryptions: &mut EncryptionChecks<Scalar, Enc>,
     ) -> Result<(), CryptoError> {
         let m = statement.shuffled_ciphers.len();
         let n = statement.shuffled_ciphers[0].len();
@@ -64,6 +83,22 @@ where
 
         let challenge = Scalar::rand(fs_rng);
 
+        for (name, found) in [
+            ("commitments to b_k", self.commit_b_k.len()),
+            ("ciphertexts E_k", self.vector_e_k.len()),
+        ] {
+            if found != 2 * m {
+                return Err(CryptoError::failed_check(
+                    Argument::MultiExponentiation,
+                    Check::Length {
+                        name,
+                        expected: 2 * m,
+                        found,
+                    },
+                ));
+            }
+        }
+
         // Precompute all powers of the challenge from 0 to number_of_diagonals
         let challenge_powers = scalar_powers(challenge, num_of_diagonals);
 
@@ -73,54 +108,51 @@ where
         let left = self.commit_b_k[m];
         let right = Comm::commit(
             proof_parameters.commit_key,
-            &vec![Scalar::zero()],
+            &[Scalar::zero()],
             Scalar::zero(),
         )?;
 
         if left != right {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Multi Exponentiation",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::MultiExponentiation,
+                Check::CommitmentToZero {
+                    name: "commitments to b_k",
+                    index: Some(m),
+                },
+            ));
         }
 
         if self.vector_e_k[m] != statement.product {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Multi Exponentiation",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::MultiExponentiation,
+                Check::Mismatch {
+                    name: "ciphertexts E_k",
+                    index: Some(m),
+                },
+            ));
         }
 
         let c_a_x = dot_product(&x_array, statement.commitments_to_exponents)?;
-        let verifier_commit_a =
-            Comm::commit(proof_parameters.commit_key, &self.a_blinded, self.r_blinded)?;
-
-        let left = c_a_x + self.a_0_commit;
-        if left != verifier_commit_a {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Multi Exponentiation",
-            )));
-        }
+        openings.check(
+            proof_parameters.commit_key,
+            Argument::MultiExponentiation,
+            "commitment to A",
+            c_a_x + self.a_0_commit,
+            &self.a_blinded,
+            self.r_blinded,
+        )?;
 
         let c_b_k = dot_product(&challenge_powers, &self.commit_b_k)?;
-        let verif_commit_b = Comm::commit(
+        openings.check(
             proof_parameters.commit_key,
-            &vec![self.b_blinded],
+            Argument::MultiExponentiation,
+            "commitments to b_k",
+            c_b_k,
+            &[self.b_blinded],
             self.s_blinded,
         )?;
-        if c_b_k != verif_commit_b {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Multi Exponentiation",
-            )));
-        }
-
-        let sum_e_k = dot_product(&challenge_powers, &self.vector_e_k)?;
 
-        let message = *proof_parameters.generator * self.b_blinded;
-        let aggregate_masking_cipher = Enc::encrypt(
-            proof_parameters.encrypt_parameters,
-            proof_parameters.public_key,
-            &message,
-            &self.tau_blinded,
-        )?;
+        let sum_e_k = Enc::dot_product_projective(&challenge_powers, &self.vector_e_k)?;
 
         /*
             c1 * x^m-1; x[m-1]
@@ -130,34 +162,27 @@ where
             cm * x^m-m; x[0]
         */
 
-        let verif_rhs: Result<Vec<Enc::Ciphertext>, CryptoError> = challenge_powers
+        // All the chunks are combined in a single dot product with the exponents x^m-i * a_vec
+        let exponents = challenge_powers
             .iter()
             .take(m)
             .rev()
-            .zip(statement.shuffled_ciphers.iter())
-            .map(
-                |(power_of_x, cipher_chunk)| -> Result<Enc::Ciphertext, CryptoError> {
-                    // x^m - i * a_vec
-                    let xm_minus_i_times_a = self
-                        .a_blinded
-                        .iter()
-                        .map(|element_of_a| *element_of_a * *power_of_x)
-                        .collect::<_>();
-                    let dot_p = dot_product(&xm_minus_i_times_a, cipher_chunk)?;
-                    Ok(dot_p)
-                },
-            )
-            .collect();
-
-        let verif_rhs = verif_rhs?
-            .iter()
-            .fold(Enc::Ciphertext::zero(), |acc, &x| acc + x);
-        if sum_e_k != aggregate_masking_cipher + verif_rhs {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Multi Exponentiation",
-            )));
-        }
-
-        Ok(())
+            .flat_map(|power_of_x| {
+                self.a_blinded
+                    .iter()
+                    .map(move |element_of_a| *element_of_a * power_of_x)
+            })
+            .collect::<Vec<_>>();
+        let verif_rhs =
+            Enc::dot_product_projective(&exponents, &statement.shuffled_ciphers.concat())?;
+        encryptions.check(
+            openings.weight(),
+            proof_parameters,
+            "ciphertexts E_k",
+            sum_e_k,
+            self.b_blinded,
+            self.tau_blinded,
+            verif_rhs,
+        )
     }
 }
@@ -1,5 +1,5 @@
 use super::proof::Proof;
-use super::{Parameters, Statement, Witness};
+use super::{DiagonalStrategy, Parameters, Statement, Witness};
 
 use crate::error::CryptoError;
 use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
@@ -10,9 +10,13 @@ use crate::zkp::arguments::scalar_powers;
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::{Field, Zero};
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{cfg_into_iter, cfg_iter};
 use digest::Digest;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 pub struct Prover<'a, Scalar, Enc, Comm>
 where
     Scalar: Field,
@@ -22,6 +26,7 @@ where
     parameters: &'a Parameters<'a, Scalar, Enc, Comm>,
     statement: &'a Statement<'a, Scalar, Enc, Comm>,
     witness: &'a Witness<'a, Scalar>,
+    diagonal_strategy: DiagonalStrategy,
     _encryption_scheme: PhantomData<Enc>,
     _commitment_scheme: PhantomData<Comm>,
 }
@@ -42,12 +47,19 @@ where
             parameters,
             statement,
             witness,
+            diagonal_strategy: DiagonalStrategy::default(),
             _encryption_scheme: PhantomData::<Enc>,
             _commitment_scheme: PhantomData::<Comm>,
         }
     }
 
-    pub fn prove<R: Rng, D: Digest>(
+    /// Choose how the diagonals are computed, [`DiagonalStrategy::Direct`] by default
+    pub fn with_diagonal_strategy(mut self, diagonal_strategy: DiagonalStrategy) -> Self {
+        self.diagonal_strategy = diagonal_strategy;
+        self
+    }
+
+    pub fn prove<R: CryptoRng + RngCore, D: Digest>(
         &self,
         rng: &mut R,
         fs_rng: &mut FiatShamirRng<D>,
@@ -80,35 +92,37 @@ where
 
         let a_0_commit = Comm::commit(self.parameters.commit_key, &a_0, r_0)?;
 
-        let commit_b_k = b
-            .iter()
-            .zip(s.iter())
+        let commit_key = self.parameters.commit_key;
+        let commit_b_k = cfg_iter!(b)
+            .zip(cfg_iter!(s))
             .map(|(&b_k, &s_k)| {
-                let commit = Comm::commit(self.parameters.commit_key, &vec![b_k], s_k)?;
+                let commit = Comm::commit(commit_key, &[b_k], s_k)?;
                 Ok(commit)
             })
             .collect::<Result<Vec<Comm::Commitment>, CryptoError>>()?;
 
-        let diagonals = Self::diagonals_from_chunks(
-            self.statement.shuffled_ciphers,
-            self.witness.matrix_a,
-            &a_0,
-        )
-        .unwrap();
+        let diagonals = match self.diagonal_strategy {
+            DiagonalStrategy::Direct => Self::diagonals_from_chunks(
+                self.statement.shuffled_ciphers,
+                self.witness.matrix_a,
+                &a_0,
+            )?,
+            DiagonalStrategy::Interpolation => Self::diagonals_by_interpolation(
+                self.statement.shuffled_ciphers,
+                self.witness.matrix_a,
+                &a_0,
+            )?,
+        };
 
-        let vector_e_k = b
-            .iter()
-            .zip(tau.iter())
-            .zip(diagonals.iter())
+        let parameters = self.parameters;
+        let generator = *self.parameters.generator;
+        let vector_e_k = cfg_iter!(b)
+            .zip(cfg_iter!(tau))
+            .zip(cfg_iter!(diagonals))
             .map(|((&b_k, tau_k), &d_k)| {
-                let message = *self.parameters.generator * b_k;
+                let message = generator * b_k;
 
-                let encrypted_random = Enc::encrypt(
-                    self.parameters.encrypt_parameters,
-                    self.parameters.public_key,
-                    &message,
-                    tau_k,
-                );
+                let encrypted_random = parameters.encrypt(&message, tau_k)?;
 
                 // let encrypted_random = DiscreteLogVTMF::<C>::mask(
                 //     encryption_parameters,
@@ -116,9 +130,10 @@ where
                 //     &self.parameters.masking_generator.mul(*b_k).into_affine(),
                 //     &Randomness::<C>(*tau_k),
                 // );
-                encrypted_random.unwrap() + d_k
+                Ok(Enc::ProjectiveCiphertext::from(encrypted_random) + d_k)
             })
-            .collect::<Vec<Enc::Ciphertext>>();
+            .collect::<Result<Vec<_>, CryptoError>>()?;
+        let vector_e_k = Enc::normalize_batch(&vector_e_k);
 
         fs_rng.absorb(&a_0_commit);
         fs_rng.absorb(&commit_b_k);
@@ -153,10 +168,10 @@ where
         // c0[3]x + c1[3]x^2 ... cm[3]x^m
         // ...
         // c0[n]x + c1[n]x^2 ... cm[n]x^m = b[n]
-        for i in 0..n {
-            let mut poly = a_0[i];
-            for j in 0..m {
-                poly += scalar_products_ax[j][i];
+        for (i, &a_0_i) in a_0.iter().enumerate().take(n) {
+            let mut poly = a_0_i;
+            for ax_j in scalar_products_ax.iter().take(m) {
+                poly += ax_j[i];
             }
             a_blinded.push(poly);
         }
@@ -184,60 +199,140 @@ where
     }
 
     fn diagonals_from_chunks(
-        cipher_chunks: &Vec<Vec<Enc::Ciphertext>>,
-        scalar_chunks: &Vec<Vec<Scalar>>,
-        a_0_randomness: &Vec<Scalar>,
-    ) -> Result<Vec<Enc::Ciphertext>, CryptoError> {
+        cipher_chunks: &[Vec<Enc::Ciphertext>],
+        scalar_chunks: &[Vec<Scalar>],
+        a_0_randomness: &[Scalar],
+    ) -> Result<Vec<Enc::ProjectiveCiphertext>, CryptoError> {
         let m = cipher_chunks.len();
         let num_of_diagonals = 2 * m - 1;
 
-        let mut diagonal_sums: Vec<Enc::Ciphertext> =
-            vec![Enc::Ciphertext::zero(); num_of_diagonals];
+        let mut diagonal_sums: Vec<Enc::ProjectiveCiphertext> =
+            vec![Enc::ProjectiveCiphertext::zero(); num_of_diagonals];
         let center = num_of_diagonals / 2_usize;
 
-        for d in 1..m {
-            let additional_randomness = dot_product(a_0_randomness, &cipher_chunks[d - 1])?;
-            let mut tmp_product1 = Enc::Ciphertext::zero();
-            let mut tmp_product2 = Enc::Ciphertext::zero();
-            for i in d..m {
-                let dot = dot_product(&scalar_chunks[i - d], &cipher_chunks[i])?;
-                tmp_product1 = tmp_product1 + dot;
-
-                let dot = dot_product(&scalar_chunks[i], &cipher_chunks[i - d])?;
-                tmp_product2 = tmp_product2 + dot;
-            }
+        // Each pair of diagonals on either side of the center is independent of the others
+        let off_center = cfg_into_iter!(1..m)
+            .map(|d| {
+                let additional_randomness =
+                    Enc::dot_product_projective(a_0_randomness, &cipher_chunks[d - 1])?;
+                let mut tmp_product1 = Enc::ProjectiveCiphertext::zero();
+                let mut tmp_product2 = Enc::ProjectiveCiphertext::zero();
+                for i in d..m {
+                    let dot =
+                        Enc::dot_product_projective(&scalar_chunks[i - d], &cipher_chunks[i])?;
+                    tmp_product1 = tmp_product1 + dot;
+
+                    let dot =
+                        Enc::dot_product_projective(&scalar_chunks[i], &cipher_chunks[i - d])?;
+                    tmp_product2 = tmp_product2 + dot;
+                }
+
+                Ok((tmp_product1 + additional_randomness, tmp_product2))
+            })
+            .collect::<Result<Vec<_>, CryptoError>>()?;
 
-            diagonal_sums[center - d] = tmp_product1 + additional_randomness;
-            diagonal_sums[center + d] = tmp_product2;
+        for (d, (lower, upper)) in (1..m).zip(off_center) {
+            diagonal_sums[center - d] = lower;
+            diagonal_sums[center + d] = upper;
         }
 
-        // let commit_b_k: Vec<_> = b
-        // .iter()
-        // .zip(s.iter())
-        // .map(|(&b_k, &s_k)| {
-        //     let commit = Comm::commit(self.parameters.commit_key, vec![b_k], s_k)?;
-        //     Ok(commit)
-        // }).collect()?;
-
-        let product: Result<Vec<Enc::Ciphertext>, CryptoError> = cipher_chunks
-            .iter()
-            .zip(scalar_chunks.iter())
-            .map(|(c_i, a_i)| {
-                let dot_p = dot_product(a_i, c_i)?;
-                Ok(dot_p)
-            })
-            .collect();
-
-        let product = product?
-            .iter()
-            .fold(Enc::Ciphertext::zero(), |acc, &x| acc + x);
-        // .sum();
+        let product =
+            Enc::dot_product_projective(&scalar_chunks.concat(), &cipher_chunks.concat())?;
 
         diagonal_sums[center] = product;
 
-        let zeroth_diagonal = dot_product(a_0_randomness, cipher_chunks.last().unwrap())?;
+        let zeroth_diagonal =
+            Enc::dot_product_projective(a_0_randomness, cipher_chunks.last().unwrap())?;
         diagonal_sums.insert(0, zeroth_diagonal);
 
         Ok(diagonal_sums)
     }
+
+    /// Same output as [`Prover::diagonals_from_chunks`]. With $a_0$ the random row and
+    /// $a_1, ..., a_m$ the rows of exponents, the diagonals $E_k$ are the coefficients of
+    /// $\sum_k E_k t^k = <\sum_j a_j t^j, \sum_i C_i t^{m-i}>$, which is evaluated at
+    /// $t = 0, ..., 2m-1$ before interpolating.
+    fn diagonals_by_interpolation(
+        cipher_chunks: &[Vec<Enc::Ciphertext>],
+        scalar_chunks: &[Vec<Scalar>],
+        a_0_randomness: &[Scalar],
+    ) -> Result<Vec<Enc::ProjectiveCiphertext>, CryptoError> {
+        let m = cipher_chunks.len();
+        let points = (0..2 * m)
+            .map(|t| Scalar::from(t as u64))
+            .collect::<Vec<_>>();
+
+        let evaluations = cfg_iter!(points)
+            .map(|&t| {
+                // Horner's method, the ciphertexts are only multiplied by t
+                let mut a_t = scalar_chunks[m - 1].clone();
+                let lower_rows = scalar_chunks.iter().rev().skip(1).map(Vec::as_slice);
+                for a_j in lower_rows.chain([a_0_randomness]) {
+                    for (acc, &a_j_l) in a_t.iter_mut().zip(a_j.iter()) {
+                        *acc = *acc * t + a_j_l;
+                    }
+                }
+
+                // Stay in projective form along the chain and normalise once at the end
+                let mut c_t = cipher_chunks[0]
+                    .iter()
+                    .map(|&c| Enc::ProjectiveCiphertext::from(c))
+                    .collect::<Vec<_>>();
+                for c_i in cipher_chunks.iter().skip(1) {
+                    for (acc, &c_i_l) in c_t.iter_mut().zip(c_i.iter()) {
+                        *acc = *acc * t + c_i_l.into();
+                    }
+                }
+
+                Enc::dot_product_projective(&a_t, &Enc::normalize_batch(&c_t))
+            })
+            .collect::<Result<Vec<_>, CryptoError>>()?;
+        let evaluations = Enc::normalize_batch(&evaluations);
+
+        let interpolation = interpolation_matrix(&points);
+        cfg_iter!(interpolation)
+            .map(|row| Enc::dot_product_projective(row, &evaluations))
+            .collect()
+    }
+}
+
+/// Inverse of the Vandermonde matrix of `points`: row k holds the coefficients of $x^k$ in the
+/// Lagrange polynomials of the points.
+fn interpolation_matrix<F: Field>(points: &[F]) -> Vec<Vec<F>> {
+    let size = points.len();
+
+    // Coefficients of prod (x - point), lowest degree first
+    let mut vanishing = vec![F::one()];
+    for point in points {
+        let mut next = vec![F::zero(); vanishing.len() + 1];
+        for (k, &coefficient) in vanishing.iter().enumerate() {
+            next[k + 1] += coefficient;
+            next[k] -= coefficient * point;
+        }
+        vanishing = next;
+    }
+
+    let mut matrix = vec![vec![F::zero(); size]; size];
+    for (u, &point) in points.iter().enumerate() {
+        // Divide the vanishing polynomial by (x - point)
+        let mut quotient = vec![F::zero(); size];
+        let mut carry = F::zero();
+        for k in (0..size).rev() {
+            carry = vanishing[k + 1] + carry * point;
+            quotient[k] = carry;
+        }
+
+        let denominator = quotient
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, &coefficient| acc * point + coefficient);
+        let inverse = denominator
+            .inverse()
+            .expect("interpolation points are distinct");
+        for (row, coefficient) in matrix.iter_mut().zip(quotient) {
+            row[u] = coefficient * inverse;
+        }
+    }
+
+    matrix
 }
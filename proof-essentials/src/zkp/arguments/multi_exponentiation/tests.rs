@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod test {
 
-    use crate::error::CryptoError;
+    use crate::error::{Argument, Check, CryptoError};
     use crate::homomorphic_encryption::{el_gamal, HomomorphicEncryptionScheme};
     use crate::utils::{
         rand::sample_vector,
@@ -12,9 +12,12 @@ mod test {
 
     use crate::utils::rand::FiatShamirRng;
     use ark_ff::Zero;
+    use ark_serialize::CanonicalSerialize;
     use ark_std::iter::Iterator;
-    use ark_std::{rand::thread_rng, UniformRand};
+    use ark_std::rand::{thread_rng, SeedableRng};
+    use ark_std::UniformRand;
     use blake2::Blake2s256;
+    use rand_chacha::ChaChaRng;
 
     // Choose ellitptic curve setting
     type Curve = ark_bn254::G1Projective;
@@ -29,6 +32,7 @@ mod test {
     type Witness<'a> = multi_exponentiation::Witness<'a, Scalar>;
     type Statement<'a> = multi_exponentiation::Statement<'a, Scalar, Enc, Comm>;
     type MultiExpArg<'a> = multi_exponentiation::MultiExponentiation<'a, Scalar, Enc, Comm>;
+    type Prover<'a> = multi_exponentiation::prover::Prover<'a, Scalar, Enc, Comm>;
     type FS = FiatShamirRng<Blake2s256>;
 
     #[test]
@@ -101,9 +105,74 @@ mod test {
         let mut fs_rng = FS::from_seed(b"Initialised with some input");
         assert_eq!(
             invalid_proof.verify(&parameters, &statement, &mut fs_rng),
-            Err(CryptoError::ProofVerificationError(String::from(
-                "Multi Exponentiation",
-            )))
+            Err(CryptoError::ArgumentVerificationError {
+                argument: Argument::MultiExponentiation,
+                check: Check::Mismatch {
+                    name: "ciphertexts E_k",
+                    index: Some(m),
+                },
+            })
         );
     }
+
+    #[test]
+    fn test_diagonal_strategies() {
+        let rng = &mut thread_rng();
+        let m = 6;
+        let n = 5;
+
+        let encrypt_parameters = Enc::setup(rng).unwrap();
+        let (pk, _) = Enc::keygen(&encrypt_parameters, rng).unwrap();
+        let commit_key = Comm::setup(rng, n);
+        let generator = Generator::rand(rng);
+        let parameters = multi_exponentiation::Parameters::new(
+            &encrypt_parameters,
+            &pk,
+            &commit_key,
+            &generator,
+        );
+
+        let ciphers: Vec<Ciphertext> = sample_vector(rng, m * n);
+        let exponents: Vec<Scalar> = sample_vector(rng, m * n);
+        let a_chunks = reshape(&exponents, m, n).unwrap();
+        let r: Vec<Scalar> = sample_vector(rng, m);
+        let rho = Scalar::rand(rng);
+        let witness = Witness::new(&a_chunks, &r, rho);
+
+        let c_chunks = reshape(&ciphers, m, n).unwrap();
+        let masking_term =
+            Enc::encrypt(&encrypt_parameters, &pk, &Plaintext::zero(), &rho).unwrap();
+        let grand_product = dot_product(&exponents, &ciphers).unwrap() + masking_term;
+        let c_a = a_chunks
+            .iter()
+            .zip(r.iter())
+            .map(|(a_chunk, random)| Comm::commit(&commit_key, a_chunk, *random).unwrap())
+            .collect::<Vec<_>>();
+        let statement = Statement::new(&c_chunks, grand_product, &c_a);
+
+        let proofs = [
+            multi_exponentiation::DiagonalStrategy::Direct,
+            multi_exponentiation::DiagonalStrategy::Interpolation,
+        ]
+        .map(|strategy| {
+            let mut fs_rng = FS::from_seed(b"Initialised with some input");
+            Prover::new(&parameters, &statement, &witness)
+                .with_diagonal_strategy(strategy)
+                .prove(&mut ChaChaRng::seed_from_u64(42), &mut fs_rng)
+                .unwrap()
+        });
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            proofs[1].verify(&parameters, &statement, &mut fs_rng),
+            Ok(())
+        );
+
+        let [direct, interpolated] = proofs.map(|proof| {
+            let mut data = Vec::with_capacity(proof.compressed_size());
+            proof.serialize_compressed(&mut data).unwrap();
+            data
+        });
+        assert_eq!(direct, interpolated);
+    }
 }
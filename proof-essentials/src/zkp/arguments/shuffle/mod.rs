@@ -1,17 +1,19 @@
 pub mod proof;
 pub mod prover;
 mod tests;
+pub mod verifier;
 
 use crate::error::CryptoError;
 use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
 use crate::utils::permutation::Permutation;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::multi_exponentiation;
 use crate::zkp::ArgumentOfKnowledge;
 
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::Field;
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
 pub struct ShuffleArgument<
@@ -36,7 +38,7 @@ where
     type Witness = Witness<'a, F>;
     type Proof = proof::Proof<F, Enc, Comm>;
 
-    fn prove<R: Rng, D: Digest>(
+    fn prove<R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         common_reference_string: &Self::CommonReferenceString,
         statement: &Self::Statement,
@@ -59,6 +61,27 @@ where
     }
 }
 
+impl<'a, F, Enc, Comm> ShuffleArgument<'a, F, Enc, Comm>
+where
+    F: Field,
+    Enc: HomomorphicEncryptionScheme<F>,
+    Comm: HomomorphicCommitmentScheme<F>,
+{
+    /// Verify many shuffles of `m * n` ciphertexts at once, see
+    /// [`verifier::PreparedShuffleVerifier::batch_verify`]. Keep a prepared verifier instead when
+    /// verifying several batches with the same parameters.
+    pub fn batch_verify<D: Digest>(
+        common_reference_string: &Parameters<'a, F, Enc, Comm>,
+        m: usize,
+        n: usize,
+        instances: &[Instance<F, Enc, Comm>],
+        fs_rng: &FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        verifier::PreparedShuffleVerifier::new(*common_reference_string, m, n)?
+            .batch_verify(instances, fs_rng)
+    }
+}
+
 /// Parameters for the product argument
 pub struct Parameters<'a, Scalar, Enc, Comm>
 where
@@ -70,6 +93,8 @@ where
     pub public_key: &'a Enc::PublicKey,
     pub commit_key: &'a Comm::CommitKey,
     pub generator: &'a Enc::Generator,
+    /// Tables for `encrypt_parameters` and `public_key`, see [`Parameters::with_prepared`]
+    pub prepared: Option<(&'a Enc::PreparedParameters, &'a Enc::PreparedPublicKey)>,
 }
 
 impl<'a, Scalar, Enc, Comm> Parameters<'a, Scalar, Enc, Comm>
@@ -89,18 +114,68 @@ where
             public_key,
             commit_key,
             generator,
+            prepared: None,
+        }
+    }
+
+    /// Use precomputed tables for the encryptions of the argument. They must have been prepared
+    /// from `encrypt_parameters` and `public_key`.
+    pub fn with_prepared(
+        mut self,
+        encrypt_parameters: &'a Enc::PreparedParameters,
+        public_key: &'a Enc::PreparedPublicKey,
+    ) -> Self {
+        self.prepared = Some((encrypt_parameters, public_key));
+        self
+    }
+
+    /// Parameters of the multi-exponentiation argument embedded in the shuffle argument
+    pub(crate) fn multi_exp_parameters(
+        &self,
+    ) -> multi_exponentiation::Parameters<'a, Scalar, Enc, Comm> {
+        let parameters = multi_exponentiation::Parameters::new(
+            self.encrypt_parameters,
+            self.public_key,
+            self.commit_key,
+            self.generator,
+        );
+
+        match self.prepared {
+            Some((encrypt_parameters, public_key)) => {
+                parameters.with_prepared(encrypt_parameters, public_key)
+            }
+            None => parameters,
         }
     }
 }
 
+impl<'a, Scalar, Enc, Comm> Clone for Parameters<'a, Scalar, Enc, Comm>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+    Comm: HomomorphicCommitmentScheme<Scalar>,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Scalar, Enc, Comm> Copy for Parameters<'a, Scalar, Enc, Comm>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+    Comm: HomomorphicCommitmentScheme<Scalar>,
+{
+}
+
 /// Statement of a shuffle. Contains the input ciphertexts, the output ciphertexts and the matrix dimensions
 pub struct Statement<'a, Scalar, Enc>
 where
     Scalar: Field,
     Enc: HomomorphicEncryptionScheme<Scalar>,
 {
-    pub input_ciphers: &'a Vec<Enc::Ciphertext>,
-    pub shuffled_ciphers: &'a Vec<Enc::Ciphertext>,
+    pub input_ciphers: &'a [Enc::Ciphertext],
+    pub shuffled_ciphers: &'a [Enc::Ciphertext],
     pub m: usize,
     pub n: usize,
 }
@@ -111,8 +186,8 @@ where
     Enc: HomomorphicEncryptionScheme<Scalar>,
 {
     pub fn new(
-        input_ciphers: &'a Vec<Enc::Ciphertext>,
-        shuffled_ciphers: &'a Vec<Enc::Ciphertext>,
+        input_ciphers: &'a [Enc::Ciphertext],
+        shuffled_ciphers: &'a [Enc::Ciphertext],
         m: usize,
         n: usize,
     ) -> Self {
@@ -136,14 +211,21 @@ where
     }
 }
 
+/// A pair of decks together with the proof that the second one is a shuffle of the first one
+pub type Instance<'a, Scalar, Enc, Comm> = (
+    &'a [<Enc as HomomorphicEncryptionScheme<Scalar>>::Ciphertext],
+    &'a [<Enc as HomomorphicEncryptionScheme<Scalar>>::Ciphertext],
+    &'a proof::Proof<Scalar, Enc, Comm>,
+);
+
 /// Witness
 pub struct Witness<'a, Scalar: Field> {
     pub permutation: &'a Permutation,
-    pub rho: &'a Vec<Scalar>,
+    pub rho: &'a [Scalar],
 }
 
 impl<'a, Scalar: Field> Witness<'a, Scalar> {
-    pub fn new(permutation: &'a Permutation, rho: &'a Vec<Scalar>) -> Self {
+    pub fn new(permutation: &'a Permutation, rho: &'a [Scalar]) -> Self {
         Self { permutation, rho }
     }
 }
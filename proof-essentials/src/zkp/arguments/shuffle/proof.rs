@@ -1,17 +1,22 @@
+use super::verifier::PreparedShuffleVerifier;
 use super::{Parameters, Statement};
 
-use crate::error::CryptoError;
+use crate::error::{Argument, Check, CryptoError};
 use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
-use crate::utils::vector_arithmetic::dot_product;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::checks::{EncryptionChecks, OpeningChecks};
 use crate::zkp::arguments::scalar_powers;
 use crate::zkp::arguments::{matrix_elements_product as product_argument, multi_exponentiation};
 
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::Field;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::cfg_iter;
 use digest::Digest;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 #[derive(CanonicalDeserialize, CanonicalSerialize)]
 pub struct Proof<Scalar, Enc, Comm>
 where
@@ -39,15 +44,57 @@ where
     ) -> Result<(), CryptoError> {
         statement.is_valid()?;
 
+        let verifier = PreparedShuffleVerifier::new(*proof_parameters, statement.m, statement.n)?;
+        self.verify_with(
+            &verifier,
+            statement,
+            fs_rng,
+            &mut OpeningChecks::new(),
+            &mut EncryptionChecks::new(),
+        )
+    }
+
+    /// Same as [`Proof::verify`] with the values cached by `verifier`, and the opening and
+    /// encryption equations handled by `openings` and `encryptions`
+    pub(crate) fn verify_with<D: Digest>(
+        &self,
+        verifier: &PreparedShuffleVerifier<Scalar, Enc, Comm>,
+        statement: &Statement<Scalar, Enc>,
+        fs_rng: &mut FiatShamirRng<D>,
+        openings: &mut OpeningChecks<Scalar, Comm>,
+        encryptions: &mut EncryptionChecks<Scalar, Enc>,
+    ) -> Result<(), CryptoError> {
+        statement.is_valid()?;
+        if statement.m != verifier.m || statement.n != verifier.n {
+            return Err(CryptoError::InvalidShuffleStatement);
+        }
+        let proof_parameters = &verifier.parameters;
+
+        for (name, found) in [
+            ("commitments to A", self.a_commits.len()),
+            ("commitments to B", self.b_commits.len()),
+        ] {
+            if found != statement.m {
+                return Err(CryptoError::failed_check(
+                    Argument::Shuffle,
+                    Check::Length {
+                        name,
+                        expected: statement.m,
+                        found,
+                    },
+                ));
+            }
+        }
+
         fs_rng.absorb(b"shuffle_argument");
 
         // Public data
-        fs_rng.absorb(proof_parameters.public_key);
-        fs_rng.absorb(proof_parameters.commit_key);
+        fs_rng.absorb_serialized(&verifier.public_key_bytes);
+        fs_rng.absorb_serialized(&verifier.commit_key_bytes);
 
         // statement
-        fs_rng.absorb(statement.input_ciphers);
-        fs_rng.absorb(statement.shuffled_ciphers);
+        fs_rng.absorb(&statement.input_ciphers);
+        fs_rng.absorb(&statement.shuffled_ciphers);
         fs_rng.absorb(&(statement.m as u32));
         fs_rng.absorb(&(statement.n as u32));
 
@@ -63,21 +110,18 @@ where
         let z = Scalar::rand(fs_rng);
 
         // PRODUCT ARGUMENT -------------------------------------------------------------
-        let z_vec = vec![-z; statement.n];
-        let zero = Scalar::zero();
-        let single_neg_z_commit = Comm::commit(proof_parameters.commit_key, &z_vec, zero)?;
-        let neg_z_commit = vec![single_neg_z_commit; statement.m];
+        let neg_z_commit = verifier.ones_commit * -z;
 
-        let c_d = self
-            .a_commits
-            .iter()
-            .zip(self.b_commits.iter())
+        let c_d = cfg_iter!(self.a_commits)
+            .zip(cfg_iter!(self.b_commits))
             .map(|(&a, &b)| a * y + b)
             .collect::<Vec<_>>();
 
-        let verifier_side_expected_product = (1..=statement.n * statement.m)
+        let verifier_side_expected_product = verifier
+            .indices
+            .iter()
             .zip(challenge_powers.iter())
-            .map(|(i, x_pow_i)| y * Scalar::from(i as u64) + x_pow_i - z)
+            .map(|(i, x_pow_i)| y * i + x_pow_i - z)
             .product();
 
         let product_argument_parameters = product_argument::Parameters::new(
@@ -88,25 +132,20 @@ where
 
         let commitments_to_a = c_d
             .iter()
-            .zip(neg_z_commit.iter())
-            .map(|(&d_commit, &z_commit)| d_commit + z_commit)
+            .map(|&d_commit| d_commit + neg_z_commit)
             .collect::<Vec<_>>();
         let product_argument_statement =
             product_argument::Statement::new(&commitments_to_a, verifier_side_expected_product);
 
-        self.product_argument_proof.verify(
+        self.product_argument_proof.verify_with(
             &product_argument_parameters,
             &product_argument_statement,
             fs_rng,
+            openings,
         )?;
 
         // MULTI-EXPONENTIATION ARGUMENT -------------------------------------------------------
-        let multi_exp_parameters = multi_exponentiation::Parameters::new(
-            proof_parameters.encrypt_parameters,
-            proof_parameters.public_key,
-            proof_parameters.commit_key,
-            proof_parameters.generator,
-        );
+        let multi_exp_parameters = proof_parameters.multi_exp_parameters();
 
         let shuffled_chunks = statement
             .shuffled_ciphers
@@ -114,13 +153,18 @@ where
             .map(|c| c.to_vec())
             .collect::<Vec<_>>();
 
-        let product = dot_product(&challenge_powers, statement.input_ciphers).unwrap();
+        let product = Enc::dot_product(&challenge_powers, statement.input_ciphers)?;
 
         let multi_exp_statement =
             multi_exponentiation::Statement::new(&shuffled_chunks, product, &self.b_commits);
 
-        self.multi_exp_proof
-            .verify(&multi_exp_parameters, &multi_exp_statement, fs_rng)?;
+        self.multi_exp_proof.verify_with(
+            &multi_exp_parameters,
+            &multi_exp_statement,
+            fs_rng,
+            openings,
+            encryptions,
+        )?;
 
         Ok(())
     }
@@ -2,17 +2,22 @@ use super::{proof::Proof, Parameters, Statement, Witness};
 
 use crate::error::CryptoError;
 use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
+use crate::utils::permutation::Permutation;
 use crate::utils::rand::sample_vector;
+use crate::utils::secret::Secret;
 use crate::utils::vector_arithmetic::{dot_product, reshape};
 use crate::vector_commitment::HomomorphicCommitmentScheme;
 use crate::zkp::arguments::scalar_powers;
 use crate::zkp::arguments::{matrix_elements_product as product_argument, multi_exponentiation};
-use crate::zkp::ArgumentOfKnowledge;
 
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::{Field, Zero};
+use ark_std::cfg_iter;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
-use rand::Rng;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 pub struct Prover<'a, Scalar, Enc, Comm>
 where
@@ -23,6 +28,24 @@ where
     parameters: &'a Parameters<'a, Scalar, Enc, Comm>,
     statement: &'a Statement<'a, Scalar, Enc>,
     witness: &'a Witness<'a, Scalar>,
+    diagonal_strategy: multi_exponentiation::DiagonalStrategy,
+    precomputation: Option<Precomputation<Scalar, Comm>>,
+}
+
+/// Part of the proof that only depends on the permutation: the permuted indices and the
+/// commitments to them. It can be computed with [`Prover::precompute`] before the deck to shuffle
+/// is known.
+pub struct Precomputation<Scalar, Comm>
+where
+    Scalar: Field,
+    Comm: HomomorphicCommitmentScheme<Scalar>,
+{
+    permutation: Permutation,
+    m: usize,
+    n: usize,
+    a_chunks: Secret<Vec<Vec<Scalar>>>,
+    r: Secret<Vec<Scalar>>,
+    a_commits: Vec<Comm::Commitment>,
 }
 
 impl<'a, Scalar, Enc, Comm> Prover<'a, Scalar, Enc, Comm>
@@ -41,39 +64,100 @@ where
             parameters,
             statement,
             witness,
+            diagonal_strategy: multi_exponentiation::DiagonalStrategy::default(),
+            precomputation: None,
         }
     }
 
-    pub fn prove<R: Rng, D: Digest>(
-        &self,
+    /// Commit to the permutation of an `m` by `n` deck. This is the only part of the proof that
+    /// does not depend on the deck, so it can be done while waiting for it.
+    pub fn precompute<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        parameters: &Parameters<'a, Scalar, Enc, Comm>,
+        permutation: &Permutation,
+        m: usize,
+        n: usize,
+    ) -> Result<Precomputation<Scalar, Comm>, CryptoError> {
+        let r: Vec<Scalar> = sample_vector(rng, m);
+
+        let index = (1..=m * n)
+            .map(|x| Scalar::from(x as u64))
+            .collect::<Vec<_>>();
+
+        let a = Secret::new(permutation.permute_secret_array(&index));
+
+        let a_chunks = reshape(a.expose(), m, n)?;
+
+        let commit_key = parameters.commit_key;
+        let a_commits = cfg_iter!(a_chunks)
+            .zip(cfg_iter!(r))
+            .map(|(chunk, &r)| Comm::commit(commit_key, chunk, r))
+            .collect::<Result<Vec<_>, CryptoError>>()?;
+
+        Ok(Precomputation {
+            permutation: permutation.clone(),
+            m,
+            n,
+            a_chunks: Secret::new(a_chunks),
+            r: Secret::new(r),
+            a_commits,
+        })
+    }
+
+    /// Use a [`Precomputation`] made for the permutation of the witness. It is consumed by the
+    /// proof, as proofs sharing a precomputation can be linked.
+    pub fn with_precomputation(mut self, precomputation: Precomputation<Scalar, Comm>) -> Self {
+        self.precomputation = Some(precomputation);
+        self
+    }
+
+    /// Choose how the multi-exponentiation argument computes its diagonals
+    pub fn with_diagonal_strategy(
+        mut self,
+        diagonal_strategy: multi_exponentiation::DiagonalStrategy,
+    ) -> Self {
+        self.diagonal_strategy = diagonal_strategy;
+        self
+    }
+
+    pub fn prove<R: CryptoRng + RngCore, D: Digest>(
+        mut self,
         rng: &mut R,
         fs_rng: &mut FiatShamirRng<D>,
     ) -> Result<Proof<Scalar, Enc, Comm>, CryptoError> {
         fs_rng.absorb(b"shuffle_argument");
 
-        let r: Vec<Scalar> = sample_vector(rng, self.statement.m);
-
-        let index = (1..=self.statement.m * self.statement.n)
-            .map(|x| Scalar::from(x as u64))
-            .collect::<Vec<_>>();
+        let precomputation = match self.precomputation.take() {
+            Some(precomputation) => precomputation,
+            None => Self::precompute(
+                rng,
+                self.parameters,
+                self.witness.permutation,
+                self.statement.m,
+                self.statement.n,
+            )?,
+        };
 
-        let a = self.witness.permutation.permute_array(&index);
+        if precomputation.m != self.statement.m
+            || precomputation.n != self.statement.n
+            || precomputation.permutation.mapping() != self.witness.permutation.mapping()
+        {
+            return Err(CryptoError::InvalidPrecomputation);
+        }
 
-        let a_chunks = reshape(&a, self.statement.m, self.statement.n)?;
+        let r = precomputation.r.expose();
+        let a = precomputation.a_chunks.expose().concat();
+        let a_commits = precomputation.a_commits.clone();
 
-        let a_commits = a_chunks
-            .iter()
-            .zip(r.iter())
-            .map(|(chunk, &r)| Comm::commit(self.parameters.commit_key, chunk, r))
-            .collect::<Result<Vec<_>, CryptoError>>()?;
+        let commit_key = self.parameters.commit_key;
 
         // Public data
         fs_rng.absorb(self.parameters.public_key);
         fs_rng.absorb(self.parameters.commit_key);
 
         // statement
-        fs_rng.absorb(self.statement.input_ciphers);
-        fs_rng.absorb(self.statement.shuffled_ciphers);
+        fs_rng.absorb(&self.statement.input_ciphers);
+        fs_rng.absorb(&self.statement.shuffled_ciphers);
         fs_rng.absorb(&(self.statement.m as u32));
         fs_rng.absorb(&(self.statement.n as u32));
 
@@ -83,7 +167,10 @@ where
 
         let challenge_powers = scalar_powers(x, self.witness.permutation.size)[1..].to_vec();
 
-        let b = self.witness.permutation.permute_array(&challenge_powers);
+        let b = self
+            .witness
+            .permutation
+            .permute_secret_array(&challenge_powers);
         let s: Vec<Scalar> = sample_vector(rng, self.statement.m);
 
         let b_chunks = b
@@ -91,10 +178,9 @@ where
             .map(|c| c.to_vec())
             .collect::<Vec<_>>();
 
-        let b_commits = b_chunks
-            .iter()
-            .zip(s.iter())
-            .map(|(b, &s)| Comm::commit(self.parameters.commit_key, b, s))
+        let b_commits = cfg_iter!(b_chunks)
+            .zip(cfg_iter!(s))
+            .map(|(b, &s)| Comm::commit(commit_key, b, s))
             .collect::<Result<Vec<_>, CryptoError>>()?;
 
         //round 2
@@ -126,10 +212,9 @@ where
             .map(|c| c.to_vec())
             .collect::<Vec<_>>();
 
-        let d_minus_z_commits = d_minus_z_chunks
-            .iter()
-            .zip(t.iter())
-            .map(|(chunk, &random)| Comm::commit(self.parameters.commit_key, chunk, random))
+        let d_minus_z_commits = cfg_iter!(d_minus_z_chunks)
+            .zip(cfg_iter!(t))
+            .map(|(chunk, &random)| Comm::commit(commit_key, chunk, random))
             .collect::<Result<Vec<_>, CryptoError>>()?;
 
         let claimed_product = d_minus_z.iter().product();
@@ -148,26 +233,16 @@ where
         let product_argument_proof = product_argument_prover.prove(rng, fs_rng)?;
 
         // Engage in multi-exponentation argument ----------------------------------------------------------
-        let multi_exp_parameters = multi_exponentiation::Parameters::new(
-            self.parameters.encrypt_parameters,
-            self.parameters.public_key,
-            self.parameters.commit_key,
-            self.parameters.generator,
-        );
+        let multi_exp_parameters = self.parameters.multi_exp_parameters();
 
         let minus_rho_witness = self.witness.rho.iter().map(|&x| -x).collect::<Vec<_>>();
         let rho = dot_product(&minus_rho_witness, &b)?;
 
-        let temp = dot_product(&b, self.statement.shuffled_ciphers)?;
+        let temp = Enc::dot_product_projective(&b, self.statement.shuffled_ciphers)?;
         let zero_cipher = Enc::Plaintext::zero();
-        let masking_cipher = Enc::encrypt(
-            self.parameters.encrypt_parameters,
-            self.parameters.public_key,
-            &zero_cipher,
-            &rho,
-        )?;
+        let masking_cipher = multi_exp_parameters.encrypt(&zero_cipher, &rho)?;
 
-        let product = temp + masking_cipher;
+        let product = Enc::normalize_batch(&[temp + masking_cipher.into()])[0];
 
         let shuffled_chunks = self
             .statement
@@ -181,13 +256,13 @@ where
 
         let multi_exp_witness = multi_exponentiation::Witness::new(&b_chunks, &s, rho);
 
-        let multi_exp_proof = multi_exponentiation::MultiExponentiation::prove(
-            rng,
+        let multi_exp_proof = multi_exponentiation::prover::Prover::new(
             &multi_exp_parameters,
             &multi_exp_statement,
             &multi_exp_witness,
-            fs_rng,
-        )?;
+        )
+        .with_diagonal_strategy(self.diagonal_strategy)
+        .prove(rng, fs_rng)?;
 
         // Produce proof
         let proof = Proof {
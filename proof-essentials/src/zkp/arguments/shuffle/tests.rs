@@ -1,6 +1,6 @@
 #[cfg(test)]
-
 mod test {
+    use crate::error::{Argument, Check, CryptoError};
     use crate::homomorphic_encryption::{el_gamal, HomomorphicEncryptionScheme};
     use crate::utils::permutation::Permutation;
     use crate::utils::rand::sample_vector;
@@ -9,9 +9,12 @@ mod test {
 
     use crate::utils::rand::FiatShamirRng;
     use ark_ff::Zero;
+    use ark_serialize::CanonicalSerialize;
     use ark_std::iter::Iterator;
-    use ark_std::{rand::thread_rng, UniformRand};
+    use ark_std::rand::{thread_rng, CryptoRng, RngCore, SeedableRng};
+    use ark_std::UniformRand;
     use blake2::Blake2s256;
+    use rand_chacha::ChaChaRng;
 
     // Choose ellitptic curve setting
     type Curve = ark_bn254::G1Projective;
@@ -27,9 +30,51 @@ mod test {
     type Statement<'a> = shuffle::Statement<'a, Scalar, Enc>;
     type ShuffleArgument<'a> = shuffle::ShuffleArgument<'a, Scalar, Enc, Comm>;
     type Parameters<'a> = shuffle::Parameters<'a, Scalar, Enc, Comm>;
+    type Prover<'a> = shuffle::prover::Prover<'a, Scalar, Enc, Comm>;
+    type PreparedShuffleVerifier<'a> =
+        shuffle::verifier::PreparedShuffleVerifier<'a, Scalar, Enc, Comm>;
+
+    type Proof = shuffle::proof::Proof<Scalar, Enc, Comm>;
 
     type FS = FiatShamirRng<Blake2s256>;
 
+    fn shuffle_and_prove<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        parameters: &Parameters,
+        ciphers: &[Ciphertext],
+        m: usize,
+        n: usize,
+    ) -> (Vec<Ciphertext>, Proof) {
+        let masking_factors: Vec<Scalar> = sample_vector(rng, m * n);
+        let permutation = Permutation::new(rng, m * n);
+
+        let shuffled_deck = permutation
+            .permute_array(ciphers)
+            .iter()
+            .zip(masking_factors.iter())
+            .map(|(&cipher, masking_factor)| {
+                let masking_cipher = Enc::encrypt(
+                    parameters.encrypt_parameters,
+                    parameters.public_key,
+                    &Plaintext::zero(),
+                    masking_factor,
+                )
+                .unwrap();
+
+                cipher + masking_cipher
+            })
+            .collect::<Vec<_>>();
+
+        let statement = Statement::new(ciphers, &shuffled_deck, m, n);
+        let witness = Witness::new(&permutation, &masking_factors);
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let proof =
+            ShuffleArgument::prove(rng, parameters, &statement, &witness, &mut fs_rng).unwrap();
+
+        (shuffled_deck, proof)
+    }
+
     #[test]
     fn test_shuffle_argument() {
         let m = 4;
@@ -91,5 +136,236 @@ mod test {
             Ok(()),
             ShuffleArgument::verify(&parameters, &statement, &invalid_proof, &mut fs_rng)
         );
+
+        let mut malformed_proof = valid_proof;
+        malformed_proof.b_commits.pop();
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            Err(CryptoError::ArgumentVerificationError {
+                argument: Argument::Shuffle,
+                check: Check::Length {
+                    name: "commitments to B",
+                    expected: m,
+                    found: m - 1,
+                },
+            }),
+            ShuffleArgument::verify(&parameters, &statement, &malformed_proof, &mut fs_rng)
+        );
+    }
+
+    #[test]
+    fn test_prepared_verifier() {
+        let m = 4;
+        let n = 13;
+
+        let rng = &mut thread_rng();
+
+        let encrypt_parameters = Enc::setup(rng).unwrap();
+        let (pk, _) = Enc::keygen(&encrypt_parameters, rng).unwrap();
+        let commit_key = Comm::setup(rng, n);
+        let generator = Generator::rand(rng);
+        let parameters = Parameters::new(&encrypt_parameters, &pk, &commit_key, &generator);
+
+        let verifier = PreparedShuffleVerifier::new(parameters, m, n).unwrap();
+
+        // The same verifier checks several shuffles
+        let mut deck: Vec<Ciphertext> = sample_vector(rng, m * n);
+        for _ in 0..3 {
+            let (shuffled_deck, proof) = shuffle_and_prove(rng, &parameters, &deck, m, n);
+
+            let mut fs_rng = FS::from_seed(b"Initialised with some input");
+            assert_eq!(
+                verifier.verify(&deck, &shuffled_deck, &proof, &mut fs_rng),
+                Ok(())
+            );
+
+            let wrong_output: Vec<Ciphertext> = sample_vector(rng, m * n);
+            let mut fs_rng = FS::from_seed(b"Initialised with some input");
+            assert!(verifier
+                .verify(&deck, &wrong_output, &proof, &mut fs_rng)
+                .is_err());
+
+            deck = shuffled_deck;
+        }
+
+        // Decks that do not have the dimensions of the verifier are rejected
+        let (shuffled_deck, proof) = shuffle_and_prove(rng, &parameters, &deck, m, n);
+        let other_verifier = PreparedShuffleVerifier::new(parameters, 2 * m, n / 2).unwrap();
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            other_verifier.verify(&deck, &shuffled_deck, &proof, &mut fs_rng),
+            Err(CryptoError::InvalidShuffleStatement)
+        );
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let m = 4;
+        let n = 13;
+        let num_of_shuffles = 3;
+
+        let rng = &mut thread_rng();
+
+        let encrypt_parameters = Enc::setup(rng).unwrap();
+        let (pk, _) = Enc::keygen(&encrypt_parameters, rng).unwrap();
+        let commit_key = Comm::setup(rng, n);
+        let generator = Generator::rand(rng);
+        let parameters = Parameters::new(&encrypt_parameters, &pk, &commit_key, &generator);
+
+        let mut decks: Vec<Vec<Ciphertext>> = vec![sample_vector(rng, m * n)];
+        let mut proofs = Vec::with_capacity(num_of_shuffles);
+        for i in 0..num_of_shuffles {
+            let (shuffled_deck, proof) = shuffle_and_prove(rng, &parameters, &decks[i], m, n);
+            decks.push(shuffled_deck);
+            proofs.push(proof);
+        }
+
+        let fs_rng = FS::from_seed(b"Initialised with some input");
+        let batch_verify = |proofs: &[Proof]| {
+            let instances = decks
+                .windows(2)
+                .zip(proofs.iter())
+                .map(|(decks, proof)| (decks[0].as_slice(), decks[1].as_slice(), proof))
+                .collect::<Vec<_>>();
+
+            ShuffleArgument::batch_verify(&parameters, m, n, &instances, &fs_rng)
+        };
+
+        assert_eq!(batch_verify(&proofs), Ok(()));
+
+        // Only detected once the equations of the batch are folded
+        proofs[1].multi_exp_proof.tau_blinded += Scalar::from(1u64);
+        assert_eq!(
+            batch_verify(&proofs),
+            Err(CryptoError::InvalidBatchProof {
+                index: 1,
+                source: Box::new(CryptoError::ArgumentVerificationError {
+                    argument: Argument::MultiExponentiation,
+                    check: Check::Mismatch {
+                        name: "ciphertexts E_k",
+                        index: None,
+                    },
+                }),
+            })
+        );
+
+        // Detected while going through the proofs
+        proofs[2].b_commits.pop();
+        assert_eq!(
+            batch_verify(&proofs),
+            Err(CryptoError::InvalidBatchProof {
+                index: 2,
+                source: Box::new(CryptoError::ArgumentVerificationError {
+                    argument: Argument::Shuffle,
+                    check: Check::Length {
+                        name: "commitments to B",
+                        expected: m,
+                        found: m - 1,
+                    },
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn test_deterministic_proof() {
+        let m = 4;
+        let n = 13;
+
+        let rng = &mut ChaChaRng::seed_from_u64(0);
+        let encrypt_parameters = Enc::setup(rng).unwrap();
+        let (pk, _) = Enc::keygen(&encrypt_parameters, rng).unwrap();
+        let commit_key = Comm::setup(rng, n);
+        let generator = Generator::rand(rng);
+        let parameters = Parameters::new(&encrypt_parameters, &pk, &commit_key, &generator);
+        let ciphers: Vec<Ciphertext> = sample_vector(rng, m * n);
+
+        // Parallel and sequential provers consume the same randomness in the same order, so a
+        // given seed always produces the same proof whatever the thread scheduling
+        let [first, second] = [(); 2].map(|_| {
+            let rng = &mut ChaChaRng::seed_from_u64(42);
+            let (shuffled_deck, proof) = shuffle_and_prove(rng, &parameters, &ciphers, m, n);
+
+            let mut data = Vec::new();
+            shuffled_deck.serialize_compressed(&mut data).unwrap();
+            proof.serialize_compressed(&mut data).unwrap();
+            data
+        });
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_precomputation() {
+        let m = 4;
+        let n = 13;
+
+        let rng = &mut thread_rng();
+        let encrypt_parameters = Enc::setup(rng).unwrap();
+        let (pk, _) = Enc::keygen(&encrypt_parameters, rng).unwrap();
+        let commit_key = Comm::setup(rng, n);
+        let generator = Generator::rand(rng);
+        let parameters = Parameters::new(&encrypt_parameters, &pk, &commit_key, &generator);
+
+        // Offline: only the permutation and the masking factors are known
+        let permutation = Permutation::new(rng, m * n);
+        let masking_factors: Vec<Scalar> = sample_vector(rng, m * n);
+        let seed = 42;
+        let prover_rng = &mut ChaChaRng::seed_from_u64(seed);
+        let precomputation =
+            Prover::precompute(prover_rng, &parameters, &permutation, m, n).unwrap();
+
+        // Online: the deck arrives
+        let ciphers: Vec<Ciphertext> = sample_vector(rng, m * n);
+        let shuffled_deck = permutation
+            .permute_array(&ciphers)
+            .iter()
+            .zip(masking_factors.iter())
+            .map(|(&cipher, masking_factor)| {
+                cipher
+                    + Enc::encrypt(&encrypt_parameters, &pk, &Plaintext::zero(), masking_factor)
+                        .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let statement = Statement::new(&ciphers, &shuffled_deck, m, n);
+        let witness = Witness::new(&permutation, &masking_factors);
+
+        let precomputed = Prover::new(&parameters, &statement, &witness)
+            .with_precomputation(precomputation)
+            .prove(
+                prover_rng,
+                &mut FS::from_seed(b"Initialised with some input"),
+            )
+            .unwrap();
+        // Same randomness as proving in one go
+        let direct = Prover::new(&parameters, &statement, &witness)
+            .prove(
+                &mut ChaChaRng::seed_from_u64(seed),
+                &mut FS::from_seed(b"Initialised with some input"),
+            )
+            .unwrap();
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            ShuffleArgument::verify(&parameters, &statement, &precomputed, &mut fs_rng),
+            Ok(())
+        );
+        let [precomputed, direct] = [precomputed, direct].map(|proof| {
+            let mut data = Vec::new();
+            proof.serialize_compressed(&mut data).unwrap();
+            data
+        });
+        assert_eq!(precomputed, direct);
+
+        let precomputation = Prover::precompute(rng, &parameters, &permutation, m, n).unwrap();
+        let other_permutation = Permutation::new(rng, m * n);
+        let other_witness = Witness::new(&other_permutation, &masking_factors);
+        assert!(matches!(
+            Prover::new(&parameters, &statement, &other_witness)
+                .with_precomputation(precomputation)
+                .prove(rng, &mut FS::from_seed(b"Initialised with some input")),
+            Err(CryptoError::InvalidPrecomputation)
+        ));
     }
 }
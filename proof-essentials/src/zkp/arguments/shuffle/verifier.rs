@@ -0,0 +1,169 @@
+use super::{proof::Proof, Instance, Parameters, Statement};
+
+use crate::error::CryptoError;
+use crate::homomorphic_encryption::HomomorphicEncryptionScheme;
+use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::checks::{EncryptionChecks, OpeningChecks};
+
+use crate::utils::rand::FiatShamirRng;
+use ark_ff::Field;
+use ark_serialize::CanonicalSerialize;
+use digest::Digest;
+
+/// Verifier for shuffles of `m * n` ciphertexts under fixed parameters. The values that only
+/// depend on the parameters are computed once, so a verifier built at the start of a game can
+/// check all of its shuffles.
+pub struct PreparedShuffleVerifier<'a, Scalar, Enc, Comm>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+    Comm: HomomorphicCommitmentScheme<Scalar>,
+{
+    pub(crate) parameters: Parameters<'a, Scalar, Enc, Comm>,
+    pub(crate) m: usize,
+    pub(crate) n: usize,
+    /// Public key and commit key as absorbed into the transcript
+    pub(crate) public_key_bytes: Vec<u8>,
+    pub(crate) commit_key_bytes: Vec<u8>,
+    /// Commitment to a row of ones, so that committing to a row of `-z` is a single multiplication
+    pub(crate) ones_commit: Comm::Commitment,
+    /// Position `i` of every ciphertext in the deck, from 1 to `m * n`
+    pub(crate) indices: Vec<Scalar>,
+}
+
+impl<'a, Scalar, Enc, Comm> PreparedShuffleVerifier<'a, Scalar, Enc, Comm>
+where
+    Scalar: Field,
+    Enc: HomomorphicEncryptionScheme<Scalar>,
+    Comm: HomomorphicCommitmentScheme<Scalar>,
+{
+    pub fn new(
+        parameters: Parameters<'a, Scalar, Enc, Comm>,
+        m: usize,
+        n: usize,
+    ) -> Result<Self, CryptoError> {
+        let ones_commit = Comm::commit(
+            parameters.commit_key,
+            &vec![Scalar::one(); n],
+            Scalar::zero(),
+        )?;
+        let indices = (1..=m * n).map(|i| Scalar::from(i as u64)).collect();
+
+        Ok(Self {
+            parameters,
+            m,
+            n,
+            public_key_bytes: serialize(parameters.public_key),
+            commit_key_bytes: serialize(parameters.commit_key),
+            ones_commit,
+            indices,
+        })
+    }
+
+    pub fn parameters(&self) -> &Parameters<'a, Scalar, Enc, Comm> {
+        &self.parameters
+    }
+
+    /// Verify that `shuffled_ciphers` is a shuffle of `input_ciphers`
+    pub fn verify<D: Digest>(
+        &self,
+        input_ciphers: &[Enc::Ciphertext],
+        shuffled_ciphers: &[Enc::Ciphertext],
+        proof: &Proof<Scalar, Enc, Comm>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        let statement = Statement::new(input_ciphers, shuffled_ciphers, self.m, self.n);
+        proof.verify_with(
+            self,
+            &statement,
+            fs_rng,
+            &mut OpeningChecks::new(),
+            &mut EncryptionChecks::new(),
+        )
+    }
+
+    /// Verify several shuffle proofs, for example the successive shuffles of a deck. Each proof is
+    /// checked against its own copy of `fs_rng`. The opening and multi-exponentiation equations of
+    /// all the proofs are folded with random weights derived from the whole batch, so they are
+    /// checked with a single commitment and a single encryption. If the folded equations do not
+    /// hold, those of each proof are checked separately and the error points to the first invalid
+    /// proof.
+    pub fn batch_verify<D: Digest>(
+        &self,
+        instances: &[Instance<Scalar, Enc, Comm>],
+        fs_rng: &FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        let blame = |index: usize| {
+            move |source| CryptoError::InvalidBatchProof {
+                index,
+                source: Box::new(source),
+            }
+        };
+
+        let mut weights = FiatShamirRng::<D>::from_seed(b"shuffle_batch");
+        for (input_ciphers, shuffled_ciphers, proof) in instances {
+            weights.absorb(input_ciphers);
+            weights.absorb(shuffled_ciphers);
+            weights.absorb(*proof);
+        }
+
+        // The equations of each proof are kept apart to find the invalid one if the batch fails
+        let mut deferred = Vec::with_capacity(instances.len());
+        for (index, (input_ciphers, shuffled_ciphers, proof)) in instances.iter().enumerate() {
+            let statement = Statement::new(input_ciphers, shuffled_ciphers, self.m, self.n);
+            let mut openings = OpeningChecks::deferred(&mut weights);
+            let mut encryptions = EncryptionChecks::new();
+            proof
+                .verify_with(
+                    self,
+                    &statement,
+                    &mut fs_rng.clone(),
+                    &mut openings,
+                    &mut encryptions,
+                )
+                .map_err(blame(index))?;
+            deferred.push((openings.detach(), encryptions));
+        }
+
+        let mut openings = OpeningChecks::new();
+        let mut encryptions = EncryptionChecks::new();
+        for (proof_openings, proof_encryptions) in &deferred {
+            openings.merge(proof_openings);
+            encryptions.merge(proof_encryptions);
+        }
+
+        let multi_exp_parameters = self.parameters.multi_exp_parameters();
+        if openings.finalize(self.parameters.commit_key)?
+            && encryptions.finalize(&multi_exp_parameters)?
+        {
+            return Ok(());
+        }
+
+        // The folded equations of the batch are the sums of those of its proofs, so at least one
+        // proof has folded equations that do not hold. If all the others do, the last one is blamed.
+        let mut invalid = instances.len() - 1;
+        for (index, (openings, encryptions)) in deferred.into_iter().enumerate() {
+            if !(openings.finalize(self.parameters.commit_key)?
+                && encryptions.finalize(&multi_exp_parameters)?)
+            {
+                invalid = index;
+                break;
+            }
+        }
+
+        // Checking the invalid proof alone tells which of its equations does not hold
+        let (input_ciphers, shuffled_ciphers, proof) = instances[invalid];
+        let source = self
+            .verify(input_ciphers, shuffled_ciphers, proof, &mut fs_rng.clone())
+            .err()
+            .unwrap_or_else(|| CryptoError::ProofVerificationError(String::from("Shuffle")));
+        Err(blame(invalid)(source))
+    }
+}
+
+fn serialize<T: CanonicalSerialize>(data: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(data.compressed_size());
+    data.serialize_compressed(&mut bytes)
+        .expect("failed to serialize public data");
+    bytes
+}
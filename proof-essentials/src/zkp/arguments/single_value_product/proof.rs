@@ -1,7 +1,8 @@
 use super::{Parameters, Statement};
 
-use crate::error::CryptoError;
+use crate::error::{Argument, Check, CryptoError};
 use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::checks::OpeningChecks;
 
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::Field;
@@ -36,21 +37,51 @@ where
         proof_parameters: &Parameters<Scalar, Comm>,
         statement: &Statement<Scalar, Comm>,
         fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        self.verify_with(
+            proof_parameters,
+            statement,
+            fs_rng,
+            &mut OpeningChecks::new(),
+        )
+    }
+
+    /// Same as [`Proof::verify`], with the opening equations handled by `openings`
+    pub(crate) fn verify_with<D: Digest>(
+        &self,
+        proof_parameters: &Parameters<Scalar, Comm>,
+        statement: &Statement<Scalar, Comm>,
+        fs_rng: &mut FiatShamirRng<D>,
+        openings: &mut OpeningChecks<Scalar, Comm>,
     ) -> Result<(), CryptoError> {
         if self.b_blinded.len() != proof_parameters.n {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Single Value Product Argument (5.3)",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::SingleValueProduct,
+                Check::Length {
+                    name: "blinded b",
+                    expected: proof_parameters.n,
+                    found: self.b_blinded.len(),
+                },
+            ));
         }
         if self.a_blinded.len() != proof_parameters.n {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Single Value Product Argument (5.3)",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::SingleValueProduct,
+                Check::Length {
+                    name: "blinded a",
+                    expected: proof_parameters.n,
+                    found: self.a_blinded.len(),
+                },
+            ));
         }
         if self.b_blinded[0] != self.a_blinded[0] {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Single Value Product Argument (5.3)",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::SingleValueProduct,
+                Check::Mismatch {
+                    name: "blinded b",
+                    index: Some(0),
+                },
+            ));
         }
 
         fs_rng.absorb(b"single_value_product_argument");
@@ -67,20 +98,26 @@ where
         let x = Scalar::rand(fs_rng);
 
         if self.b_blinded[proof_parameters.n - 1] != x * statement.b {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Single Value Product Argument (5.3)",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::SingleValueProduct,
+                Check::Mismatch {
+                    name: "blinded b",
+                    index: Some(proof_parameters.n - 1),
+                },
+            ));
         }
 
         // verify that blinded a is correctly formed
         // let left = statement.a_commit.mul(x.into_repr()) + self.d_commit;
         let left = *statement.a_commit * x + self.d_commit;
-        let right = Comm::commit(proof_parameters.commit_key, &self.a_blinded, self.r_blinded)?;
-        if left != right {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Single Value Product Argument (5.3)",
-            )));
-        }
+        openings.check(
+            proof_parameters.commit_key,
+            Argument::SingleValueProduct,
+            "commitment to a",
+            left,
+            &self.a_blinded,
+            self.r_blinded,
+        )?;
 
         //verify that diffs are correctly formed
         // let left = self.diff_commit.mul(x.into_repr()) + self.delta_commit;
@@ -94,13 +131,13 @@ where
             .map(|((&b, &b_minus_one), &a)| x * b - b_minus_one * a)
             .collect::<Vec<_>>();
 
-        let right = Comm::commit(proof_parameters.commit_key, &blinded_diffs, self.s_blinded)?;
-        if left != right {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Single Value Product Argument (5.3)",
-            )));
-        }
-
-        Ok(())
+        openings.check(
+            proof_parameters.commit_key,
+            Argument::SingleValueProduct,
+            "commitment to the differences",
+            left,
+            &blinded_diffs,
+            self.s_blinded,
+        )
     }
 }
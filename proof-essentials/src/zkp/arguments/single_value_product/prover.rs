@@ -8,7 +8,7 @@ use crate::vector_commitment::HomomorphicCommitmentScheme;
 use crate::utils::rand::FiatShamirRng;
 use ark_ff::Field;
 use ark_std::iter;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
 pub struct Prover<'a, Scalar, Comm>
@@ -38,7 +38,7 @@ where
         }
     }
 
-    pub fn prove<R: Rng, D: Digest>(
+    pub fn prove<R: CryptoRng + RngCore, D: Digest>(
         &self,
         rng: &mut R,
         fs_rng: &mut FiatShamirRng<D>,
@@ -135,7 +135,7 @@ where
         Ok(proof)
     }
 
-    fn blind(x: &Vec<Scalar>, blinders: &Vec<Scalar>, challenge: Scalar) -> Vec<Scalar> {
+    fn blind(x: &[Scalar], blinders: &[Scalar], challenge: Scalar) -> Vec<Scalar> {
         let blinded = x
             .iter()
             .zip(blinders.iter())
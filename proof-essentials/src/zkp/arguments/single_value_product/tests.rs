@@ -1,7 +1,6 @@
 #[cfg(test)]
-
 mod test {
-    use crate::error::CryptoError;
+    use crate::error::{Argument, Check, CryptoError};
     use crate::utils::rand::sample_vector;
     use crate::vector_commitment::{pedersen, HomomorphicCommitmentScheme};
     use crate::zkp::{arguments::single_value_product, ArgumentOfKnowledge};
@@ -60,9 +59,13 @@ mod test {
 
         let mut fs_rng = FS::from_seed(b"Initialised with some input");
         assert_eq!(
-            Err(CryptoError::ProofVerificationError(String::from(
-                "Single Value Product Argument (5.3)",
-            ))),
+            Err(CryptoError::ArgumentVerificationError {
+                argument: Argument::SingleValueProduct,
+                check: Check::Mismatch {
+                    name: "blinded b",
+                    index: Some(n - 1),
+                },
+            }),
             SingleValueProd::verify(&parameters, &statement, &invalid_proof, &mut fs_rng)
         );
     }
@@ -7,7 +7,8 @@ use crate::utils::rand::FiatShamirRng;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
 use crate::zkp::{arguments::scalar_powers, ArgumentOfKnowledge};
 use ark_ff::Field;
-use ark_std::{marker::PhantomData, rand::Rng};
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
 pub struct ZeroValueArgument<'a, F, Comm>
@@ -29,7 +30,7 @@ where
     type Witness = Witness<'a, Scalar>;
     type Proof = proof::Proof<Scalar, Comm>;
 
-    fn prove<R: Rng, D: Digest>(
+    fn prove<R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         common_reference_string: &Self::CommonReferenceString,
         statement: &Self::Statement,
@@ -135,7 +136,7 @@ where
 }
 
 pub trait BilinearMap<Scalar: Field> {
-    fn compute_mapping(&self, a: &Vec<Scalar>, b: &Vec<Scalar>) -> Result<Scalar, CryptoError>;
+    fn compute_mapping(&self, a: &[Scalar], b: &[Scalar]) -> Result<Scalar, CryptoError>;
 }
 
 pub struct YMapping<Scalar: Field> {
@@ -152,7 +153,7 @@ impl<F: Field> YMapping<F> {
 }
 
 impl<Scalar: Field> BilinearMap<Scalar> for YMapping<Scalar> {
-    fn compute_mapping(&self, a: &Vec<Scalar>, b: &Vec<Scalar>) -> Result<Scalar, CryptoError> {
+    fn compute_mapping(&self, a: &[Scalar], b: &[Scalar]) -> Result<Scalar, CryptoError> {
         if a.len() != b.len() || a.len() != self.powers.len() {
             return Err(CryptoError::BilinearMapLengthError(a.len(), b.len()));
         }
@@ -1,8 +1,9 @@
 use super::{BilinearMap, Parameters, Statement};
 
-use crate::error::CryptoError;
+use crate::error::{Argument, Check, CryptoError};
 use crate::utils::vector_arithmetic::dot_product;
 use crate::vector_commitment::HomomorphicCommitmentScheme;
+use crate::zkp::arguments::checks::OpeningChecks;
 use crate::zkp::arguments::scalar_powers;
 
 use crate::utils::rand::FiatShamirRng;
@@ -40,16 +41,48 @@ where
         statement: &Statement<Scalar, Comm>,
         fs_rng: &mut FiatShamirRng<D>,
     ) -> Result<(), CryptoError> {
+        self.verify_with(
+            proof_parameters,
+            statement,
+            fs_rng,
+            &mut OpeningChecks::new(),
+        )
+    }
+
+    /// Same as [`Proof::verify`], with the opening equations handled by `openings`
+    pub(crate) fn verify_with<D: Digest>(
+        &self,
+        proof_parameters: &Parameters<Scalar, Comm>,
+        statement: &Statement<Scalar, Comm>,
+        fs_rng: &mut FiatShamirRng<D>,
+        openings: &mut OpeningChecks<Scalar, Comm>,
+    ) -> Result<(), CryptoError> {
+        let num_of_diagonals = 2 * proof_parameters.m + 1;
+        if self.vector_of_committed_diagonals.len() != num_of_diagonals {
+            return Err(CryptoError::failed_check(
+                Argument::ZeroValue,
+                Check::Length {
+                    name: "committed diagonals",
+                    expected: num_of_diagonals,
+                    found: self.vector_of_committed_diagonals.len(),
+                },
+            ));
+        }
+
         if self.vector_of_committed_diagonals[proof_parameters.m + 1]
             != Comm::commit(
                 proof_parameters.commit_key,
-                &vec![Scalar::zero()],
+                &[Scalar::zero()],
                 Scalar::zero(),
             )?
         {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Zero Argument (5.2)",
-            )));
+            return Err(CryptoError::failed_check(
+                Argument::ZeroValue,
+                Check::CommitmentToZero {
+                    name: "committed diagonals",
+                    index: Some(proof_parameters.m + 1),
+                },
+            ));
         }
 
         fs_rng.absorb(b"zero_argument");
@@ -84,35 +117,39 @@ where
         // Verify commitment to A against a commitment on blinded a with blinded random r
         let left: Comm::Commitment =
             self.a_0_commit + dot_product(&first_m_non_zero_powers, statement.commitment_to_a)?;
-        let right = Comm::commit(proof_parameters.commit_key, &self.a_blinded, self.r_blinded)?;
-        if left != right {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Zero Argument (5.2)",
-            )));
-        }
+        openings.check(
+            proof_parameters.commit_key,
+            Argument::ZeroValue,
+            "commitment to A",
+            left,
+            &self.a_blinded,
+            self.r_blinded,
+        )?;
 
         // Verify commitment to B against a commitment on blinded b with blinded random s
         let left = self.b_m_commit
             + dot_product(&first_m_non_zero_powers_reversed, statement.commitment_to_b)?;
-        let right = Comm::commit(proof_parameters.commit_key, &self.b_blinded, self.s_blinded)?;
-        if left != right {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Zero Argument (5.2)",
-            )));
-        }
+        openings.check(
+            proof_parameters.commit_key,
+            Argument::ZeroValue,
+            "commitment to B",
+            left,
+            &self.b_blinded,
+            self.s_blinded,
+        )?;
 
         // Verify commitments to the diagonals against a commitment on bilinear_map(blinded a, blinded a) with blinded random t
         let left = dot_product(&challenge_powers, &self.vector_of_committed_diagonals)?;
         let a_star_b = statement
             .bilinear_map
             .compute_mapping(&self.a_blinded, &self.b_blinded)?;
-        let right = Comm::commit(proof_parameters.commit_key, &vec![a_star_b], self.t_blinded)?;
-        if left != right {
-            return Err(CryptoError::ProofVerificationError(String::from(
-                "Zero Argument (5.2)",
-            )));
-        }
-
-        Ok(())
+        openings.check(
+            proof_parameters.commit_key,
+            Argument::ZeroValue,
+            "commitments to the diagonals",
+            left,
+            &[a_star_b],
+            self.t_blinded,
+        )
     }
 }
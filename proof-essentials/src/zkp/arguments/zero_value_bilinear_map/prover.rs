@@ -8,7 +8,7 @@ use crate::zkp::arguments::scalar_powers;
 use ark_ff::Field;
 use digest::Digest;
 
-use rand::Rng;
+use ark_std::rand::{CryptoRng, RngCore};
 
 pub struct Prover<'a, Scalar, Comm>
 where
@@ -37,7 +37,7 @@ where
         }
     }
 
-    pub fn prove<R: Rng, D: Digest>(
+    pub fn prove<R: CryptoRng + RngCore, D: Digest>(
         &self,
         rng: &mut R,
         fs_rng: &mut FiatShamirRng<D>,
@@ -75,7 +75,7 @@ where
             .iter()
             .zip(t.iter())
             .map(|(&diagonal, &random)| -> Result<_, CryptoError> {
-                Comm::commit(self.parameters.commit_key, &vec![diagonal], random)
+                Comm::commit(self.parameters.commit_key, &[diagonal], random)
             })
             .collect::<Result<Vec<_>, CryptoError>>()?;
 
@@ -113,19 +113,24 @@ where
         // ...
         // a1[n]x + a2[n]x^2 ... am[n]x^m = b[n]
         let mut a_blinded: Vec<Scalar> = Vec::with_capacity(self.parameters.m + 1);
-        for i in 0..self.parameters.n {
-            let mut poly = a_0[i];
-            for j in 0..self.parameters.m {
-                poly += self.witness.matrix_a[j][i] * first_m_non_zero_powers[j];
+        for (i, &a_0_i) in a_0.iter().enumerate().take(self.parameters.n) {
+            let mut poly = a_0_i;
+            for (a_j, &x_j) in self.witness.matrix_a.iter().zip(&first_m_non_zero_powers) {
+                poly += a_j[i] * x_j;
             }
             a_blinded.push(poly);
         }
 
         let mut b_blinded: Vec<Scalar> = Vec::with_capacity(self.parameters.m + 1);
-        for i in 0..self.parameters.n {
-            let mut poly = b_m[i];
-            for j in 0..self.parameters.m {
-                poly += self.witness.matrix_b[j][i] * first_m_non_zero_powers_reversed[j];
+        for (i, &b_m_i) in b_m.iter().enumerate().take(self.parameters.n) {
+            let mut poly = b_m_i;
+            for (b_j, &x_j) in self
+                .witness
+                .matrix_b
+                .iter()
+                .zip(&first_m_non_zero_powers_reversed)
+            {
+                poly += b_j[i] * x_j;
             }
             b_blinded.push(poly);
         }
@@ -155,8 +160,8 @@ where
 
     fn diagonals_from_chunks(
         &self,
-        a_chunks: &Vec<Vec<Scalar>>,
-        b_chunks: &Vec<Vec<Scalar>>,
+        a_chunks: &[Vec<Scalar>],
+        b_chunks: &[Vec<Scalar>],
         statement_diagonal: usize,
         statement_value: Scalar,
     ) -> Result<Vec<Scalar>, CryptoError> {
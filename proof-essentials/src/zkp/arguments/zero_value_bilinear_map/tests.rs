@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod test {
-    use crate::error::CryptoError;
+    use crate::error::{Argument, Check, CryptoError};
     use crate::utils::rand::sample_vector;
     use crate::utils::vector_arithmetic::reshape;
     use crate::vector_commitment::{pedersen, HomomorphicCommitmentScheme};
@@ -101,9 +101,13 @@ mod test {
 
         let mut fs_rng = FS::from_seed(b"Initialised with some input");
         assert_eq!(
-            Err(CryptoError::ProofVerificationError(String::from(
-                "Zero Argument (5.2)",
-            ))),
+            Err(CryptoError::ArgumentVerificationError {
+                argument: Argument::ZeroValue,
+                check: Check::Opening {
+                    name: "commitment to B",
+                    index: None,
+                },
+            }),
             ZeroValueArgument::verify(&proof_parameters, &statement, &invalid_proof, &mut fs_rng)
         );
     }
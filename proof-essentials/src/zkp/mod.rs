@@ -1,11 +1,10 @@
 use crate::error::CryptoError;
 use crate::utils::rand::FiatShamirRng;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
 pub mod arguments;
 pub mod proofs;
-pub mod transcript;
 
 pub trait ArgumentOfKnowledge {
     type CommonReferenceString;
@@ -13,7 +12,7 @@ pub trait ArgumentOfKnowledge {
     type Witness;
     type Proof;
 
-    fn prove<R: Rng, D: Digest>(
+    fn prove<R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         common_reference_string: &Self::CommonReferenceString,
         statement: &Self::Statement,
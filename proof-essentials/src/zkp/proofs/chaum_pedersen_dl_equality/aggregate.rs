@@ -0,0 +1,203 @@
+use super::prover::Prover;
+use super::{Parameters, ProofEncoding, Statement, Witness};
+use crate::error::CryptoError;
+
+use crate::utils::rand::{sample_vector, FiatShamirRng};
+use crate::utils::secret::Secret;
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, RngCore};
+use digest::Digest;
+
+/// Statement that a single secret $x$ satisfies $A_i = xG_i$ for every base $G_i$ and $B = xH$.
+/// It is proven with one Chaum-Pedersen proof for the combined base $\sum \rho_i G_i$ and point
+/// $\sum \rho_i A_i$, where the weights $\rho_i$ are derived from the whole statement. The proof has
+/// the same size whatever the number of bases.
+#[derive(Copy, Clone)]
+pub struct AggregateStatement<'a, C: CurveGroup> {
+    pub bases: &'a [C::Affine],
+    pub points: &'a [C::Affine],
+    pub h: &'a C::Affine,
+    pub point_b: &'a C::Affine,
+}
+
+impl<'a, C: CurveGroup> AggregateStatement<'a, C> {
+    pub fn new(
+        bases: &'a [C::Affine],
+        points: &'a [C::Affine],
+        h: &'a C::Affine,
+        point_b: &'a C::Affine,
+    ) -> Self {
+        Self {
+            bases,
+            points,
+            h,
+            point_b,
+        }
+    }
+
+    /// Absorb the statement and combine the bases and the points with the weights drawn from
+    /// `fs_rng`
+    fn combine<D: Digest>(
+        &self,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(C::Affine, C::Affine), CryptoError> {
+        if self.bases.len() != self.points.len() {
+            return Err(CryptoError::AggregateStatementLengthError(
+                self.bases.len(),
+                self.points.len(),
+            ));
+        }
+
+        fs_rng.absorb(b"chaum_pedersen_aggregate");
+        fs_rng.absorb(self.h);
+        fs_rng.absorb(self.point_b);
+        fs_rng.absorb(&self.bases);
+        fs_rng.absorb(&self.points);
+
+        let weights: Vec<C::ScalarField> = sample_vector(fs_rng, self.bases.len());
+        let combined = C::normalize_batch(&[
+            C::msm_unchecked(self.bases, &weights),
+            C::msm_unchecked(self.points, &weights),
+        ]);
+
+        Ok((combined[0], combined[1]))
+    }
+}
+
+/// Statements $A_i = x_iG$ and $B_i = x_iH$ for the same parameters $G$ and $H$ but a different
+/// secret $x_i$ each. They are proven with one Chaum-Pedersen proof for $\sum \rho_i A_i$ and
+/// $\sum \rho_i B_i$, whose secret is $\sum \rho_i x_i$. A random combination of pairs only has
+/// equal discrete logarithms if every pair does, except with negligible probability, since the
+/// weights $\rho_i$ are derived from all the pairs.
+#[derive(Copy, Clone)]
+pub struct CombinedStatement<'a, C: CurveGroup>(pub &'a [(C::Affine, C::Affine)]);
+
+impl<'a, C: CurveGroup> CombinedStatement<'a, C> {
+    pub fn new(points: &'a [(C::Affine, C::Affine)]) -> Self {
+        Self(points)
+    }
+
+    /// Absorb the statement and draw the weights of the combination from `fs_rng`
+    fn weights<D: Digest>(
+        &self,
+        parameters: &Parameters<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Vec<C::ScalarField> {
+        fs_rng.absorb(b"chaum_pedersen_combined");
+        fs_rng.absorb(parameters.g);
+        fs_rng.absorb(parameters.h);
+        fs_rng.absorb(&self.0);
+
+        sample_vector(fs_rng, self.0.len())
+    }
+
+    fn combine(&self, weights: &[C::ScalarField]) -> (C::Affine, C::Affine) {
+        let (points_a, points_b): (Vec<C::Affine>, Vec<C::Affine>) = self.0.iter().copied().unzip();
+        let combined = C::normalize_batch(&[
+            C::msm_unchecked(&points_a, weights),
+            C::msm_unchecked(&points_b, weights),
+        ]);
+
+        (combined[0], combined[1])
+    }
+}
+
+pub fn prove<C, P, R, D>(
+    rng: &mut R,
+    statement: &AggregateStatement<C>,
+    witness: &Witness<C>,
+    fs_rng: &mut FiatShamirRng<D>,
+) -> Result<P, CryptoError>
+where
+    C: CurveGroup,
+    P: ProofEncoding<C>,
+    R: CryptoRng + RngCore,
+    D: Digest,
+{
+    let (base, point) = statement.combine(fs_rng)?;
+
+    Prover::create_proof(
+        rng,
+        &Parameters::new(&base, statement.h),
+        &Statement::new(&point, statement.point_b),
+        witness,
+        fs_rng,
+    )
+}
+
+pub fn verify<C, P, D>(
+    statement: &AggregateStatement<C>,
+    proof: &P,
+    fs_rng: &mut FiatShamirRng<D>,
+) -> Result<(), CryptoError>
+where
+    C: CurveGroup,
+    P: ProofEncoding<C>,
+    D: Digest,
+{
+    let (base, point) = statement.combine(fs_rng)?;
+
+    proof.verify(
+        &Parameters::new(&base, statement.h),
+        &Statement::new(&point, statement.point_b),
+        fs_rng,
+    )
+}
+
+/// Prove a [`CombinedStatement`], where `witnesses[i]` is the secret of the `i`-th pair
+pub fn prove_combined<C, P, R, D>(
+    rng: &mut R,
+    parameters: &Parameters<C>,
+    statement: &CombinedStatement<C>,
+    witnesses: &[Witness<C>],
+    fs_rng: &mut FiatShamirRng<D>,
+) -> Result<P, CryptoError>
+where
+    C: CurveGroup,
+    P: ProofEncoding<C>,
+    R: CryptoRng + RngCore,
+    D: Digest,
+{
+    if witnesses.len() != statement.0.len() {
+        return Err(CryptoError::ScalarsLengthError(
+            String::from("Witness"),
+            witnesses.len(),
+            statement.0.len(),
+        ));
+    }
+
+    let weights = statement.weights(parameters, fs_rng);
+    let (point_a, point_b) = statement.combine(&weights);
+    let witness = Secret::new(
+        weights
+            .iter()
+            .zip(witnesses.iter())
+            .map(|(weight, witness)| *weight * witness)
+            .sum::<C::ScalarField>(),
+    );
+
+    Prover::create_proof(
+        rng,
+        parameters,
+        &Statement::new(&point_a, &point_b),
+        witness.expose(),
+        fs_rng,
+    )
+}
+
+pub fn verify_combined<C, P, D>(
+    parameters: &Parameters<C>,
+    statement: &CombinedStatement<C>,
+    proof: &P,
+    fs_rng: &mut FiatShamirRng<D>,
+) -> Result<(), CryptoError>
+where
+    C: CurveGroup,
+    P: ProofEncoding<C>,
+    D: Digest,
+{
+    let weights = statement.weights(parameters, fs_rng);
+    let (point_a, point_b) = statement.combine(&weights);
+
+    proof.verify(parameters, &Statement::new(&point_a, &point_b), fs_rng)
+}
@@ -1,17 +1,23 @@
+pub mod aggregate;
 pub mod proof;
 pub mod prover;
 mod test;
 
 use crate::error::CryptoError;
 use crate::utils::rand::FiatShamirRng;
+use crate::zkp::proofs::sigma::LinearRelation;
 use crate::zkp::ArgumentOfKnowledge;
 use ark_ec::{CurveGroup, Group};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
-pub struct DLEquality<'a, C: CurveGroup> {
+/// Chaum-Pedersen proofs encoded as `P`, either [`proof::Proof`] or the smaller
+/// [`proof::CompactProof`]
+pub struct DLEquality<'a, C: CurveGroup, P = proof::Proof<C>> {
     _group: PhantomData<&'a C>,
+    _proof: PhantomData<P>,
 }
 
 #[derive(Copy, Clone)]
@@ -40,16 +46,98 @@ impl<'a, C: CurveGroup> Statement<'a, C> {
 
 type Witness<C> = <C as Group>::ScalarField;
 
-impl<'a, C> ArgumentOfKnowledge for DLEquality<'a, C>
+/// The statement as the sigma protocol `x = w * g` and `y = w * h`
+pub(crate) fn relation<C: CurveGroup>(
+    parameters: &Parameters<C>,
+    statement: &Statement<C>,
+) -> LinearRelation<C> {
+    LinearRelation::new(b"chaum_pedersen", 1)
+        .with_equation(*statement.0, vec![(0, *parameters.g)])
+        .with_equation(*statement.1, vec![(0, *parameters.h)])
+}
+
+/// Encoding of a Chaum-Pedersen proof
+pub trait ProofEncoding<C: CurveGroup>: Sized + CanonicalSerialize + CanonicalDeserialize {
+    /// Proof for the transcript made of the commitments `a` and `b`, the Fiat-Shamir challenge `c`
+    /// and the response `r`
+    fn from_transcript(a: C::Affine, b: C::Affine, c: C::ScalarField, r: C::ScalarField) -> Self;
+
+    fn verify<D: Digest>(
+        &self,
+        parameters: &Parameters<C>,
+        statement: &Statement<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError>;
+
+    /// Verify many proofs, each against its own copy of `fs_rng`. The error does not say which
+    /// proof is invalid.
+    fn batch_verify<D: Digest>(
+        instances: &[(Parameters<C>, Statement<C>, &Self)],
+        fs_rng: &FiatShamirRng<D>,
+    ) -> Result<(), CryptoError>;
+}
+
+impl<'a, C: CurveGroup, P: ProofEncoding<C>> DLEquality<'a, C, P> {
+    /// Verify many proofs at once, see [`proof::batch_verify`] for full proofs
+    pub fn batch_verify<D: Digest>(
+        instances: &[(Parameters<'a, C>, Statement<'a, C>, &P)],
+        fs_rng: &FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        P::batch_verify(instances, fs_rng)
+    }
+
+    /// Prove many statements sharing the same witness with a single proof, see
+    /// [`aggregate::AggregateStatement`]
+    pub fn prove_aggregate<R: CryptoRng + RngCore, D: Digest>(
+        rng: &mut R,
+        statement: &aggregate::AggregateStatement<C>,
+        witness: &Witness<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<P, CryptoError> {
+        aggregate::prove(rng, statement, witness, fs_rng)
+    }
+
+    pub fn verify_aggregate<D: Digest>(
+        statement: &aggregate::AggregateStatement<C>,
+        proof: &P,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        aggregate::verify(statement, proof, fs_rng)
+    }
+
+    /// Prove many statements with their own witnesses with a single proof, see
+    /// [`aggregate::CombinedStatement`]
+    pub fn prove_combined<R: CryptoRng + RngCore, D: Digest>(
+        rng: &mut R,
+        parameters: &Parameters<C>,
+        statement: &aggregate::CombinedStatement<C>,
+        witnesses: &[Witness<C>],
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<P, CryptoError> {
+        aggregate::prove_combined(rng, parameters, statement, witnesses, fs_rng)
+    }
+
+    pub fn verify_combined<D: Digest>(
+        parameters: &Parameters<C>,
+        statement: &aggregate::CombinedStatement<C>,
+        proof: &P,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        aggregate::verify_combined(parameters, statement, proof, fs_rng)
+    }
+}
+
+impl<'a, C, P> ArgumentOfKnowledge for DLEquality<'a, C, P>
 where
     C: CurveGroup,
+    P: ProofEncoding<C>,
 {
     type CommonReferenceString = Parameters<'a, C>;
     type Statement = Statement<'a, C>;
     type Witness = Witness<C>;
-    type Proof = proof::Proof<C>;
+    type Proof = P;
 
-    fn prove<R: Rng, D: Digest>(
+    fn prove<R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         common_reference_string: &Self::CommonReferenceString,
         statement: &Self::Statement,
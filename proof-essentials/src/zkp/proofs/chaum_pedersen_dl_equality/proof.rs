@@ -1,10 +1,10 @@
 use crate::error::CryptoError;
-use ark_std::ops::Mul;
 
-use super::{Parameters, Statement};
+use super::{relation, Parameters, ProofEncoding, Statement};
 
 use crate::utils::rand::FiatShamirRng;
-use ark_ec::{AffineRepr, CurveGroup};
+use crate::zkp::proofs::sigma::{proof::challenge, LinearRelation, SigmaProtocol};
+use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::UniformRand;
 use digest::Digest;
@@ -26,25 +26,74 @@ impl<C: CurveGroup> Proof<C> {
         statement: &Statement<C>,
         fs_rng: &mut FiatShamirRng<D>,
     ) -> Result<(), CryptoError> {
-        fs_rng.absorb(b"chaum_pedersen");
-        fs_rng.absorb(parameters.g);
-        fs_rng.absorb(parameters.h);
-        fs_rng.absorb(statement.0);
-        fs_rng.absorb(statement.1);
-        fs_rng.absorb(&self.a);
-        fs_rng.absorb(&self.b);
-
-        let c = C::ScalarField::rand(fs_rng);
-
-        // g * r ==? a + x*c
-        if parameters.g.mul(self.r) != self.a.into_group() + statement.0.mul(c) {
+        let relation = relation(parameters, statement);
+        let c = self.challenge(&relation, fs_rng);
+
+        // g * r ==? a + x*c and h * r ==? b + y*c
+        if relation.recompute_commitment(&c, &vec![self.r])? != vec![self.a, self.b] {
             return Err(CryptoError::ProofVerificationError(String::from(
                 "Chaum-Pedersen",
             )));
         }
 
-        // h * r ==? b + y*c
-        if parameters.h.mul(self.r) != self.b.into_group() + statement.1.mul(c) {
+        Ok(())
+    }
+
+    fn challenge<D: Digest>(
+        &self,
+        relation: &LinearRelation<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> C::ScalarField {
+        challenge(relation, &vec![self.a, self.b], fs_rng)
+    }
+}
+
+impl<C: CurveGroup> ProofEncoding<C> for Proof<C> {
+    fn from_transcript(a: C::Affine, b: C::Affine, _c: C::ScalarField, r: C::ScalarField) -> Self {
+        Self { a, b, r }
+    }
+
+    fn verify<D: Digest>(
+        &self,
+        parameters: &Parameters<C>,
+        statement: &Statement<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        self.verify(parameters, statement, fs_rng)
+    }
+
+    fn batch_verify<D: Digest>(
+        instances: &[(Parameters<C>, Statement<C>, &Self)],
+        fs_rng: &FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        batch_verify(instances, fs_rng)
+    }
+}
+
+/// Chaum-Pedersen proof sent as the challenge and the response. The verifier recomputes both
+/// commitments and checks that they lead to the same challenge, which makes the proof two scalars
+/// instead of two points and a scalar.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Debug, CanonicalDeserialize, CanonicalSerialize)]
+pub struct CompactProof<C>
+where
+    C: CurveGroup,
+{
+    pub(crate) c: C::ScalarField,
+    pub(crate) r: C::ScalarField,
+}
+
+impl<C: CurveGroup> CompactProof<C> {
+    pub fn verify<D: Digest>(
+        &self,
+        parameters: &Parameters<C>,
+        statement: &Statement<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        let relation = relation(parameters, statement);
+
+        // a = g * r - x * c and b = h * r - y * c
+        let commitment = relation.recompute_commitment(&self.c, &vec![self.r])?;
+        if challenge(&relation, &commitment, fs_rng) != self.c {
             return Err(CryptoError::ProofVerificationError(String::from(
                 "Chaum-Pedersen",
             )));
@@ -53,3 +102,84 @@ impl<C: CurveGroup> Proof<C> {
         Ok(())
     }
 }
+
+impl<C: CurveGroup> ProofEncoding<C> for CompactProof<C> {
+    fn from_transcript(_a: C::Affine, _b: C::Affine, c: C::ScalarField, r: C::ScalarField) -> Self {
+        Self { c, r }
+    }
+
+    fn verify<D: Digest>(
+        &self,
+        parameters: &Parameters<C>,
+        statement: &Statement<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        self.verify(parameters, statement, fs_rng)
+    }
+
+    /// Compact proofs are verified one by one
+    fn batch_verify<D: Digest>(
+        instances: &[(Parameters<C>, Statement<C>, &Self)],
+        fs_rng: &FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        for (parameters, statement, proof) in instances {
+            proof.verify(parameters, statement, &mut fs_rng.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Verify several proofs with a single multi-scalar multiplication. The two equations of every
+/// proof are combined using random weights derived from all the proofs, so the batch only passes if
+/// every proof is valid (except with negligible probability). Each proof is checked against its own
+/// copy of `fs_rng`. The error does not say which proof is invalid: verify them one by one to find
+/// out.
+pub fn batch_verify<C: CurveGroup, D: Digest>(
+    instances: &[(Parameters<C>, Statement<C>, &Proof<C>)],
+    fs_rng: &FiatShamirRng<D>,
+) -> Result<(), CryptoError> {
+    let mut weight_rng = FiatShamirRng::<D>::from_seed(b"chaum_pedersen_batch");
+    let challenges = instances
+        .iter()
+        .map(|(parameters, statement, proof)| {
+            let c = proof.challenge(&relation(parameters, statement), &mut fs_rng.clone());
+            weight_rng.absorb(*proof);
+            weight_rng.absorb(&c);
+            c
+        })
+        .collect::<Vec<_>>();
+
+    let mut bases = Vec::with_capacity(6 * instances.len());
+    let mut scalars = Vec::with_capacity(6 * instances.len());
+    for ((parameters, statement, proof), c) in instances.iter().zip(challenges) {
+        let rho = C::ScalarField::rand(&mut weight_rng);
+        let sigma = C::ScalarField::rand(&mut weight_rng);
+
+        // rho * (g * r - a - x * c) + sigma * (h * r - b - y * c) ==? 0
+        bases.extend_from_slice(&[
+            *parameters.g,
+            proof.a,
+            *statement.0,
+            *parameters.h,
+            proof.b,
+            *statement.1,
+        ]);
+        scalars.extend_from_slice(&[
+            rho * proof.r,
+            -rho,
+            -rho * c,
+            sigma * proof.r,
+            -sigma,
+            -sigma * c,
+        ]);
+    }
+
+    if !C::msm_unchecked(&bases, &scalars).is_zero() {
+        return Err(CryptoError::ProofVerificationError(String::from(
+            "Chaum-Pedersen",
+        )));
+    }
+
+    Ok(())
+}
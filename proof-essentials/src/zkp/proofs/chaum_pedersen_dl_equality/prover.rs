@@ -1,12 +1,11 @@
 use crate::error::CryptoError;
 
-use super::proof::Proof;
-use super::{Parameters, Statement, Witness};
+use super::{relation, Parameters, ProofEncoding, Statement, Witness};
 
 use crate::utils::rand::FiatShamirRng;
-use ark_ec::AffineRepr;
-use ark_ff::PrimeField;
-use ark_std::{rand::Rng, UniformRand};
+use crate::utils::secret::Secret;
+use crate::zkp::proofs::sigma;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
 use ark_ec::CurveGroup;
@@ -23,30 +22,26 @@ impl<C> Prover<C>
 where
     C: CurveGroup,
 {
-    pub fn create_proof<R: Rng, D: Digest>(
+    pub fn create_proof<P: ProofEncoding<C>, R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         parameters: &Parameters<C>,
         statement: &Statement<C>,
         witness: &Witness<C>,
         fs_rng: &mut FiatShamirRng<D>,
-    ) -> Result<Proof<C>, CryptoError> {
-        fs_rng.absorb(b"chaum_pedersen");
-        fs_rng.absorb(parameters.g);
-        fs_rng.absorb(parameters.h);
-        fs_rng.absorb(statement.0);
-        fs_rng.absorb(statement.1);
-
-        let omega = C::ScalarField::rand(rng);
-        let a = parameters.g.mul_bigint(omega.into_bigint()).into_affine();
-        let b = parameters.h.mul_bigint(omega.into_bigint()).into_affine();
-
-        fs_rng.absorb(&a);
-        fs_rng.absorb(&b);
-
-        let c = C::ScalarField::rand(fs_rng);
-
-        let r = omega + c * *witness;
-
-        Ok(Proof { a, b, r })
+    ) -> Result<P, CryptoError> {
+        let witness = Secret::new(vec![*witness]);
+        let (commitment, c, response) = sigma::prover::Prover::transcript(
+            rng,
+            &relation(parameters, statement),
+            witness.expose(),
+            fs_rng,
+        )?;
+
+        Ok(P::from_transcript(
+            commitment[0],
+            commitment[1],
+            c,
+            response[0],
+        ))
     }
 }
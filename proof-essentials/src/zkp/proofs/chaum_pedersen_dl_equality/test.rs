@@ -1,11 +1,15 @@
 #[cfg(test)]
-mod test {
+mod tests {
     use crate::error::CryptoError;
     use crate::utils::rand::FiatShamirRng;
     use crate::zkp::proofs::chaum_pedersen_dl_equality;
+    use crate::zkp::proofs::chaum_pedersen_dl_equality::aggregate::{
+        AggregateStatement, CombinedStatement,
+    };
     use crate::zkp::proofs::chaum_pedersen_dl_equality::DLEquality;
     use crate::zkp::ArgumentOfKnowledge;
     use ark_ec::CurveGroup;
+    use ark_serialize::CanonicalSerialize;
     use ark_std::{rand::thread_rng, UniformRand};
     use blake2::Blake2s256;
     use rand::{prelude::ThreadRng, Rng};
@@ -15,6 +19,10 @@ mod test {
     type Curve = ark_bn254::G1Projective;
     type Scalar = ark_bn254::Fr;
     type Parameters<'a> = chaum_pedersen_dl_equality::Parameters<'a, Curve>;
+    type Statement<'a> = chaum_pedersen_dl_equality::Statement<'a, Curve>;
+    type Proof = chaum_pedersen_dl_equality::proof::Proof<Curve>;
+    type CompactProof = chaum_pedersen_dl_equality::proof::CompactProof<Curve>;
+    type CompactDLEquality<'a> = DLEquality<'a, Curve, CompactProof>;
     type FS = FiatShamirRng<Blake2s256>;
 
     fn setup<R: Rng>(rng: &mut R) -> (AffinePoint, AffinePoint) {
@@ -50,7 +58,7 @@ mod test {
             &mut rng,
             &crs,
             &statement,
-            &witness,
+            witness,
             &mut fs_rng,
         )
         .unwrap();
@@ -85,7 +93,7 @@ mod test {
             &mut rng,
             &crs,
             &statement,
-            &wrong_witness,
+            wrong_witness,
             &mut fs_rng,
         )
         .unwrap();
@@ -103,4 +111,231 @@ mod test {
             )))
         );
     }
+
+    fn instances<'a>(
+        g: &'a AffinePoint,
+        hs: &'a [AffinePoint],
+        points: &'a [(AffinePoint, AffinePoint)],
+        proofs: &'a [Proof],
+    ) -> Vec<(Parameters<'a>, Statement<'a>, &'a Proof)> {
+        hs.iter()
+            .zip(points.iter())
+            .zip(proofs.iter())
+            .map(|((h, (point_a, point_b)), proof)| {
+                (
+                    Parameters::new(g, h),
+                    Statement::new(point_a, point_b),
+                    proof,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let (mut rng, g, _, _) = test_template();
+        let batch_size = 6;
+
+        let hs: Vec<AffinePoint> = (0..batch_size)
+            .map(|_| Curve::rand(&mut rng).into_affine())
+            .collect();
+        let secrets: Vec<Scalar> = (0..batch_size).map(|_| Scalar::rand(&mut rng)).collect();
+        let points: Vec<(AffinePoint, AffinePoint)> = hs
+            .iter()
+            .zip(secrets.iter())
+            .map(|(h, secret)| (g.mul(secret).into_affine(), h.mul(secret).into_affine()))
+            .collect();
+
+        let fs_rng = FS::from_seed(b"Initialised with some input");
+        let mut proofs = hs
+            .iter()
+            .zip(points.iter())
+            .zip(secrets.iter())
+            .map(|((h, (point_a, point_b)), secret)| {
+                DLEquality::<Curve>::prove(
+                    &mut rng,
+                    &Parameters::new(&g, h),
+                    &Statement::new(point_a, point_b),
+                    secret,
+                    &mut FS::from_seed(b"Initialised with some input"),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            DLEquality::<Curve>::batch_verify(&instances(&g, &hs, &points, &proofs), &fs_rng),
+            Ok(())
+        );
+
+        // A batch with a single invalid proof is rejected
+        proofs[3] = proofs[2];
+        assert_eq!(
+            DLEquality::<Curve>::batch_verify(&instances(&g, &hs, &points, &proofs), &fs_rng),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Chaum-Pedersen"
+            )))
+        );
+
+        // Proofs must be verified with the transcript they were created with
+        assert!(DLEquality::<Curve>::batch_verify(
+            &instances(&g, &hs, &points, &proofs[..2]),
+            &FS::from_seed(b"Some other input")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_compact_proof() {
+        let (mut rng, g, h, secret) = test_template();
+
+        let point_a = g.mul(secret).into_affine();
+        let point_b = h.mul(secret).into_affine();
+        let crs = Parameters::new(&g, &h);
+        let statement = Statement::new(&point_a, &point_b);
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let proof =
+            CompactDLEquality::prove(&mut rng, &crs, &statement, &secret, &mut fs_rng).unwrap();
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            CompactDLEquality::verify(&crs, &statement, &proof, &mut fs_rng),
+            Ok(())
+        );
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let full_proof =
+            DLEquality::<Curve>::prove(&mut rng, &crs, &statement, &secret, &mut fs_rng).unwrap();
+        assert!(proof.compressed_size() < full_proof.compressed_size());
+        assert!(2 * proof.uncompressed_size() < full_proof.uncompressed_size());
+
+        // The proof does not hold for another statement
+        let swapped = Statement::new(&point_b, &point_a);
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            CompactDLEquality::verify(&crs, &swapped, &proof, &mut fs_rng),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Chaum-Pedersen"
+            )))
+        );
+
+        let fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            CompactDLEquality::batch_verify(
+                &[(crs, statement, &proof), (crs, swapped, &proof)],
+                &fs_rng
+            ),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Chaum-Pedersen"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_aggregate_proof() {
+        let (mut rng, _, h, secret) = test_template();
+        let num_of_bases = 7;
+
+        let bases: Vec<AffinePoint> = (0..num_of_bases)
+            .map(|_| Curve::rand(&mut rng).into_affine())
+            .collect();
+        let mut points: Vec<AffinePoint> = bases
+            .iter()
+            .map(|base| base.mul(secret).into_affine())
+            .collect();
+        let point_b = h.mul(secret).into_affine();
+
+        let statement = AggregateStatement::new(&bases, &points, &h, &point_b);
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let proof: Proof =
+            DLEquality::prove_aggregate(&mut rng, &statement, &secret, &mut fs_rng).unwrap();
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            DLEquality::verify_aggregate(&statement, &proof, &mut fs_rng),
+            Ok(())
+        );
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let compact_proof: CompactProof =
+            DLEquality::prove_aggregate(&mut rng, &statement, &secret, &mut fs_rng).unwrap();
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            DLEquality::verify_aggregate(&statement, &compact_proof, &mut fs_rng),
+            Ok(())
+        );
+
+        // A single point computed with another secret invalidates the proof
+        points[4] = bases[4].mul(Scalar::rand(&mut rng)).into_affine();
+        let statement = AggregateStatement::new(&bases, &points, &h, &point_b);
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            DLEquality::verify_aggregate(&statement, &proof, &mut fs_rng),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Chaum-Pedersen"
+            )))
+        );
+
+        let statement = AggregateStatement::new(&bases, &points[1..], &h, &point_b);
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            DLEquality::verify_aggregate(&statement, &proof, &mut fs_rng),
+            Err(CryptoError::AggregateStatementLengthError(
+                num_of_bases,
+                num_of_bases - 1
+            ))
+        );
+    }
+
+    #[test]
+    fn test_combined_proof() {
+        let (mut rng, g, h, _) = test_template();
+        let num_of_pairs = 7;
+
+        let crs = Parameters::new(&g, &h);
+        let secrets: Vec<Scalar> = (0..num_of_pairs).map(|_| Scalar::rand(&mut rng)).collect();
+        let mut points: Vec<(AffinePoint, AffinePoint)> = secrets
+            .iter()
+            .map(|secret| (g.mul(secret).into_affine(), h.mul(secret).into_affine()))
+            .collect();
+
+        let statement = CombinedStatement::new(&points);
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let proof: Proof =
+            DLEquality::prove_combined(&mut rng, &crs, &statement, &secrets, &mut fs_rng).unwrap();
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            DLEquality::verify_combined(&crs, &statement, &proof, &mut fs_rng),
+            Ok(())
+        );
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            DLEquality::<Curve>::prove_combined(
+                &mut rng,
+                &crs,
+                &statement,
+                &secrets[1..],
+                &mut fs_rng
+            ),
+            Err(CryptoError::ScalarsLengthError(
+                String::from("Witness"),
+                num_of_pairs - 1,
+                num_of_pairs
+            ))
+        );
+
+        // A single pair with different discrete logarithms invalidates the proof
+        points[2].1 = h.mul(Scalar::rand(&mut rng)).into_affine();
+        let statement = CombinedStatement::new(&points);
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            DLEquality::verify_combined(&crs, &statement, &proof, &mut fs_rng),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Chaum-Pedersen"
+            )))
+        );
+    }
 }
@@ -1,2 +1,3 @@
 pub mod chaum_pedersen_dl_equality;
 pub mod schnorr_identification;
+pub mod sigma;
@@ -4,14 +4,19 @@ mod test;
 
 use crate::error::CryptoError;
 use crate::utils::rand::FiatShamirRng;
+use crate::zkp::proofs::sigma::LinearRelation;
 use crate::zkp::ArgumentOfKnowledge;
 use ark_ec::{CurveGroup, Group};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
-pub struct SchnorrIdentification<C: CurveGroup> {
+/// Schnorr identification with proofs encoded as `P`, either [`proof::Proof`] or the smaller
+/// [`proof::CompactProof`]
+pub struct SchnorrIdentification<C: CurveGroup, P = proof::Proof<C>> {
     _group: PhantomData<C>,
+    _proof: PhantomData<P>,
 }
 
 pub type Parameters<C> = <C as CurveGroup>::Affine;
@@ -20,13 +25,46 @@ pub type Statement<C> = <C as CurveGroup>::Affine;
 
 pub type Witness<C> = <C as Group>::ScalarField;
 
-impl<C: CurveGroup> ArgumentOfKnowledge for SchnorrIdentification<C> {
+/// The statement `statement = witness * pp` as a sigma protocol. Schnorr proofs answer the
+/// opposite of the Fiat-Shamir challenge, with an opening `random - c * witness`.
+pub(crate) fn relation<C: CurveGroup>(
+    pp: &Parameters<C>,
+    statement: &Statement<C>,
+) -> LinearRelation<C> {
+    LinearRelation::new(b"schnorr_identity", 1).with_equation(*statement, vec![(0, *pp)])
+}
+
+/// Encoding of a Schnorr proof
+pub trait ProofEncoding<C: CurveGroup>: Sized + CanonicalSerialize + CanonicalDeserialize {
+    /// Proof for the transcript made of the commitment, the Fiat-Shamir challenge and the opening
+    fn from_transcript(
+        random_commit: C::Affine,
+        challenge: C::ScalarField,
+        opening: C::ScalarField,
+    ) -> Self;
+
+    fn verify<D: Digest>(
+        &self,
+        pp: &Parameters<C>,
+        statement: &Statement<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError>;
+
+    /// Verify many proofs for the same generator, each against its own transcript. An invalid
+    /// proof is reported as [`CryptoError::InvalidBatchProof`].
+    fn batch_verify<D: Digest>(
+        pp: &Parameters<C>,
+        instances: &[(Statement<C>, &Self, FiatShamirRng<D>)],
+    ) -> Result<(), CryptoError>;
+}
+
+impl<C: CurveGroup, P: ProofEncoding<C>> ArgumentOfKnowledge for SchnorrIdentification<C, P> {
     type CommonReferenceString = Parameters<C>;
     type Statement = Statement<C>;
     type Witness = Witness<C>;
-    type Proof = proof::Proof<C>;
+    type Proof = P;
 
-    fn prove<R: Rng, D: Digest>(
+    fn prove<R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         common_reference_string: &Self::CommonReferenceString,
         statement: &Self::Statement,
@@ -46,6 +84,14 @@ impl<C: CurveGroup> ArgumentOfKnowledge for SchnorrIdentification<C> {
     }
 }
 
-impl<C: CurveGroup> SchnorrIdentification<C> {
+impl<C: CurveGroup, P: ProofEncoding<C>> SchnorrIdentification<C, P> {
     pub const PROTOCOL_NAME: &'static [u8] = b"Schnorr Identification Scheme";
+
+    /// Verify many proofs at once, see [`proof::batch_verify`] for full proofs
+    pub fn batch_verify<D: Digest>(
+        pp: &Parameters<C>,
+        instances: &[(Statement<C>, &P, FiatShamirRng<D>)],
+    ) -> Result<(), CryptoError> {
+        P::batch_verify(pp, instances)
+    }
 }
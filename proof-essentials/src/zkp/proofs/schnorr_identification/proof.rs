@@ -1,9 +1,10 @@
-use super::{Parameters, Statement};
+use super::{relation, Parameters, ProofEncoding, Statement};
 use crate::error::CryptoError;
 
 use crate::utils::rand::FiatShamirRng;
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::PrimeField;
+use crate::zkp::proofs::sigma::{proof::challenge, LinearRelation, SigmaProtocol};
+use ark_ec::CurveGroup;
+use ark_ff::Zero;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::UniformRand;
 use digest::Digest;
@@ -24,16 +25,79 @@ impl<C: CurveGroup> Proof<C> {
         statement: &Statement<C>,
         fs_rng: &mut FiatShamirRng<D>,
     ) -> Result<(), CryptoError> {
-        fs_rng.absorb(b"schnorr_identity");
-        fs_rng.absorb(pp);
-        fs_rng.absorb(statement);
-        fs_rng.absorb(&self.random_commit);
+        let relation = relation(pp, statement);
+        let c = self.challenge(&relation, fs_rng);
 
-        let c = C::ScalarField::rand(fs_rng);
+        if relation.recompute_commitment(&-c, &vec![self.opening])? != vec![self.random_commit] {
+            return Err(CryptoError::ProofVerificationError(String::from(
+                "Schnorr Identification",
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn challenge<D: Digest>(
+        &self,
+        relation: &LinearRelation<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> C::ScalarField {
+        challenge(relation, &vec![self.random_commit], fs_rng)
+    }
+}
+
+impl<C: CurveGroup> ProofEncoding<C> for Proof<C> {
+    fn from_transcript(
+        random_commit: C::Affine,
+        _challenge: C::ScalarField,
+        opening: C::ScalarField,
+    ) -> Self {
+        Self {
+            random_commit,
+            opening,
+        }
+    }
+
+    fn verify<D: Digest>(
+        &self,
+        pp: &Parameters<C>,
+        statement: &Statement<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        self.verify(pp, statement, fs_rng)
+    }
+
+    fn batch_verify<D: Digest>(
+        pp: &Parameters<C>,
+        instances: &[(Statement<C>, &Self, FiatShamirRng<D>)],
+    ) -> Result<(), CryptoError> {
+        batch_verify(pp, instances)
+    }
+}
+
+/// Schnorr proof sent as the challenge and the opening. The verifier recomputes the commitment
+/// and checks that it leads to the same challenge. Compact proofs are two scalars instead of a
+/// point and a scalar, but cannot be verified with a single multi-scalar multiplication.
+#[derive(Copy, Clone, CanonicalDeserialize, CanonicalSerialize, Debug, PartialEq, Eq)]
+pub struct CompactProof<C>
+where
+    C: CurveGroup,
+{
+    pub(crate) challenge: C::ScalarField,
+    pub(crate) opening: C::ScalarField,
+}
 
-        if pp.mul_bigint(self.opening.into_bigint()) + statement.mul_bigint(c.into_bigint())
-            != self.random_commit.into()
-        {
+impl<C: CurveGroup> CompactProof<C> {
+    pub fn verify<D: Digest>(
+        &self,
+        pp: &Parameters<C>,
+        statement: &Statement<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        let relation = relation::<C>(pp, statement);
+        let random_commit = relation.recompute_commitment(&-self.challenge, &vec![self.opening])?;
+
+        if challenge(&relation, &random_commit, fs_rng) != self.challenge {
             return Err(CryptoError::ProofVerificationError(String::from(
                 "Schnorr Identification",
             )));
@@ -42,3 +106,92 @@ impl<C: CurveGroup> Proof<C> {
         Ok(())
     }
 }
+
+impl<C: CurveGroup> ProofEncoding<C> for CompactProof<C> {
+    fn from_transcript(
+        _random_commit: C::Affine,
+        challenge: C::ScalarField,
+        opening: C::ScalarField,
+    ) -> Self {
+        Self { challenge, opening }
+    }
+
+    fn verify<D: Digest>(
+        &self,
+        pp: &Parameters<C>,
+        statement: &Statement<C>,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError> {
+        self.verify(pp, statement, fs_rng)
+    }
+
+    /// Compact proofs are verified one by one
+    fn batch_verify<D: Digest>(
+        pp: &Parameters<C>,
+        instances: &[(Statement<C>, &Self, FiatShamirRng<D>)],
+    ) -> Result<(), CryptoError> {
+        for (index, (statement, proof, fs_rng)) in instances.iter().enumerate() {
+            proof
+                .verify(pp, statement, &mut fs_rng.clone())
+                .map_err(|source| CryptoError::InvalidBatchProof {
+                    index,
+                    source: Box::new(source),
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Verify several proofs for the same generator with a single multi-scalar multiplication. The
+/// equations of the proofs are combined using random weights derived from all the proofs, so the
+/// batch only passes if every proof is valid (except with negligible probability). Each proof is
+/// checked against its own transcript. If the combined equation does not hold, the proofs are
+/// verified one by one and the error points to the first invalid one.
+pub fn batch_verify<C: CurveGroup, D: Digest>(
+    pp: &Parameters<C>,
+    instances: &[(Statement<C>, &Proof<C>, FiatShamirRng<D>)],
+) -> Result<(), CryptoError> {
+    let mut weight_rng = FiatShamirRng::<D>::from_seed(b"schnorr_batch");
+    let challenges = instances
+        .iter()
+        .map(|(statement, proof, fs_rng)| {
+            let c = proof.challenge(&relation(pp, statement), &mut fs_rng.clone());
+            weight_rng.absorb(*proof);
+            weight_rng.absorb(&c);
+            c
+        })
+        .collect::<Vec<_>>();
+
+    let mut bases = Vec::with_capacity(2 * instances.len() + 1);
+    let mut scalars = Vec::with_capacity(2 * instances.len() + 1);
+    let mut generator_scalar = C::ScalarField::zero();
+    for ((statement, proof, _), c) in instances.iter().zip(challenges) {
+        let rho = C::ScalarField::rand(&mut weight_rng);
+
+        // rho * (g * opening + pk * c - random_commit) ==? 0
+        generator_scalar += rho * proof.opening;
+        bases.extend_from_slice(&[*statement, proof.random_commit]);
+        scalars.extend_from_slice(&[rho * c, -rho]);
+    }
+    bases.push(*pp);
+    scalars.push(generator_scalar);
+
+    if C::msm_unchecked(&bases, &scalars).is_zero() {
+        return Ok(());
+    }
+
+    // Find out which proof is invalid
+    for (index, (statement, proof, fs_rng)) in instances.iter().enumerate() {
+        proof
+            .verify(pp, statement, &mut fs_rng.clone())
+            .map_err(|source| CryptoError::InvalidBatchProof {
+                index,
+                source: Box::new(source),
+            })?;
+    }
+
+    Err(CryptoError::ProofVerificationError(String::from(
+        "Schnorr Identification batch",
+    )))
+}
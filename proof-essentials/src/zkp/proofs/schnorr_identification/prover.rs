@@ -1,14 +1,14 @@
 use crate::error::CryptoError;
 
-use super::{proof::Proof, Parameters, Statement, Witness};
+use super::{relation, Parameters, ProofEncoding, Statement, Witness};
 
 use crate::utils::rand::FiatShamirRng;
-use ark_ff::PrimeField;
-use ark_std::rand::Rng;
-use ark_std::UniformRand;
+use crate::utils::secret::Secret;
+use crate::zkp::proofs::sigma::{proof::challenge, LinearRelation, SigmaProtocol};
+use ark_std::rand::{CryptoRng, RngCore};
 use digest::Digest;
 
-use ark_ec::{AffineRepr, CurveGroup};
+use ark_ec::CurveGroup;
 use ark_std::marker::PhantomData;
 
 pub struct Prover<C>
@@ -22,29 +22,22 @@ impl<C> Prover<C>
 where
     C: CurveGroup,
 {
-    pub fn create_proof<R: Rng, D: Digest>(
+    pub fn create_proof<P: ProofEncoding<C>, R: CryptoRng + RngCore, D: Digest>(
         rng: &mut R,
         pp: &Parameters<C>,
         statement: &Statement<C>,
         witness: &Witness<C>,
         fs_rng: &mut FiatShamirRng<D>,
-    ) -> Result<Proof<C>, CryptoError> {
-        let random = C::ScalarField::rand(rng);
+    ) -> Result<P, CryptoError> {
+        let relation = relation(pp, statement);
+        let witness = Secret::new(vec![*witness]);
 
-        let random_commit = pp.mul_bigint(random.into_bigint()).into();
+        let (state, commitment) = relation.commit(rng, witness.expose())?;
 
-        fs_rng.absorb(b"schnorr_identity");
-        fs_rng.absorb(pp);
-        fs_rng.absorb(statement);
-        fs_rng.absorb(&random_commit);
+        // Answer the opposite of the challenge
+        let c = challenge::<LinearRelation<C>, D>(&relation, &commitment, fs_rng);
+        let response = relation.respond(witness.expose(), state, &-c)?;
 
-        let c = C::ScalarField::rand(fs_rng);
-
-        let opening = random - c * witness;
-
-        Ok(Proof {
-            random_commit,
-            opening,
-        })
+        Ok(P::from_transcript(commitment[0], c, response[0]))
     }
 }
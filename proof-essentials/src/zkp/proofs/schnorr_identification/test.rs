@@ -1,9 +1,10 @@
 #[cfg(test)]
-mod test {
+mod tests {
     use crate::error::CryptoError;
     use crate::utils::rand::FiatShamirRng;
     use crate::zkp::{proofs::schnorr_identification, ArgumentOfKnowledge};
     use ark_ec::CurveGroup;
+    use ark_serialize::CanonicalSerialize;
     use ark_std::ops::Mul;
     use ark_std::rand::thread_rng;
     use ark_std::UniformRand;
@@ -16,6 +17,9 @@ mod test {
     type Scalar = ark_bn254::Fr;
     type Parameters = schnorr_identification::Parameters<Curve>;
     type FS = FiatShamirRng<Blake2s256>;
+    type Proof = schnorr_identification::proof::Proof<Curve>;
+    type CompactProof = schnorr_identification::proof::CompactProof<Curve>;
+    type CompactSchnorr = schnorr_identification::SchnorrIdentification<Curve, CompactProof>;
 
     fn setup<R: Rng>(rng: &mut R) -> Result<Parameters, CryptoError> {
         Ok(Curve::rand(rng).into_affine())
@@ -61,4 +65,102 @@ mod test {
             )))
         );
     }
+
+    fn instances<'a>(
+        keys: &[Point],
+        proofs: &'a [Proof],
+        transcripts: &[FS],
+    ) -> Vec<(Point, &'a Proof, FS)> {
+        keys.iter()
+            .zip(proofs.iter())
+            .zip(transcripts.iter())
+            .map(|((pk, proof), fs_rng)| (*pk, proof, fs_rng.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let (mut rng, crs, _, _) = test_template();
+        let batch_size = 6;
+
+        // Each proof is bound to its own transcript, as in key registration
+        let transcripts: Vec<FS> = (0..batch_size)
+            .map(|i| {
+                let mut fs_rng = FS::from_seed(b"Initialised with some input");
+                fs_rng.absorb(&(i as u64));
+                fs_rng
+            })
+            .collect();
+        let secrets: Vec<Scalar> = (0..batch_size).map(|_| Scalar::rand(&mut rng)).collect();
+        let keys: Vec<Point> = secrets.iter().map(|sk| crs.mul(sk).into_affine()).collect();
+        let mut proofs = keys
+            .iter()
+            .zip(secrets.iter())
+            .zip(transcripts.iter())
+            .map(|((pk, sk), fs_rng)| {
+                Schnorr::prove(&mut rng, &crs, pk, sk, &mut fs_rng.clone()).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            Schnorr::batch_verify(&crs, &instances(&keys, &proofs, &transcripts)),
+            Ok(())
+        );
+
+        // The invalid proof is identified
+        proofs[3] = proofs[2];
+        assert_eq!(
+            Schnorr::batch_verify(&crs, &instances(&keys, &proofs, &transcripts)),
+            Err(CryptoError::InvalidBatchProof {
+                index: 3,
+                source: Box::new(CryptoError::ProofVerificationError(String::from(
+                    "Schnorr Identification"
+                )))
+            })
+        );
+    }
+
+    #[test]
+    fn test_compact_proof() {
+        let (mut rng, crs, sk, pk) = test_template();
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let proof = CompactSchnorr::prove(&mut rng, &crs, &pk, &sk, &mut fs_rng).unwrap();
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            CompactSchnorr::verify(&crs, &pk, &proof, &mut fs_rng),
+            Ok(())
+        );
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let full_proof = Schnorr::prove(&mut rng, &crs, &pk, &sk, &mut fs_rng).unwrap();
+        assert!(proof.uncompressed_size() < full_proof.uncompressed_size());
+
+        let mut fs_rng = FS::from_seed(b"Some other input");
+        assert_eq!(
+            CompactSchnorr::verify(&crs, &pk, &proof, &mut fs_rng),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Schnorr Identification"
+            )))
+        );
+
+        let another_scalar = Scalar::rand(&mut rng);
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let invalid_proof =
+            CompactSchnorr::prove(&mut rng, &crs, &pk, &another_scalar, &mut fs_rng).unwrap();
+        let fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            CompactSchnorr::batch_verify(
+                &crs,
+                &[(pk, &proof, fs_rng.clone()), (pk, &invalid_proof, fs_rng)]
+            ),
+            Err(CryptoError::InvalidBatchProof {
+                index: 1,
+                source: Box::new(CryptoError::ProofVerificationError(String::from(
+                    "Schnorr Identification"
+                )))
+            })
+        );
+    }
 }
@@ -0,0 +1,185 @@
+use super::SigmaProtocol;
+
+use crate::error::CryptoError;
+use crate::utils::constant_time::mul_secret;
+use crate::utils::rand::{sample_vector, FiatShamirRng};
+use crate::utils::secret::Secret;
+
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, RngCore};
+use digest::Digest;
+
+/// Equation `image = sum of witness[index] * base` over the `(index, base)` terms
+#[derive(Clone)]
+pub struct Equation<C: CurveGroup> {
+    pub image: C::Affine,
+    pub terms: Vec<(usize, C::Affine)>,
+}
+
+/// Knowledge of secret scalars satisfying a system of linear equations over group elements.
+/// For example, a Chaum-Pedersen statement is the two equations `x = w * g` and `y = w * h` over a
+/// single scalar `w`. The response to challenge `c` for the nonces `k` is `k + c * w`.
+#[derive(Clone)]
+pub struct LinearRelation<C: CurveGroup> {
+    label: &'static [u8],
+    num_of_scalars: usize,
+    equations: Vec<Equation<C>>,
+}
+
+impl<C: CurveGroup> LinearRelation<C> {
+    /// Relation without any equation over `num_of_scalars` scalars. `label` separates the
+    /// transcripts of different kinds of statements.
+    pub fn new(label: &'static [u8], num_of_scalars: usize) -> Self {
+        Self {
+            label,
+            num_of_scalars,
+            equations: Vec::new(),
+        }
+    }
+
+    pub fn with_equation(mut self, image: C::Affine, terms: Vec<(usize, C::Affine)>) -> Self {
+        self.equations.push(Equation { image, terms });
+        self
+    }
+
+    pub fn equations(&self) -> &[Equation<C>] {
+        &self.equations
+    }
+
+    pub fn num_of_scalars(&self) -> usize {
+        self.num_of_scalars
+    }
+
+    pub fn is_valid(&self) -> Result<(), CryptoError> {
+        if self
+            .equations
+            .iter()
+            .flat_map(|equation| equation.terms.iter())
+            .any(|(index, _)| *index >= self.num_of_scalars)
+        {
+            return Err(CryptoError::InvalidLinearRelation);
+        }
+
+        Ok(())
+    }
+
+    fn check_length(&self, name: &str, length: usize) -> Result<(), CryptoError> {
+        if length != self.num_of_scalars {
+            return Err(CryptoError::ScalarsLengthError(
+                String::from(name),
+                length,
+                self.num_of_scalars,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: CurveGroup> SigmaProtocol for LinearRelation<C> {
+    type Scalar = C::ScalarField;
+    type Witness = Vec<C::ScalarField>;
+    type ProverState = Secret<Vec<C::ScalarField>>;
+    type Commitment = Vec<C::Affine>;
+    type Response = Vec<C::ScalarField>;
+
+    /// Absorb the label, every base and then every image
+    fn absorb_statement<D: Digest>(&self, fs_rng: &mut FiatShamirRng<D>) {
+        fs_rng.absorb_serialized(self.label);
+        for (_, base) in self.equations.iter().flat_map(|equation| &equation.terms) {
+            fs_rng.absorb(base);
+        }
+        for equation in &self.equations {
+            fs_rng.absorb(&equation.image);
+        }
+    }
+
+    /// Absorb the commitment of every equation on its own
+    fn absorb_commitment<D: Digest>(
+        &self,
+        commitment: &Self::Commitment,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) {
+        for point in commitment {
+            fs_rng.absorb(point);
+        }
+    }
+
+    fn commit<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        witness: &Self::Witness,
+    ) -> Result<(Self::ProverState, Self::Commitment), CryptoError> {
+        self.is_valid()?;
+        self.check_length("Witness", witness.len())?;
+
+        let nonces: Vec<C::ScalarField> = sample_vector(rng, self.num_of_scalars);
+        let commitment = self
+            .equations
+            .iter()
+            .map(|equation| {
+                equation
+                    .terms
+                    .iter()
+                    .map(|(index, base)| mul_secret::<C>(base, &nonces[*index]))
+                    .sum()
+            })
+            .collect::<Vec<C>>();
+
+        Ok((Secret::new(nonces), C::normalize_batch(&commitment)))
+    }
+
+    fn respond(
+        &self,
+        witness: &Self::Witness,
+        state: Self::ProverState,
+        challenge: &Self::Scalar,
+    ) -> Result<Self::Response, CryptoError> {
+        self.check_length("Witness", witness.len())?;
+        self.check_length("Prover state", state.expose().len())?;
+
+        Ok(state
+            .expose()
+            .iter()
+            .zip(witness.iter())
+            .map(|(nonce, scalar)| *nonce + *challenge * scalar)
+            .collect())
+    }
+
+    fn recompute_commitment(
+        &self,
+        challenge: &Self::Scalar,
+        response: &Self::Response,
+    ) -> Result<Self::Commitment, CryptoError> {
+        self.is_valid()?;
+        self.check_length("Response", response.len())?;
+
+        // sum of response[index] * base - challenge * image
+        let commitment = self
+            .equations
+            .iter()
+            .map(|equation| {
+                let (bases, scalars): (Vec<C::Affine>, Vec<C::ScalarField>) = equation
+                    .terms
+                    .iter()
+                    .map(|(index, base)| (*base, response[*index]))
+                    .chain([(equation.image, -*challenge)])
+                    .unzip();
+                C::msm_unchecked(&bases, &scalars)
+            })
+            .collect::<Vec<C>>();
+
+        Ok(C::normalize_batch(&commitment))
+    }
+
+    fn simulate<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        challenge: &Self::Scalar,
+    ) -> Result<(Self::Commitment, Self::Response), CryptoError> {
+        let response: Vec<C::ScalarField> = sample_vector(rng, self.num_of_scalars);
+        let commitment = self.recompute_commitment(challenge, &response)?;
+
+        Ok((commitment, response))
+    }
+}
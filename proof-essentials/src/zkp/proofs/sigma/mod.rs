@@ -0,0 +1,269 @@
+//! Sigma protocols made non-interactive with [`FiatShamirRng`]. A protocol holds its own
+//! statement, [`linear::LinearRelation`] covers proofs of knowledge of discrete logarithms and
+//! [`And`] / [`Or`] compose protocols into larger statements.
+
+pub mod linear;
+pub mod proof;
+pub mod prover;
+mod test;
+
+use crate::error::CryptoError;
+use crate::utils::rand::FiatShamirRng;
+
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::UniformRand;
+use digest::Digest;
+
+pub use linear::LinearRelation;
+
+/// Three-move protocol: the prover sends a commitment, receives a challenge and answers with a
+/// response that the verifier checks against the statement.
+pub trait SigmaProtocol {
+    type Scalar: PrimeField;
+    type Witness;
+    /// Secret randomness of the commitment, consumed by the response
+    type ProverState;
+    type Commitment: Clone + PartialEq + CanonicalSerialize + CanonicalDeserialize;
+    type Response: Clone + PartialEq + CanonicalSerialize + CanonicalDeserialize;
+
+    fn absorb_statement<D: Digest>(&self, fs_rng: &mut FiatShamirRng<D>);
+
+    fn absorb_commitment<D: Digest>(
+        &self,
+        commitment: &Self::Commitment,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) {
+        fs_rng.absorb(commitment);
+    }
+
+    fn commit<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        witness: &Self::Witness,
+    ) -> Result<(Self::ProverState, Self::Commitment), CryptoError>;
+
+    fn respond(
+        &self,
+        witness: &Self::Witness,
+        state: Self::ProverState,
+        challenge: &Self::Scalar,
+    ) -> Result<Self::Response, CryptoError>;
+
+    /// The only commitment accepted with `challenge` and `response`. A transcript is valid if the
+    /// prover sent this commitment, so a proof can also be sent as the challenge and the response.
+    fn recompute_commitment(
+        &self,
+        challenge: &Self::Scalar,
+        response: &Self::Response,
+    ) -> Result<Self::Commitment, CryptoError>;
+
+    /// Accepting transcript for `challenge` produced without the witness
+    fn simulate<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        challenge: &Self::Scalar,
+    ) -> Result<(Self::Commitment, Self::Response), CryptoError>;
+}
+
+/// Proof that both statements hold, answering the same challenge
+pub struct And<A, B>(pub A, pub B);
+
+impl<A, B> SigmaProtocol for And<A, B>
+where
+    A: SigmaProtocol,
+    B: SigmaProtocol<Scalar = A::Scalar>,
+{
+    type Scalar = A::Scalar;
+    type Witness = (A::Witness, B::Witness);
+    type ProverState = (A::ProverState, B::ProverState);
+    type Commitment = (A::Commitment, B::Commitment);
+    type Response = (A::Response, B::Response);
+
+    fn absorb_statement<D: Digest>(&self, fs_rng: &mut FiatShamirRng<D>) {
+        fs_rng.absorb(b"sigma_and");
+        self.0.absorb_statement(fs_rng);
+        self.1.absorb_statement(fs_rng);
+    }
+
+    fn absorb_commitment<D: Digest>(
+        &self,
+        commitment: &Self::Commitment,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) {
+        self.0.absorb_commitment(&commitment.0, fs_rng);
+        self.1.absorb_commitment(&commitment.1, fs_rng);
+    }
+
+    fn commit<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        witness: &Self::Witness,
+    ) -> Result<(Self::ProverState, Self::Commitment), CryptoError> {
+        let (state_0, commitment_0) = self.0.commit(rng, &witness.0)?;
+        let (state_1, commitment_1) = self.1.commit(rng, &witness.1)?;
+
+        Ok(((state_0, state_1), (commitment_0, commitment_1)))
+    }
+
+    fn respond(
+        &self,
+        witness: &Self::Witness,
+        state: Self::ProverState,
+        challenge: &Self::Scalar,
+    ) -> Result<Self::Response, CryptoError> {
+        Ok((
+            self.0.respond(&witness.0, state.0, challenge)?,
+            self.1.respond(&witness.1, state.1, challenge)?,
+        ))
+    }
+
+    fn recompute_commitment(
+        &self,
+        challenge: &Self::Scalar,
+        response: &Self::Response,
+    ) -> Result<Self::Commitment, CryptoError> {
+        Ok((
+            self.0.recompute_commitment(challenge, &response.0)?,
+            self.1.recompute_commitment(challenge, &response.1)?,
+        ))
+    }
+
+    fn simulate<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        challenge: &Self::Scalar,
+    ) -> Result<(Self::Commitment, Self::Response), CryptoError> {
+        let (commitment_0, response_0) = self.0.simulate(rng, challenge)?;
+        let (commitment_1, response_1) = self.1.simulate(rng, challenge)?;
+
+        Ok(((commitment_0, commitment_1), (response_0, response_1)))
+    }
+}
+
+/// Proof that at least one of the statements holds, without revealing which one (Cramer, Damgård
+/// and Schoenmakers, 1994). The branch the prover cannot answer is simulated with a challenge of
+/// its choice, and the challenges of the two branches must add up to the verifier's challenge.
+pub struct Or<A, B>(pub A, pub B);
+
+/// Witness for one of the branches of an [`Or`] proof
+pub enum OrWitness<A, B> {
+    Left(A),
+    Right(B),
+}
+
+/// Prover state of an [`Or`] proof: the state of the real branch and the simulated transcript of
+/// the other one
+pub enum OrState<A: SigmaProtocol, B: SigmaProtocol<Scalar = A::Scalar>> {
+    Left(A::ProverState, A::Scalar, B::Response),
+    Right(A::Scalar, A::Response, B::ProverState),
+}
+
+impl<A, B> SigmaProtocol for Or<A, B>
+where
+    A: SigmaProtocol,
+    B: SigmaProtocol<Scalar = A::Scalar>,
+{
+    type Scalar = A::Scalar;
+    type Witness = OrWitness<A::Witness, B::Witness>;
+    type ProverState = OrState<A, B>;
+    type Commitment = (A::Commitment, B::Commitment);
+    /// Challenge of the first branch and the responses of both branches
+    type Response = (A::Scalar, A::Response, B::Response);
+
+    fn absorb_statement<D: Digest>(&self, fs_rng: &mut FiatShamirRng<D>) {
+        fs_rng.absorb(b"sigma_or");
+        self.0.absorb_statement(fs_rng);
+        self.1.absorb_statement(fs_rng);
+    }
+
+    fn absorb_commitment<D: Digest>(
+        &self,
+        commitment: &Self::Commitment,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) {
+        self.0.absorb_commitment(&commitment.0, fs_rng);
+        self.1.absorb_commitment(&commitment.1, fs_rng);
+    }
+
+    fn commit<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        witness: &Self::Witness,
+    ) -> Result<(Self::ProverState, Self::Commitment), CryptoError> {
+        match witness {
+            OrWitness::Left(witness) => {
+                let (state, commitment_0) = self.0.commit(rng, witness)?;
+                let challenge_1 = Self::Scalar::rand(rng);
+                let (commitment_1, response_1) = self.1.simulate(rng, &challenge_1)?;
+
+                Ok((
+                    OrState::Left(state, challenge_1, response_1),
+                    (commitment_0, commitment_1),
+                ))
+            }
+            OrWitness::Right(witness) => {
+                let challenge_0 = Self::Scalar::rand(rng);
+                let (commitment_0, response_0) = self.0.simulate(rng, &challenge_0)?;
+                let (state, commitment_1) = self.1.commit(rng, witness)?;
+
+                Ok((
+                    OrState::Right(challenge_0, response_0, state),
+                    (commitment_0, commitment_1),
+                ))
+            }
+        }
+    }
+
+    fn respond(
+        &self,
+        witness: &Self::Witness,
+        state: Self::ProverState,
+        challenge: &Self::Scalar,
+    ) -> Result<Self::Response, CryptoError> {
+        match (witness, state) {
+            (OrWitness::Left(witness), OrState::Left(state, challenge_1, response_1)) => {
+                let challenge_0 = *challenge - challenge_1;
+                let response_0 = self.0.respond(witness, state, &challenge_0)?;
+                Ok((challenge_0, response_0, response_1))
+            }
+            (OrWitness::Right(witness), OrState::Right(challenge_0, response_0, state)) => {
+                let challenge_1 = *challenge - challenge_0;
+                let response_1 = self.1.respond(witness, state, &challenge_1)?;
+                Ok((challenge_0, response_0, response_1))
+            }
+            _ => Err(CryptoError::InvalidSigmaState),
+        }
+    }
+
+    fn recompute_commitment(
+        &self,
+        challenge: &Self::Scalar,
+        response: &Self::Response,
+    ) -> Result<Self::Commitment, CryptoError> {
+        let (challenge_0, response_0, response_1) = response;
+        let challenge_1 = *challenge - challenge_0;
+
+        Ok((
+            self.0.recompute_commitment(challenge_0, response_0)?,
+            self.1.recompute_commitment(&challenge_1, response_1)?,
+        ))
+    }
+
+    fn simulate<R: CryptoRng + RngCore>(
+        &self,
+        rng: &mut R,
+        challenge: &Self::Scalar,
+    ) -> Result<(Self::Commitment, Self::Response), CryptoError> {
+        let challenge_0 = Self::Scalar::rand(rng);
+        let challenge_1 = *challenge - challenge_0;
+        let (commitment_0, response_0) = self.0.simulate(rng, &challenge_0)?;
+        let (commitment_1, response_1) = self.1.simulate(rng, &challenge_1)?;
+
+        Ok((
+            (commitment_0, commitment_1),
+            (challenge_0, response_0, response_1),
+        ))
+    }
+}
@@ -0,0 +1,105 @@
+use super::SigmaProtocol;
+use crate::error::CryptoError;
+
+use crate::utils::rand::FiatShamirRng;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use digest::Digest;
+
+/// Non-interactive proof for a [`SigmaProtocol`], see [`SigmaProof`]
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalDeserialize, CanonicalSerialize)]
+pub struct Proof<Commitment, Response>
+where
+    Commitment: CanonicalSerialize + CanonicalDeserialize,
+    Response: CanonicalSerialize + CanonicalDeserialize,
+{
+    pub commitment: Commitment,
+    pub response: Response,
+}
+
+/// Proof for the sigma protocol `S`
+pub type SigmaProof<S> = Proof<<S as SigmaProtocol>::Commitment, <S as SigmaProtocol>::Response>;
+
+impl<Commitment, Response> Proof<Commitment, Response>
+where
+    Commitment: CanonicalSerialize + CanonicalDeserialize,
+    Response: CanonicalSerialize + CanonicalDeserialize,
+{
+    pub fn verify<S, D>(
+        &self,
+        protocol: &S,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError>
+    where
+        S: SigmaProtocol<Commitment = Commitment, Response = Response>,
+        Commitment: PartialEq,
+        D: Digest,
+    {
+        let challenge = challenge(protocol, &self.commitment, fs_rng);
+
+        if protocol.recompute_commitment(&challenge, &self.response)? != self.commitment {
+            return Err(CryptoError::ProofVerificationError(String::from(
+                "Sigma protocol",
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Non-interactive proof for a [`SigmaProtocol`] sent as the challenge and the response, see
+/// [`CompactSigmaProof`]. The verifier recomputes the commitment and checks that it leads to the
+/// same challenge. This is smaller than a [`Proof`] whenever the commitment is larger than a
+/// scalar.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalDeserialize, CanonicalSerialize)]
+pub struct CompactProof<Scalar, Response>
+where
+    Scalar: CanonicalSerialize + CanonicalDeserialize,
+    Response: CanonicalSerialize + CanonicalDeserialize,
+{
+    pub challenge: Scalar,
+    pub response: Response,
+}
+
+/// Compact proof for the sigma protocol `S`
+pub type CompactSigmaProof<S> =
+    CompactProof<<S as SigmaProtocol>::Scalar, <S as SigmaProtocol>::Response>;
+
+impl<Scalar, Response> CompactProof<Scalar, Response>
+where
+    Scalar: CanonicalSerialize + CanonicalDeserialize,
+    Response: CanonicalSerialize + CanonicalDeserialize,
+{
+    pub fn verify<S, D>(
+        &self,
+        protocol: &S,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<(), CryptoError>
+    where
+        S: SigmaProtocol<Scalar = Scalar, Response = Response>,
+        Scalar: PartialEq,
+        D: Digest,
+    {
+        let commitment = protocol.recompute_commitment(&self.challenge, &self.response)?;
+
+        if challenge(protocol, &commitment, fs_rng) != self.challenge {
+            return Err(CryptoError::ProofVerificationError(String::from(
+                "Sigma protocol",
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Fiat-Shamir challenge for `commitment`, absorbed after the statement of `protocol`
+pub(crate) fn challenge<S: SigmaProtocol, D: Digest>(
+    protocol: &S,
+    commitment: &S::Commitment,
+    fs_rng: &mut FiatShamirRng<D>,
+) -> S::Scalar {
+    protocol.absorb_statement(fs_rng);
+    protocol.absorb_commitment(commitment, fs_rng);
+
+    S::Scalar::rand(fs_rng)
+}
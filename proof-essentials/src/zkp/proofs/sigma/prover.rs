@@ -0,0 +1,70 @@
+use crate::error::CryptoError;
+
+use super::proof::{challenge, CompactSigmaProof, SigmaProof};
+use super::SigmaProtocol;
+
+use crate::utils::rand::FiatShamirRng;
+use ark_std::rand::{CryptoRng, RngCore};
+use digest::Digest;
+
+use ark_std::marker::PhantomData;
+
+/// Commitment, Fiat-Shamir challenge and response of a proof
+pub type Transcript<S> = (
+    <S as SigmaProtocol>::Commitment,
+    <S as SigmaProtocol>::Scalar,
+    <S as SigmaProtocol>::Response,
+);
+
+pub struct Prover<S>
+where
+    S: SigmaProtocol,
+{
+    phantom: PhantomData<S>,
+}
+
+impl<S> Prover<S>
+where
+    S: SigmaProtocol,
+{
+    pub fn create_proof<R: CryptoRng + RngCore, D: Digest>(
+        rng: &mut R,
+        protocol: &S,
+        witness: &S::Witness,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<SigmaProof<S>, CryptoError> {
+        let (commitment, _, response) = Self::transcript(rng, protocol, witness, fs_rng)?;
+
+        Ok(SigmaProof::<S> {
+            commitment,
+            response,
+        })
+    }
+
+    pub fn create_compact_proof<R: CryptoRng + RngCore, D: Digest>(
+        rng: &mut R,
+        protocol: &S,
+        witness: &S::Witness,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<CompactSigmaProof<S>, CryptoError> {
+        let (_, challenge, response) = Self::transcript(rng, protocol, witness, fs_rng)?;
+
+        Ok(CompactSigmaProof::<S> {
+            challenge,
+            response,
+        })
+    }
+
+    pub fn transcript<R: CryptoRng + RngCore, D: Digest>(
+        rng: &mut R,
+        protocol: &S,
+        witness: &S::Witness,
+        fs_rng: &mut FiatShamirRng<D>,
+    ) -> Result<Transcript<S>, CryptoError> {
+        let (state, commitment) = protocol.commit(rng, witness)?;
+        let challenge = challenge(protocol, &commitment, fs_rng);
+        let response = protocol.respond(witness, state, &challenge)?;
+
+        Ok((commitment, challenge, response))
+    }
+}
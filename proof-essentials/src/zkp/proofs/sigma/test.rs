@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use crate::error::CryptoError;
+    use crate::utils::rand::FiatShamirRng;
+    use crate::zkp::proofs::sigma::{self, And, LinearRelation, Or, OrWitness, SigmaProtocol};
+    use ark_ec::CurveGroup;
+    use ark_std::ops::Mul;
+    use ark_std::{rand::thread_rng, UniformRand};
+    use blake2::Blake2s256;
+    use rand::Rng;
+
+    type Curve = ark_bn254::G1Projective;
+    type Point = ark_bn254::G1Affine;
+    type Scalar = ark_bn254::Fr;
+    type Relation = LinearRelation<Curve>;
+    type Prover<S> = sigma::prover::Prover<S>;
+    type FS = FiatShamirRng<Blake2s256>;
+
+    fn random_point<R: Rng>(rng: &mut R) -> Point {
+        Curve::rand(rng).into_affine()
+    }
+
+    /// Knowledge of the discrete logarithm of `image` in base `base`
+    fn discrete_log(base: Point, image: Point) -> Relation {
+        LinearRelation::new(b"discrete_log", 1).with_equation(image, vec![(0, base)])
+    }
+
+    fn prove<S: SigmaProtocol<Scalar = Scalar>>(
+        protocol: &S,
+        witness: &S::Witness,
+    ) -> sigma::proof::SigmaProof<S> {
+        let rng = &mut thread_rng();
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        Prover::create_proof(rng, protocol, witness, &mut fs_rng).unwrap()
+    }
+
+    fn verify<S: SigmaProtocol<Scalar = Scalar>>(
+        protocol: &S,
+        proof: &sigma::proof::SigmaProof<S>,
+    ) -> Result<(), CryptoError> {
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        proof.verify(protocol, &mut fs_rng)
+    }
+
+    #[test]
+    fn test_linear_relation() {
+        let rng = &mut thread_rng();
+
+        // Pedersen commitment `c = x * g + r * h` to a value equal to the discrete log of `y`
+        let (g, h) = (random_point(rng), random_point(rng));
+        let (x, r) = (Scalar::rand(rng), Scalar::rand(rng));
+        let c = (g.mul(x) + h.mul(r)).into_affine();
+        let y = g.mul(x).into_affine();
+        let relation: Relation = LinearRelation::new(b"opening", 2)
+            .with_equation(c, vec![(0, g), (1, h)])
+            .with_equation(y, vec![(0, g)]);
+
+        let proof = prove(&relation, &vec![x, r]);
+        assert_eq!(verify(&relation, &proof), Ok(()));
+
+        let invalid_proof = prove(&relation, &vec![x, Scalar::rand(rng)]);
+        assert_eq!(
+            verify(&relation, &invalid_proof),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Sigma protocol"
+            )))
+        );
+
+        let mut malformed_proof = proof;
+        malformed_proof.response.pop();
+        assert_eq!(
+            verify(&relation, &malformed_proof),
+            Err(CryptoError::ScalarsLengthError(
+                String::from("Response"),
+                1,
+                2
+            ))
+        );
+
+        let out_of_range: Relation =
+            LinearRelation::new(b"opening", 1).with_equation(c, vec![(1, h)]);
+        assert_eq!(
+            Prover::create_proof(
+                rng,
+                &out_of_range,
+                &vec![r],
+                &mut FS::from_seed(b"Initialised with some input")
+            ),
+            Err(CryptoError::InvalidLinearRelation)
+        );
+    }
+
+    #[test]
+    fn test_and() {
+        let rng = &mut thread_rng();
+        let g = random_point(rng);
+        let (x_0, x_1) = (Scalar::rand(rng), Scalar::rand(rng));
+        let statement = And(
+            discrete_log(g, g.mul(x_0).into_affine()),
+            discrete_log(g, g.mul(x_1).into_affine()),
+        );
+
+        let proof = prove(&statement, &(vec![x_0], vec![x_1]));
+        assert_eq!(verify(&statement, &proof), Ok(()));
+
+        let invalid_proof = prove(&statement, &(vec![x_0], vec![x_0]));
+        assert!(verify(&statement, &invalid_proof).is_err());
+    }
+
+    #[test]
+    fn test_compact_proof() {
+        let rng = &mut thread_rng();
+        let g = random_point(rng);
+        let (x_0, x_1) = (Scalar::rand(rng), Scalar::rand(rng));
+        let statement = And(
+            discrete_log(g, g.mul(x_0).into_affine()),
+            discrete_log(g, g.mul(x_1).into_affine()),
+        );
+
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        let proof =
+            Prover::create_compact_proof(rng, &statement, &(vec![x_0], vec![x_1]), &mut fs_rng)
+                .unwrap();
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(proof.verify(&statement, &mut fs_rng), Ok(()));
+
+        let mut forged_proof = proof.clone();
+        forged_proof.challenge += Scalar::from(1u64);
+        let mut fs_rng = FS::from_seed(b"Initialised with some input");
+        assert_eq!(
+            forged_proof.verify(&statement, &mut fs_rng),
+            Err(CryptoError::ProofVerificationError(String::from(
+                "Sigma protocol"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_or() {
+        let rng = &mut thread_rng();
+        let g = random_point(rng);
+        let x = Scalar::rand(rng);
+        let known = discrete_log(g, g.mul(x).into_affine());
+        let mut unknown = || discrete_log(g, random_point(rng));
+
+        // The witness of either branch is enough
+        let statement = Or(known.clone(), unknown());
+        let proof = prove(&statement, &OrWitness::Left(vec![x]));
+        assert_eq!(verify(&statement, &proof), Ok(()));
+
+        let statement = Or(unknown(), known.clone());
+        let proof = prove(&statement, &OrWitness::Right(vec![x]));
+        assert_eq!(verify(&statement, &proof), Ok(()));
+
+        // Branch challenges must add up to the Fiat-Shamir challenge
+        let mut forged_proof = proof.clone();
+        forged_proof.response.0 += Scalar::from(1u64);
+        assert!(verify(&statement, &forged_proof).is_err());
+
+        // Compositions nest
+        let statement = Or(And(unknown(), known.clone()), known.clone());
+        let proof = prove(&statement, &OrWitness::Right(vec![x]));
+        assert_eq!(verify(&statement, &proof), Ok(()));
+
+        // Without any witness
+        let statement = Or(unknown(), unknown());
+        let proof = prove(&statement, &OrWitness::Left(vec![x]));
+        assert!(verify(&statement, &proof).is_err());
+
+        // The response must use the witness of the branch the commitment was made for
+        let statement = Or(known.clone(), known);
+        let (state, _) = statement.commit(rng, &OrWitness::Left(vec![x])).unwrap();
+        assert_eq!(
+            statement
+                .respond(&OrWitness::Right(vec![x]), state, &Scalar::rand(rng))
+                .err(),
+            Some(CryptoError::InvalidSigmaState)
+        );
+    }
+}